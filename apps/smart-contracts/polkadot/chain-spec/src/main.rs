@@ -0,0 +1,94 @@
+//! Builds chain spec JSON from the todo runtime's named genesis presets.
+//!
+//! ```bash
+//! todo-chain-spec --list
+//! todo-chain-spec --preset e2e --raw --output e2e.json
+//! ```
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use sc_chain_spec::{ChainType, GenericChainSpec};
+use todo_runtime::{
+    genesis_config_presets::{
+        get_preset, preset_names, DEV_RUNTIME_PRESET, E2E_RUNTIME_PRESET,
+        LOCAL_TESTNET_RUNTIME_PRESET,
+    },
+    RuntimeGenesisConfig, WASM_BINARY,
+};
+
+type ChainSpec = GenericChainSpec<RuntimeGenesisConfig>;
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Genesis preset to build the chain spec from
+    #[arg(long, default_value = DEV_RUNTIME_PRESET)]
+    preset: String,
+
+    /// Emit a raw chain spec, with the genesis storage already built
+    #[arg(long)]
+    raw: bool,
+
+    /// Write the chain spec to this file instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+
+    /// List the available presets and exit
+    #[arg(long)]
+    list: bool,
+}
+
+/// Name, id and chain type of the chain spec built from `preset`.
+fn chain_properties(preset: &str) -> Option<(&'static str, &'static str, ChainType)> {
+    match preset {
+        DEV_RUNTIME_PRESET => Some(("Development", "dev", ChainType::Development)),
+        LOCAL_TESTNET_RUNTIME_PRESET => Some(("Local Testnet", "local_testnet", ChainType::Local)),
+        E2E_RUNTIME_PRESET => Some(("End-to-End", "e2e", ChainType::Development)),
+        _ => None,
+    }
+}
+
+fn main() -> Result<(), String> {
+    let cli = Cli::parse();
+
+    if cli.list {
+        for preset in preset_names() {
+            println!("{preset}");
+        }
+        return Ok(())
+    }
+
+    let (name, id, chain_type) = chain_properties(&cli.preset)
+        .ok_or_else(|| format!("unknown preset `{}`, see --list", cli.preset))?;
+    let wasm_binary = WASM_BINARY.ok_or("the runtime was built without its wasm binary")?;
+    let preset = cli.preset.clone();
+    let chain_spec = ChainSpec::from_genesis(
+        name,
+        id,
+        chain_type,
+        move || get_preset(&preset, wasm_binary).expect("the preset was checked above; qed"),
+        // Bootnodes
+        vec![],
+        // Telemetry
+        None,
+        // Protocol ID
+        None,
+        // Fork ID
+        None,
+        // Properties
+        None,
+        // Extensions
+        None,
+    );
+    let json = chain_spec.as_json(cli.raw)?;
+
+    match cli.output {
+        Some(path) => std::fs::write(&path, json)
+            .map_err(|e| format!("failed to write {}: {e}", path.display())),
+        None => {
+            println!("{json}");
+            Ok(())
+        },
+    }
+}
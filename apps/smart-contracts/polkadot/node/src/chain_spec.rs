@@ -0,0 +1,54 @@
+use sc_service::ChainType;
+use todo_runtime::{
+    genesis_config_presets::{
+        get_preset, DEV_RUNTIME_PRESET, E2E_RUNTIME_PRESET, LOCAL_TESTNET_RUNTIME_PRESET,
+    },
+    RuntimeGenesisConfig, WASM_BINARY,
+};
+
+/// Specialized `ChainSpec` for the todo runtime.
+pub type ChainSpec = sc_service::GenericChainSpec<RuntimeGenesisConfig>;
+
+/// Build a chain spec from one of the runtime's genesis presets.
+fn from_preset(
+    name: &str,
+    id: &str,
+    chain_type: ChainType,
+    preset: &'static str,
+) -> Result<ChainSpec, String> {
+    let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+
+    Ok(ChainSpec::from_genesis(
+        name,
+        id,
+        chain_type,
+        move || get_preset(preset, wasm_binary).expect("the presets of this module exist; qed"),
+        // Bootnodes
+        vec![],
+        // Telemetry
+        None,
+        // Protocol ID
+        None,
+        // Fork ID
+        None,
+        // Properties
+        None,
+        // Extensions
+        None,
+    ))
+}
+
+/// A single-authority chain for local development, used by `--dev`.
+pub fn development_config() -> Result<ChainSpec, String> {
+    from_preset("Development", "dev", ChainType::Development, DEV_RUNTIME_PRESET)
+}
+
+/// A two-authority chain (Alice and Bob) for running a local network.
+pub fn local_testnet_config() -> Result<ChainSpec, String> {
+    from_preset("Local Testnet", "local_testnet", ChainType::Local, LOCAL_TESTNET_RUNTIME_PRESET)
+}
+
+/// A development chain with the todo fixtures end-to-end tests expect.
+pub fn e2e_config() -> Result<ChainSpec, String> {
+    from_preset("End-to-End", "e2e", ChainType::Development, E2E_RUNTIME_PRESET)
+}
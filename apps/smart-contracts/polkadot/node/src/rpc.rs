@@ -0,0 +1,59 @@
+//! RPC methods served by the todo node.
+//!
+//! Extends the core RPC layer from `sc-rpc` with account nonces, fee estimates, todo lists and
+//! the todo reputation queries.
+
+#![warn(missing_docs)]
+
+use std::sync::Arc;
+
+use jsonrpsee::RpcModule;
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::ProvideRuntimeApi;
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use pallet_todo::{Todo, TodoRevision};
+use todo_runtime::{opaque::Block, AccountId, Balance, Nonce, Runtime};
+
+pub use sc_rpc_api::DenyUnsafe;
+
+/// Full client dependencies.
+pub struct FullDeps<C, P> {
+    /// The client instance to use.
+    pub client: Arc<C>,
+    /// Transaction pool instance.
+    pub pool: Arc<P>,
+    /// Whether to deny unsafe calls
+    pub deny_unsafe: DenyUnsafe,
+}
+
+/// Instantiate all full RPC extensions.
+pub fn create_full<C, P>(
+    deps: FullDeps<C, P>,
+) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
+where
+    C: ProvideRuntimeApi<Block>,
+    C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+    C: Send + Sync + 'static,
+    C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+    C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: pallet_todo_rpc::TodoRuntimeApi<Block, AccountId, Todo<Runtime>, TodoRevision<Runtime>>,
+    C::Api: pallet_todo_reputation_rpc::TodoReputationRuntimeApi<Block, AccountId>,
+    C::Api: BlockBuilder<Block>,
+    P: TransactionPool + 'static,
+{
+    use pallet_todo_reputation_rpc::{TodoReputation, TodoReputationApiServer};
+    use pallet_todo_rpc::{TodoApiServer, Todos};
+    use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+    use substrate_frame_rpc_system::{System, SystemApiServer};
+
+    let mut module = RpcModule::new(());
+    let FullDeps { client, pool, deny_unsafe } = deps;
+
+    module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(Todos::<_, _, Todo<Runtime>, TodoRevision<Runtime>>::new(client.clone()).into_rpc())?;
+    module.merge(TodoReputation::new(client).into_rpc())?;
+
+    Ok(module)
+}
@@ -0,0 +1,101 @@
+//! JSON-RPC interface for the todo reputation pallet.
+//!
+//! Wraps the `TodoReputationApi` runtime API so clients can read points, streaks and the
+//! leaderboard without decoding storage themselves.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_todo_reputation_runtime_api::TodoReputationApi as TodoReputationRuntimeApi;
+
+/// Todo reputation RPC methods
+#[rpc(client, server)]
+pub trait TodoReputationApi<BlockHash, AccountId> {
+    /// Total points currently held by an account
+    #[method(name = "todo_points")]
+    fn points(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<u64>;
+
+    /// The account's streak of consecutive completion days, or 0 if it has lapsed
+    #[method(name = "todo_activeStreak")]
+    fn active_streak(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<u32>;
+
+    /// The longest streak the account has ever reached
+    #[method(name = "todo_longestStreak")]
+    fn longest_streak(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<u32>;
+
+    /// The leaderboard, ordered from the highest score down
+    #[method(name = "todo_leaderboard")]
+    fn leaderboard(&self, at: Option<BlockHash>) -> RpcResult<Vec<(AccountId, u64)>>;
+}
+
+/// Error code for failed runtime API calls
+const RUNTIME_ERROR: i32 = 1;
+
+/// Implements [`TodoReputationApiServer`] on top of a client's runtime API.
+pub struct TodoReputation<C, Block> {
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> TodoReputation<C, Block> {
+    /// Create a new instance serving queries from `client`.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+/// Converts a runtime API failure into an RPC error.
+fn runtime_error(message: &'static str, error: impl std::fmt::Display) -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(error.to_string()))).into()
+}
+
+impl<C, Block, AccountId> TodoReputationApiServer<<Block as BlockT>::Hash, AccountId>
+    for TodoReputation<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: TodoReputationRuntimeApi<Block, AccountId>,
+    AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn points(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<u64> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .points(at, who)
+            .map_err(|e| runtime_error("Unable to query points.", e))
+    }
+
+    fn active_streak(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<u32> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .active_streak(at, who)
+            .map_err(|e| runtime_error("Unable to query the active streak.", e))
+    }
+
+    fn longest_streak(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<u32> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .longest_streak(at, who)
+            .map_err(|e| runtime_error("Unable to query the longest streak.", e))
+    }
+
+    fn leaderboard(&self, at: Option<Block::Hash>) -> RpcResult<Vec<(AccountId, u64)>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .leaderboard(at)
+            .map_err(|e| runtime_error("Unable to query the leaderboard.", e))
+    }
+}
@@ -0,0 +1,26 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API definition for the todo reputation pallet.
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// Read-only access to productivity points, streaks and the leaderboard
+    pub trait TodoReputationApi<AccountId>
+    where
+        AccountId: Codec,
+    {
+        /// Total points currently held by an account
+        fn points(who: AccountId) -> u64;
+
+        /// The account's streak of consecutive completion days, or 0 if it has lapsed
+        fn active_streak(who: AccountId) -> u32;
+
+        /// The longest streak the account has ever reached
+        fn longest_streak(who: AccountId) -> u32;
+
+        /// The leaderboard, ordered from the highest score down
+        fn leaderboard() -> Vec<(AccountId, u64)>;
+    }
+}
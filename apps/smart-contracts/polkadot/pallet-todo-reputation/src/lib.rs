@@ -0,0 +1,342 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// A companion pallet to `pallet-todo` that turns todo completions into productivity points
+/// and daily streaks. It is notified through `pallet_todo::TodoCompletionHandler`, so a
+/// completion that is reverted with `toggle_todo_completion` gives its points back.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::{pallet_prelude::*, traits::Time, weights::Weight};
+    use pallet_todo::{Priority, TodoCompletionHandler};
+    use sp_runtime::traits::{UniqueSaturatedInto, Zero};
+    use sp_std::prelude::*;
+
+    /// Configure the pallet by specifying the parameters and types on which it depends.
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_todo::Config {
+        /// Because this pallet emits events, it depends on the runtime's definition of an event.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Points awarded for completing a low priority todo; medium, high priority and urgent
+        /// completions are worth two, three and four times as much
+        #[pallet::constant]
+        type PointsPerCompletion: Get<u32>;
+
+        /// Length of a streak day, in the units of `pallet_todo::Config::Moment`
+        #[pallet::constant]
+        type MomentsPerDay: Get<<Self as pallet_todo::Config>::Moment>;
+
+        /// Maximum number of accounts kept on the leaderboard
+        #[pallet::constant]
+        type MaxLeaderboardSize: Get<u32>;
+    }
+
+    /// Points granted for a single completion, kept so they can be revoked again
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct AwardRecord {
+        /// Number of points that were awarded
+        pub points: u32,
+        /// Day index on which the completion happened
+        pub day: u32,
+        /// Whether the award came with a transferred todo, so `day` is a day of the sender's
+        /// streak rather than of the holder's
+        pub transferred: bool,
+    }
+
+    /// Daily completion streak of an account
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+    pub struct StreakInfo {
+        /// Number of consecutive days, ending at `last_day`, with at least one completion
+        pub current: u32,
+        /// Longest streak ever reached
+        pub longest: u32,
+        /// Day index of the most recent day that counted towards the streak
+        pub last_day: u32,
+        /// Longest of the streaks that ended before the current one started
+        pub longest_before: u32,
+    }
+
+    impl StreakInfo {
+        /// Count `day`, the first day with a completion since the streak last changed
+        pub fn record_day(&mut self, day: u32) {
+            if self.current > 0 && day == self.last_day {
+                return
+            }
+
+            if self.current > 0 && day == self.last_day.saturating_add(1) {
+                self.current = self.current.saturating_add(1);
+            } else {
+                // A new streak starts and the previous one becomes history
+                self.longest_before = self.longest;
+                self.current = 1;
+            }
+            self.last_day = day;
+            self.longest = self.longest.max(self.current);
+        }
+
+        /// Take `day`, whose last standing completion was reverted, out of the streak.
+        ///
+        /// A day inside the current streak splits it in two: the days after it remain the
+        /// current streak and the days before it count as a streak that has ended. Days of
+        /// streaks that ended before the current one are left as they were.
+        pub fn revert_day(&mut self, day: u32) {
+            let start = self.last_day.saturating_add(1).saturating_sub(self.current);
+            if self.current == 0 || day < start || day > self.last_day {
+                return
+            }
+
+            if day == self.last_day {
+                self.current -= 1;
+                self.last_day = day.saturating_sub(1);
+            } else {
+                self.longest_before = self.longest_before.max(day - start);
+                self.current = self.last_day - day;
+            }
+            self.longest = self.longest_before.max(self.current);
+        }
+
+        /// The streak as seen on `today`: zero once a full day has passed without a completion
+        pub fn active_on(&self, today: u32) -> u32 {
+            if self.last_day.saturating_add(1) >= today {
+                self.current
+            } else {
+                0
+            }
+        }
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Total points, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn points)]
+    pub type Points<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        u64,
+        ValueQuery,
+    >;
+
+    /// Completion streaks, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn streaks)]
+    pub type Streaks<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        StreakInfo,
+        ValueQuery,
+    >;
+
+    /// Points awarded for currently completed todos, keyed by account ID and todo ID
+    #[pallet::storage]
+    #[pallet::getter(fn awards)]
+    pub type Awards<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u64,
+        AwardRecord,
+        OptionQuery,
+    >;
+
+    /// Number of completions still standing, keyed by account ID and day index. Days without
+    /// any are not stored.
+    #[pallet::storage]
+    #[pallet::getter(fn daily_completions)]
+    pub type DailyCompletions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u32,
+        u32,
+        ValueQuery,
+    >;
+
+    /// Accounts with the most points, ordered from the highest score down
+    #[pallet::storage]
+    #[pallet::getter(fn leaderboard)]
+    pub type Leaderboard<T: Config> = StorageValue<
+        _,
+        BoundedVec<(T::AccountId, u64), T::MaxLeaderboardSize>,
+        ValueQuery,
+    >;
+
+    // Pallets use events to inform users when important changes are made.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Points were awarded for completing a todo
+        PointsAwarded { who: T::AccountId, id: u64, points: u32 },
+        /// Points were revoked because a completion was reverted
+        PointsRevoked { who: T::AccountId, id: u64, points: u32 },
+        /// An account's completion streak changed
+        StreakUpdated { who: T::AccountId, current: u32 },
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Points a completion of the given priority is worth
+        pub fn points_for(priority: Priority) -> u32 {
+            let multiplier = match priority {
+                Priority::Low => 1,
+                Priority::Medium => 2,
+                Priority::High => 3,
+                Priority::Urgent => 4,
+            };
+            T::PointsPerCompletion::get().saturating_mul(multiplier)
+        }
+
+        /// Index of the current day, counted from the start of `T::TimeProvider`
+        pub fn current_day() -> u32 {
+            let day_length = T::MomentsPerDay::get();
+            if day_length.is_zero() {
+                return 0
+            }
+            let now = <T as pallet_todo::Config>::TimeProvider::now();
+            (now / day_length).unique_saturated_into()
+        }
+
+        /// The account's streak, or zero if it has lapsed
+        pub fn active_streak(who: &T::AccountId) -> u32 {
+            Self::streaks(who).active_on(Self::current_day())
+        }
+
+        /// Move an account to its place on the leaderboard
+        fn update_leaderboard(who: &T::AccountId, total: u64) {
+            let mut board = Self::leaderboard().into_inner();
+            board.retain(|(account, _)| account != who);
+
+            if total > 0 {
+                let index = board.iter().position(|(_, points)| *points < total).unwrap_or(board.len());
+                board.insert(index, (who.clone(), total));
+            }
+
+            Leaderboard::<T>::put(BoundedVec::truncate_from(board));
+        }
+
+        /// Count a completion on `day`, returning whether it is the first one of that day
+        fn count_completion(who: &T::AccountId, day: u32) -> bool {
+            DailyCompletions::<T>::mutate(who, day, |count| {
+                *count = count.saturating_add(1);
+                *count == 1
+            })
+        }
+
+        /// Take back a completion counted on `day`, returning whether it was the last one
+        fn uncount_completion(who: &T::AccountId, day: u32) -> bool {
+            DailyCompletions::<T>::mutate_exists(who, day, |count| match count {
+                Some(1) => {
+                    *count = None;
+                    true
+                },
+                Some(count) => {
+                    *count -= 1;
+                    false
+                },
+                None => false,
+            })
+        }
+
+        /// Emit a streak event if the current streak changed
+        fn note_streak_change(who: &T::AccountId, before: u32, after: u32) {
+            if before != after {
+                Self::deposit_event(Event::StreakUpdated { who: who.clone(), current: after });
+            }
+        }
+    }
+
+    impl<T: Config> TodoCompletionHandler<T::AccountId> for Pallet<T> {
+        fn on_completed(who: &T::AccountId, id: u64, priority: Priority) {
+            // A todo can only hold one award at a time
+            if Awards::<T>::contains_key(who, id) {
+                return
+            }
+
+            let points = Self::points_for(priority);
+            let day = Self::current_day();
+            Awards::<T>::insert(who, id, AwardRecord { points, day, transferred: false });
+
+            let total = Points::<T>::mutate(who, |total| {
+                *total = total.saturating_add(points as u64);
+                *total
+            });
+            Self::update_leaderboard(who, total);
+
+            let (before, after) = Streaks::<T>::mutate(who, |streak| {
+                let before = streak.current;
+                if Self::count_completion(who, day) {
+                    streak.record_day(day);
+                }
+                (before, streak.current)
+            });
+
+            Self::deposit_event(Event::PointsAwarded { who: who.clone(), id, points });
+            Self::note_streak_change(who, before, after);
+        }
+
+        fn on_reopened(who: &T::AccountId, id: u64, _priority: Priority) {
+            let Some(award) = Awards::<T>::take(who, id) else { return };
+
+            let total = Points::<T>::mutate(who, |total| {
+                *total = total.saturating_sub(award.points as u64);
+                *total
+            });
+            Self::update_leaderboard(who, total);
+
+            let (before, after) = Streaks::<T>::mutate(who, |streak| {
+                let before = streak.current;
+                if !award.transferred && Self::uncount_completion(who, award.day) {
+                    streak.revert_day(award.day);
+                }
+                (before, streak.current)
+            });
+
+            Self::deposit_event(Event::PointsRevoked { who: who.clone(), id, points: award.points });
+            Self::note_streak_change(who, before, after);
+        }
+
+        fn on_removed(who: &T::AccountId, id: u64) {
+            // Points and the streak day of a completed todo are kept once it is purged
+            Awards::<T>::remove(who, id);
+        }
+
+        fn on_transferred(from: &T::AccountId, old_id: u64, to: &T::AccountId, new_id: u64) {
+            // The award and its points move with the todo, so the recipient cannot earn them
+            // again by reopening and completing it; streaks stay with the account that completed it
+            let Some(award) = Awards::<T>::take(from, old_id) else { return };
+            let points = award.points;
+            Awards::<T>::insert(to, new_id, AwardRecord { transferred: true, ..award });
+
+            let total = Points::<T>::mutate(from, |total| {
+                *total = total.saturating_sub(points as u64);
+                *total
+            });
+            Self::update_leaderboard(from, total);
+            let total = Points::<T>::mutate(to, |total| {
+                *total = total.saturating_add(points as u64);
+                *total
+            });
+            Self::update_leaderboard(to, total);
+
+            Self::deposit_event(Event::PointsRevoked { who: from.clone(), id: old_id, points });
+            Self::deposit_event(Event::PointsAwarded { who: to.clone(), id: new_id, points });
+        }
+
+        fn weight() -> Weight {
+            // `on_transferred` is the heaviest hook: the award, both point totals and the
+            // leaderboard, which is rewritten twice
+            T::DbWeight::get().reads_writes(5, 6)
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{mock::*, Event};
+use frame_support::{
+    assert_ok,
+    traits::{Get, OnIdle},
+    weights::Weight,
+};
+use pallet_todo::{Priority, RetentionAction, RetentionPolicy, TodoCompletionHandler, TransferMode};
+
+// Helper function to create a todo
+fn create_test_todo(account: u64, priority: Priority) {
+    assert_ok!(TodoPallet::create_todo(
+        RuntimeOrigin::signed(account),
+        b"Test Todo".to_vec(),
+        b"This is a test todo".to_vec(),
+        priority
+    ));
+}
+
+// Helper function to toggle a todo's completion status
+fn toggle(account: u64, id: u64) {
+    assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), id));
+}
+
+// Helper function to move to the first block of a given day
+fn go_to_day(day: u64) {
+    System::set_block_number(day * BLOCKS_PER_DAY + 1);
+}
+
+#[test]
+fn completion_awards_points_weighted_by_priority() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, Priority::Low);
+        create_test_todo(account, Priority::Medium);
+        create_test_todo(account, Priority::High);
+        create_test_todo(account, Priority::Urgent);
+
+        toggle(account, 0);
+        assert_eq!(Reputation::points(account), 10);
+
+        toggle(account, 1);
+        assert_eq!(Reputation::points(account), 30);
+
+        toggle(account, 2);
+        assert_eq!(Reputation::points(account), 60);
+
+        toggle(account, 3);
+        assert_eq!(Reputation::points(account), 100);
+
+        // Check that the event was emitted
+        System::assert_has_event(Event::PointsAwarded { who: account, id: 3, points: 40 }.into());
+    });
+}
+
+#[test]
+fn toggling_back_and_forth_does_not_farm_points() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, Priority::High);
+
+        // Complete and reopen the same todo several times
+        for _ in 0..5 {
+            toggle(account, 0);
+            toggle(account, 0);
+        }
+
+        // Nothing is left over once the todo is open again
+        assert_eq!(Reputation::points(account), 0);
+        assert_eq!(Reputation::awards(account, 0), None);
+        assert_eq!(Reputation::streaks(account).current, 0);
+        assert!(Reputation::leaderboard().is_empty());
+        System::assert_has_event(Event::PointsRevoked { who: account, id: 0, points: 30 }.into());
+
+        // A final completion counts exactly once
+        toggle(account, 0);
+        assert_eq!(Reputation::points(account), 30);
+    });
+}
+
+#[test]
+fn deleting_a_completed_todo_keeps_its_points() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, Priority::Medium);
+        toggle(account, 0);
+
+        // A deleted todo keeps its award while it can still be restored
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 0));
+        assert!(Reputation::awards(account, 0).is_some());
+        assert_ok!(TodoPallet::undelete_todo(RuntimeOrigin::signed(account), 0));
+        assert!(Reputation::awards(account, 0).is_some());
+
+        // The award is dropped once the todo is purged
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 0));
+        System::set_block_number(11);
+        TodoPallet::on_idle(11, Weight::MAX);
+
+        assert_eq!(Reputation::points(account), 20);
+        assert_eq!(Reputation::awards(account, 0), None);
+    });
+}
+
+#[test]
+fn transferred_completed_todos_carry_their_award() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let (from, to) = (1, 2);
+        create_test_todo(to, Priority::Low);
+        create_test_todo(from, Priority::Medium);
+        toggle(from, 0);
+        assert_eq!(Reputation::points(from), 20);
+
+        assert_ok!(TodoPallet::transfer_list(RuntimeOrigin::signed(from), to));
+        assert_ok!(TodoPallet::accept_list_transfer(RuntimeOrigin::signed(to), from, TransferMode::Merge));
+
+        // The award and its points moved with the todo, which is todo 1 of the recipient
+        assert_eq!(Reputation::awards(from, 0), None);
+        assert_eq!(Reputation::points(from), 0);
+        assert_eq!(Reputation::awards(to, 1).map(|award| award.points), Some(20));
+        assert_eq!(Reputation::points(to), 20);
+        assert_eq!(Reputation::leaderboard().into_inner(), vec![(to, 20)]);
+
+        // Reopening and completing it again earns the recipient nothing extra
+        toggle(to, 1);
+        assert_eq!(Reputation::points(to), 0);
+        toggle(to, 1);
+        assert_eq!(Reputation::points(to), 20);
+    });
+}
+
+#[test]
+fn transferred_completions_do_not_count_towards_the_recipients_streak() {
+    new_test_ext().execute_with(|| {
+        go_to_day(1);
+
+        let (from, to) = (1, 2);
+        create_test_todo(to, Priority::Low);
+        create_test_todo(from, Priority::Medium);
+        toggle(from, 0);
+
+        assert_ok!(TodoPallet::transfer_list(RuntimeOrigin::signed(from), to));
+        assert_ok!(TodoPallet::accept_list_transfer(RuntimeOrigin::signed(to), from, TransferMode::Merge));
+
+        // The completion stays on the sender's day and streak
+        assert_eq!(Reputation::awards(to, 1).map(|award| award.transferred), Some(true));
+        assert_eq!(Reputation::daily_completions(to, 1), 0);
+        assert_eq!(Reputation::streaks(to).current, 0);
+        assert_eq!(Reputation::streaks(from).current, 1);
+
+        // The recipient's own completion on the same day starts its streak
+        toggle(to, 0);
+        assert_eq!(Reputation::daily_completions(to, 1), 1);
+        assert_eq!(Reputation::streaks(to).current, 1);
+
+        // Reopening the transferred todo leaves that day of the recipient's streak alone
+        toggle(to, 1);
+        assert_eq!(Reputation::daily_completions(to, 1), 1);
+        assert_eq!(Reputation::streaks(to).current, 1);
+
+        // A completion the next day extends the streak
+        go_to_day(2);
+        toggle(to, 1);
+        assert_eq!(Reputation::streaks(to).current, 2);
+    });
+}
+
+#[test]
+fn streak_grows_on_consecutive_days_and_lapses_after_a_gap() {
+    new_test_ext().execute_with(|| {
+        let account = 1;
+
+        go_to_day(0);
+        for _ in 0..4 {
+            create_test_todo(account, Priority::Low);
+        }
+
+        // Two completions on the same day only count once towards the streak
+        toggle(account, 0);
+        toggle(account, 1);
+        assert_eq!(Reputation::streaks(account).current, 1);
+
+        go_to_day(1);
+        toggle(account, 2);
+        assert_eq!(Reputation::streaks(account).current, 2);
+        assert_eq!(Reputation::active_streak(&account), 2);
+        System::assert_has_event(Event::StreakUpdated { who: account, current: 2 }.into());
+
+        // After a full day without completions the streak has lapsed
+        go_to_day(3);
+        assert_eq!(Reputation::active_streak(&account), 0);
+
+        toggle(account, 3);
+        let streak = Reputation::streaks(account);
+        assert_eq!(streak.current, 1);
+        assert_eq!(streak.longest, 2);
+    });
+}
+
+#[test]
+fn reverting_the_only_completion_of_a_day_undoes_the_streak_day() {
+    new_test_ext().execute_with(|| {
+        let account = 1;
+
+        go_to_day(0);
+        create_test_todo(account, Priority::Low);
+        create_test_todo(account, Priority::Low);
+        toggle(account, 0);
+
+        go_to_day(1);
+        toggle(account, 1);
+        assert_eq!(Reputation::streaks(account).current, 2);
+        assert_eq!(Reputation::streaks(account).longest, 2);
+
+        // Reopening today's completion takes the day away again
+        toggle(account, 1);
+        let streak = Reputation::streaks(account);
+        assert_eq!(streak.current, 1);
+        assert_eq!(streak.longest, 1);
+        assert_eq!(streak.last_day, 0);
+        assert_eq!(Reputation::active_streak(&account), 1);
+    });
+}
+
+#[test]
+fn reverting_an_earlier_day_splits_the_streak() {
+    new_test_ext().execute_with(|| {
+        let account = 1;
+
+        go_to_day(0);
+        for _ in 0..5 {
+            create_test_todo(account, Priority::Low);
+        }
+        toggle(account, 0);
+
+        go_to_day(1);
+        toggle(account, 1);
+        toggle(account, 2);
+
+        go_to_day(2);
+        toggle(account, 3);
+
+        go_to_day(3);
+        toggle(account, 4);
+        assert_eq!(Reputation::streaks(account).current, 4);
+
+        // Day 1 still has one of its two completions, so the streak is unchanged
+        toggle(account, 1);
+        assert_eq!(Reputation::streaks(account).current, 4);
+        assert_eq!(Reputation::daily_completions(account, 1), 1);
+
+        // Reopening the last completion of day 1 leaves days 2 and 3 as the current streak
+        toggle(account, 2);
+        let streak = Reputation::streaks(account);
+        assert_eq!(streak.current, 2);
+        assert_eq!(streak.longest, 2);
+        assert_eq!(streak.last_day, 3);
+        assert_eq!(Reputation::daily_completions(account, 1), 0);
+        assert_eq!(Reputation::active_streak(&account), 2);
+        System::assert_has_event(Event::StreakUpdated { who: account, current: 2 }.into());
+
+        // Once a new streak has started, reverting a day of an ended one changes nothing
+        go_to_day(5);
+        toggle(account, 1);
+        toggle(account, 0);
+        let streak = Reputation::streaks(account);
+        assert_eq!(streak.current, 1);
+        assert_eq!(streak.longest, 2);
+        assert_eq!(streak.last_day, 5);
+    });
+}
+
+#[test]
+fn leaderboard_is_ordered_and_bounded() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        // Accounts 1 to 4 each complete one todo of increasing priority
+        let priorities = [Priority::Low, Priority::Medium, Priority::High, Priority::High];
+        for (index, priority) in priorities.into_iter().enumerate() {
+            let account = index as u64 + 1;
+            create_test_todo(account, priority);
+            toggle(account, 0);
+        }
+
+        // Only the top three accounts are kept, highest score first
+        let board = Reputation::leaderboard().into_inner();
+        assert_eq!(board, vec![(3, 30), (4, 30), (2, 20)]);
+
+        // Reopening a todo moves the account down the board
+        toggle(3, 0);
+        let board = Reputation::leaderboard().into_inner();
+        assert_eq!(board, vec![(4, 30), (2, 20)]);
+    });
+}
+
+#[test]
+fn retention_sweep_leaves_room_for_the_reputation_hooks() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        // Two accounts with a completed todo each, pruned as soon as possible
+        let policy = RetentionPolicy { max_age: 0, action: RetentionAction::Delete };
+        for account in [1, 2] {
+            assert_ok!(TodoPallet::set_retention_policy(RuntimeOrigin::signed(account), Some(policy.clone())));
+            create_test_todo(account, Priority::Medium);
+            toggle(account, 0);
+        }
+        System::set_block_number(2);
+
+        // Every todo of an account may notify the reputation pallet
+        let db = <Test as frame_system::Config>::DbWeight::get();
+        let max_todos = <Test as pallet_todo::Config>::MaxTodosPerAccount::get() as u64;
+        let per_todo = db.reads_writes(2, 5).saturating_add(<Reputation as TodoCompletionHandler<u64>>::weight());
+        let per_account = db.reads_writes(3, 3).saturating_add(per_todo.saturating_mul(max_todos));
+        let room = db.reads_writes(1, 1).saturating_mul(2).saturating_add(per_account);
+        let remaining = || [1, 2].iter().filter(|a| !TodoPallet::todos(**a).is_empty()).count();
+
+        // Without room for the hooks no account is swept
+        assert_eq!(TodoPallet::on_idle(2, room.saturating_sub(Weight::from_parts(1, 0))), db.reads_writes(1, 1));
+        assert_eq!(remaining(), 2);
+
+        // With room for one account, exactly one is swept
+        assert_eq!(TodoPallet::on_idle(2, room), room);
+        assert_eq!(remaining(), 1);
+    });
+}
@@ -0,0 +1,114 @@
+//! JSON-RPC interface for the todo pallet.
+//!
+//! Wraps the `TodoApi` runtime API so clients can read sorted todo lists, statistics and todo
+//! history at any block. Todos and revisions are generic over the runtime, so they are returned
+//! SCALE encoded and decoded with the runtime's metadata.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::{Codec, Encode};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_todo_runtime_api::{TodoApi as TodoRuntimeApi, TodoSort, TodoStatistics};
+
+/// Todo RPC methods
+#[rpc(client, server)]
+pub trait TodoApi<BlockHash, AccountId> {
+    /// The account's todos in the given order, as a SCALE encoded `Vec<Todo>`
+    #[method(name = "todo_list")]
+    fn list(&self, who: AccountId, sort: TodoSort, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+    /// The account's todo statistics, with the pending todos counted per priority
+    #[method(name = "todo_stats")]
+    fn stats(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<TodoStatistics>;
+
+    /// The kept revisions of one of the account's todos, oldest first, as a SCALE encoded
+    /// `Vec<TodoRevision>`
+    #[method(name = "todo_history")]
+    fn history(&self, who: AccountId, id: u64, at: Option<BlockHash>) -> RpcResult<Bytes>;
+}
+
+/// Error code for failed runtime API calls
+const RUNTIME_ERROR: i32 = 1;
+
+/// Error code for methods the runtime at the queried block does not support
+const UNSUPPORTED_ERROR: i32 = 2;
+
+/// Implements [`TodoApiServer`] on top of a client's runtime API.
+///
+/// `Todo` and `Revision` are the runtime's todo and revision types, as used in its `TodoApi`.
+pub struct Todos<C, Block, Todo, Revision> {
+    client: Arc<C>,
+    _marker: PhantomData<(Block, Todo, Revision)>,
+}
+
+impl<C, Block, Todo, Revision> Todos<C, Block, Todo, Revision> {
+    /// Create a new instance serving queries from `client`.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+/// Converts a runtime API failure into an RPC error.
+fn runtime_error(message: &'static str, error: impl std::fmt::Display) -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(error.to_string()))).into()
+}
+
+impl<C, Block, AccountId, Todo, Revision> TodoApiServer<<Block as BlockT>::Hash, AccountId>
+    for Todos<C, Block, Todo, Revision>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: TodoRuntimeApi<Block, AccountId, Todo, Revision>,
+    AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+    Todo: Codec + Send + Sync + 'static,
+    Revision: Codec + Send + Sync + 'static,
+{
+    fn list(&self, who: AccountId, sort: TodoSort, at: Option<Block::Hash>) -> RpcResult<Bytes> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .todos(at, who, sort)
+            .map(|todos| todos.encode().into())
+            .map_err(|e| runtime_error("Unable to query todos.", e))
+    }
+
+    fn stats(&self, who: AccountId, at: Option<Block::Hash>) -> RpcResult<TodoStatistics> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .stats(at, who)
+            .map_err(|e| runtime_error("Unable to query todo statistics.", e))
+    }
+
+    fn history(&self, who: AccountId, id: u64, at: Option<Block::Hash>) -> RpcResult<Bytes> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        let api = self.client.runtime_api();
+
+        // History was added in version 2 of the runtime API
+        let version = api
+            .api_version::<dyn TodoRuntimeApi<Block, AccountId, Todo, Revision>>(at)
+            .map_err(|e| runtime_error("Unable to query the todo API version.", e))?;
+        if version.unwrap_or(0) < 2 {
+            return Err(CallError::Custom(ErrorObject::owned(
+                UNSUPPORTED_ERROR,
+                "The runtime does not keep todo history.",
+                None::<()>,
+            ))
+            .into())
+        }
+
+        api.history(at, who, id)
+            .map(|history| history.encode().into())
+            .map_err(|e| runtime_error("Unable to query todo history.", e))
+    }
+}
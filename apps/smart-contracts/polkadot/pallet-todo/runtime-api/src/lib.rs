@@ -0,0 +1,29 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Runtime API definition for the todo pallet.
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_todo::{TodoSort, TodoStatistics};
+
+sp_api::decl_runtime_apis! {
+    /// Read-only access to an account's todos, statistics and todo history
+    #[api_version(2)]
+    pub trait TodoApi<AccountId, Todo, Revision>
+    where
+        AccountId: Codec,
+        Todo: Codec,
+        Revision: Codec,
+    {
+        /// The account's todos in the given order
+        fn todos(who: AccountId, sort: TodoSort) -> Vec<Todo>;
+
+        /// The account's todo statistics, with the pending todos counted per priority
+        fn stats(who: AccountId) -> TodoStatistics;
+
+        /// The kept revisions of one of the account's todos, oldest first
+        #[api_version(2)]
+        fn history(who: AccountId, id: u64) -> Vec<Revision>;
+    }
+}
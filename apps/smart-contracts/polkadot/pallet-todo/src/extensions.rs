@@ -0,0 +1,276 @@
+use crate::{Call, Config, DeletedTodos, Pallet, Todos, WrappedKey};
+use codec::{Decode, Encode};
+use frame_support::traits::IsSubType;
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{DispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+};
+use sp_std::marker::PhantomData;
+
+/// Custom `InvalidTransaction` codes returned by this pallet's signed extensions
+#[repr(u8)]
+pub enum ValidityError {
+    /// The sender has used up its todo operations for the current window
+    RateLimited = 1,
+    /// The todo title is too long
+    TitleTooLong = 2,
+    /// The todo description is too long
+    DescriptionTooLong = 3,
+    /// The todo was not found
+    TodoNotFound = 4,
+    /// The sender's todo list is full
+    TodoListFull = 5,
+    /// The encrypted payload is too long
+    CiphertextTooLong = 6,
+    /// The encrypted todo has no recipients or too many of them
+    BadRecipients = 7,
+}
+
+impl From<ValidityError> for TransactionValidityError {
+    fn from(error: ValidityError) -> Self {
+        InvalidTransaction::Custom(error as u8).into()
+    }
+}
+
+/// Rejects rate-limited todo calls before they enter the transaction pool.
+///
+/// The limit itself is enforced at dispatch; this only keeps block authors from spending time
+/// on calls that are bound to fail.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckRateLimit<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckRateLimit<T> {
+    /// Create a new `CheckRateLimit` extension
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Config + Send + Sync> Default for CheckRateLimit<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckRateLimit<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckRateLimit")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckRateLimit<T>
+where
+    <T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+    const IDENTIFIER: &'static str = "CheckRateLimit";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::RuntimeCall;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if let Some(
+            Call::create_todo { .. } |
+            Call::update_todo { .. } |
+            Call::create_encrypted_todo { .. } |
+            Call::update_encrypted_todo { .. } |
+            Call::set_urgency { .. } |
+            Call::set_status { .. } |
+            Call::toggle_todo_completion { .. } |
+            Call::post_board_task { .. },
+        ) = call.is_sub_type()
+        {
+            if Pallet::<T>::is_rate_limited(who) {
+                return Err(ValidityError::RateLimited.into())
+            }
+        }
+
+        Ok(ValidTransaction::default())
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        self.validate(who, call, info, len).map(|_| ())
+    }
+}
+
+/// Runs the length and existence checks of `pallet_todo` calls in `validate`, so invalid todo
+/// transactions never enter the transaction pool.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckTodoCall<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckTodoCall<T> {
+    /// Create a new `CheckTodoCall` extension
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Config + Send + Sync> Default for CheckTodoCall<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckTodoCall<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckTodoCall")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> CheckTodoCall<T> {
+    /// Check a call against the sender's current todo list
+    fn check(who: &T::AccountId, call: &Call<T>) -> Result<(), ValidityError> {
+        let exists = |id: &u64| Todos::<T>::get(who).iter().any(|t| t.id == *id);
+        let check_title = |title: &[u8]| {
+            if title.len() > T::MaxTitleLength::get() as usize {
+                return Err(ValidityError::TitleTooLong)
+            }
+            Ok(())
+        };
+        let check_description = |description: &[u8]| {
+            if description.len() > T::MaxDescriptionLength::get() as usize {
+                return Err(ValidityError::DescriptionTooLong)
+            }
+            Ok(())
+        };
+        let check_payload = |ciphertext: &[u8], recipients: &[WrappedKey]| {
+            if ciphertext.len() > T::MaxCiphertextLength::get() as usize {
+                return Err(ValidityError::CiphertextTooLong)
+            }
+            if recipients.is_empty() || recipients.len() > T::MaxRecipients::get() as usize {
+                return Err(ValidityError::BadRecipients)
+            }
+            Ok(())
+        };
+        let has_room = || Todos::<T>::decode_len(who).unwrap_or(0) < T::MaxTodosPerAccount::get() as usize;
+
+        match call {
+            Call::create_todo { title, description, .. } => {
+                check_title(title)?;
+                check_description(description)?;
+                if !has_room() {
+                    return Err(ValidityError::TodoListFull)
+                }
+            },
+            Call::post_board_task { title, description, .. } => {
+                check_title(title)?;
+                check_description(description)?;
+            },
+            Call::create_encrypted_todo { ciphertext, recipients, .. } => {
+                check_payload(ciphertext, recipients)?;
+                if !has_room() {
+                    return Err(ValidityError::TodoListFull)
+                }
+            },
+            Call::update_encrypted_todo { id, ciphertext, recipients, .. } => {
+                check_payload(ciphertext, recipients)?;
+                if !exists(id) {
+                    return Err(ValidityError::TodoNotFound)
+                }
+            },
+            Call::update_todo { id, title, description, .. } => {
+                if let Some(title) = title {
+                    check_title(title)?;
+                }
+                if let Some(description) = description {
+                    check_description(description)?;
+                }
+                if !exists(id) {
+                    return Err(ValidityError::TodoNotFound)
+                }
+            },
+            Call::undelete_todo { id } => {
+                if !DeletedTodos::<T>::get(who).iter().any(|d| d.todo.id == *id) {
+                    return Err(ValidityError::TodoNotFound)
+                }
+                if !has_room() {
+                    return Err(ValidityError::TodoListFull)
+                }
+            },
+            Call::toggle_todo_completion { id } |
+            Call::delete_todo { id } |
+            Call::set_urgency { id, .. } |
+            Call::set_status { id, .. } => {
+                if !exists(id) {
+                    return Err(ValidityError::TodoNotFound)
+                }
+            },
+            _ => {},
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckTodoCall<T>
+where
+    <T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+    const IDENTIFIER: &'static str = "CheckTodoCall";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::RuntimeCall;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if let Some(local_call) = call.is_sub_type() {
+            Self::check(who, local_call)?;
+        }
+
+        Ok(ValidTransaction::default())
+    }
+
+    fn pre_dispatch(
+        self,
+        _who: &Self::AccountId,
+        _call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        // The same checks run again at dispatch, where they fail with a proper module error
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+//! Offchain indexing of todo snapshots.
+//!
+//! Every call that changes a todo writes a snapshot of the item into the node's offchain
+//! database through `sp_io::offchain_index`, so node-side services can serve history and
+//! search without replaying blocks. Indexing is a no-op unless the node runs with
+//! `--enable-offchain-indexing true`.
+//!
+//! # Key schema
+//!
+//! All keys start with [`SNAPSHOT_PREFIX`] and are followed by SCALE-encoded parts:
+//!
+//! | Key                                                      | Value                          |
+//! |----------------------------------------------------------|--------------------------------|
+//! | `SNAPSHOT_PREFIX ++ account ++ id`                       | latest [`TodoSnapshot`]        |
+//! | `SNAPSHOT_PREFIX ++ account ++ id ++ block_number`       | [`TodoSnapshot`] at that block |
+//!
+//! `id` is the `u64` todo ID and `block_number` the block the change was made in; when a todo
+//! changes several times in one block, the per-block entry holds the last change.
+//!
+//! # Values
+//!
+//! Values are [`TodoSnapshot`]s encoded as JSON objects, so services can read them without the
+//! runtime's types, and can be read back with [`decode_snapshot`]:
+//!
+//! ```json
+//! {
+//!   "who": "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+//!   "id": 0,
+//!   "block_number": 12,
+//!   "action": "CompletionToggled",
+//!   "todo": {
+//!     "id": 0,
+//!     "title": "Buy milk",
+//!     "description": "",
+//!     "status": "Done",
+//!     "priority": "Medium",
+//!     "urgency": 96,
+//!     "created_at": 6000,
+//!     "updated_at": 72000,
+//!     "entered_at": {"backlog": 6000, "in_progress": null, "blocked": null, "done": 72000, "cancelled": null}
+//!   },
+//!   "due": 86400000
+//! }
+//! ```
+//!
+//! `who` is the hex of the SCALE-encoded account and times are in the runtime's `Moment` unit.
+//! Titles and descriptions that are not valid UTF-8 are written as arrays of bytes. `todo` is
+//! `null` once the todo was deleted; an archived todo is stored as it was archived.
+
+use crate::{json::Value, Config, DueDates, Priority, Status, StatusTimestamps, Todo, Todos};
+use codec::{Decode, Encode};
+use frame_support::BoundedVec;
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::UniqueSaturatedInto, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// Prefix of every snapshot key in the offchain database.
+pub const SNAPSHOT_PREFIX: &[u8] = b"pallet-todo::snapshot::";
+
+/// What happened to the todo in the change a snapshot was taken after
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum SnapshotAction {
+    Created,
+    Updated,
+    CompletionToggled,
+    DueDateChanged,
+    Deleted,
+    StatusChanged,
+    Restored,
+    Archived,
+    Transferred,
+}
+
+/// JSON names of the snapshot actions
+const ACTIONS: [(SnapshotAction, &str); 9] = [
+    (SnapshotAction::Created, "Created"),
+    (SnapshotAction::Updated, "Updated"),
+    (SnapshotAction::CompletionToggled, "CompletionToggled"),
+    (SnapshotAction::DueDateChanged, "DueDateChanged"),
+    (SnapshotAction::Deleted, "Deleted"),
+    (SnapshotAction::StatusChanged, "StatusChanged"),
+    (SnapshotAction::Restored, "Restored"),
+    (SnapshotAction::Archived, "Archived"),
+    (SnapshotAction::Transferred, "Transferred"),
+];
+
+/// JSON names of the statuses
+const STATUSES: [(Status, &str); 5] = [
+    (Status::Backlog, "Backlog"),
+    (Status::InProgress, "InProgress"),
+    (Status::Blocked, "Blocked"),
+    (Status::Done, "Done"),
+    (Status::Cancelled, "Cancelled"),
+];
+
+/// JSON names of the priorities
+const PRIORITIES: [(Priority, &str); 4] = [
+    (Priority::Low, "Low"),
+    (Priority::Medium, "Medium"),
+    (Priority::High, "High"),
+    (Priority::Urgent, "Urgent"),
+];
+
+/// The state of a todo after a change, as written to the offchain database
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct TodoSnapshot<T: Config> {
+    /// Owner of the todo
+    pub who: T::AccountId,
+    /// ID of the todo
+    pub id: u64,
+    /// Block the change was made in
+    pub block_number: BlockNumberFor<T>,
+    /// What the change was
+    pub action: SnapshotAction,
+    /// The todo after the change, or `None` once it was deleted
+    pub todo: Option<Todo<T>>,
+    /// The todo's due date after the change
+    pub due: Option<T::Moment>,
+}
+
+impl<T: Config> TodoSnapshot<T> {
+    /// Encode the snapshot as the JSON object described in the [module docs](self)
+    pub fn to_json(&self) -> Vec<u8> {
+        Value::object(vec![
+            ("who", Value::String(to_hex(&self.who.encode()))),
+            ("id", number(self.id)),
+            ("block_number", number(self.block_number)),
+            ("action", name(&ACTIONS, self.action)),
+            ("todo", self.todo.as_ref().map_or(Value::Null, todo_to_value::<T>)),
+            ("due", self.due.map_or(Value::Null, number)),
+        ])
+        .to_json()
+    }
+
+    /// Decode a snapshot written by [`Self::to_json`]
+    pub fn from_json(input: &[u8]) -> Option<Self> {
+        let value = crate::json::parse(input)?;
+        let who = from_hex(value.get("who")?.as_bytes()?)?;
+        Some(TodoSnapshot {
+            who: T::AccountId::decode(&mut &who[..]).ok()?,
+            id: from_number(value.get("id")?)?,
+            block_number: from_number(value.get("block_number")?)?,
+            action: from_name(&ACTIONS, value.get("action")?)?,
+            todo: optional(value.get("todo")?, todo_from_value::<T>)?,
+            due: optional(value.get("due")?, from_number)?,
+        })
+    }
+}
+
+fn todo_to_value<T: Config>(todo: &Todo<T>) -> Value {
+    let entered_at = &todo.entered_at;
+    Value::object(vec![
+        ("id", number(todo.id)),
+        ("title", bytes(&todo.title)),
+        ("description", bytes(&todo.description)),
+        ("status", name(&STATUSES, todo.status)),
+        ("priority", name(&PRIORITIES, todo.priority)),
+        ("urgency", number(todo.urgency)),
+        ("created_at", number(todo.created_at)),
+        ("updated_at", number(todo.updated_at)),
+        (
+            "entered_at",
+            Value::object(vec![
+                ("backlog", entered_at.backlog.map_or(Value::Null, number)),
+                ("in_progress", entered_at.in_progress.map_or(Value::Null, number)),
+                ("blocked", entered_at.blocked.map_or(Value::Null, number)),
+                ("done", entered_at.done.map_or(Value::Null, number)),
+                ("cancelled", entered_at.cancelled.map_or(Value::Null, number)),
+            ]),
+        ),
+    ])
+}
+
+fn todo_from_value<T: Config>(value: &Value) -> Option<Todo<T>> {
+    let entered_at = value.get("entered_at")?;
+    let moment = |key: &str| optional(entered_at.get(key)?, from_number);
+    Some(Todo {
+        id: from_number(value.get("id")?)?,
+        title: BoundedVec::try_from(from_bytes(value.get("title")?)?).ok()?,
+        description: BoundedVec::try_from(from_bytes(value.get("description")?)?).ok()?,
+        status: from_name(&STATUSES, value.get("status")?)?,
+        priority: from_name(&PRIORITIES, value.get("priority")?)?,
+        urgency: from_number(value.get("urgency")?)?,
+        created_at: from_number(value.get("created_at")?)?,
+        updated_at: from_number(value.get("updated_at")?)?,
+        entered_at: StatusTimestamps {
+            backlog: moment("backlog")?,
+            in_progress: moment("in_progress")?,
+            blocked: moment("blocked")?,
+            done: moment("done")?,
+            cancelled: moment("cancelled")?,
+        },
+    })
+}
+
+fn number<N: UniqueSaturatedInto<u128>>(n: N) -> Value {
+    Value::Number(n.unique_saturated_into())
+}
+
+fn from_number<N: TryFrom<u128>>(value: &Value) -> Option<N> {
+    N::try_from(value.as_u128()?).ok()
+}
+
+/// `null` as `None`, anything else through `decode`
+fn optional<V>(value: &Value, decode: impl Fn(&Value) -> Option<V>) -> Option<Option<V>> {
+    match value {
+        Value::Null => Some(None),
+        value => decode(value).map(Some),
+    }
+}
+
+fn name<E: Copy + PartialEq>(names: &[(E, &'static str)], item: E) -> Value {
+    let name = names.iter().find(|(e, _)| *e == item).map_or("", |(_, name)| *name);
+    Value::String(name.as_bytes().to_vec())
+}
+
+fn from_name<E: Copy>(names: &[(E, &'static str)], value: &Value) -> Option<E> {
+    let name = value.as_bytes()?;
+    names.iter().find(|(_, n)| n.as_bytes() == name).map(|(e, _)| *e)
+}
+
+/// Text as a string, other bytes as an array of numbers
+fn bytes(bytes: &[u8]) -> Value {
+    match core::str::from_utf8(bytes) {
+        Ok(_) => Value::String(bytes.to_vec()),
+        Err(_) => Value::Array(bytes.iter().map(|&b| number(b)).collect()),
+    }
+}
+
+fn from_bytes(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(items) => items.iter().map(from_number::<u8>).collect(),
+        _ => None,
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> Vec<u8> {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let mut out = b"0x".to_vec();
+    for &byte in bytes {
+        out.push(HEX[(byte >> 4) as usize]);
+        out.push(HEX[(byte & 0xf) as usize]);
+    }
+    out
+}
+
+fn from_hex(hex: &[u8]) -> Option<Vec<u8>> {
+    let digits = hex.strip_prefix(&b"0x"[..])?;
+    if digits.len() % 2 != 0 {
+        return None
+    }
+    let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+    digits.chunks(2).map(|pair| Some((digit(pair[0])? << 4) | digit(pair[1])?)).collect()
+}
+
+/// Key of the latest snapshot of a todo.
+pub fn latest_snapshot_key<T: Config>(who: &T::AccountId, id: u64) -> Vec<u8> {
+    (SNAPSHOT_PREFIX, who, id).encode()
+}
+
+/// Key of the snapshot of a todo taken in a given block.
+pub fn block_snapshot_key<T: Config>(
+    who: &T::AccountId,
+    id: u64,
+    block_number: BlockNumberFor<T>,
+) -> Vec<u8> {
+    (SNAPSHOT_PREFIX, who, id, block_number).encode()
+}
+
+/// Decode a snapshot value read from the offchain database.
+pub fn decode_snapshot<T: Config>(value: &[u8]) -> Option<TodoSnapshot<T>> {
+    TodoSnapshot::<T>::from_json(value)
+}
+
+/// Read the latest snapshot of a todo from within an offchain worker.
+pub fn read_latest_snapshot<T: Config>(who: &T::AccountId, id: u64) -> Option<TodoSnapshot<T>> {
+    let key = latest_snapshot_key::<T>(who, id);
+    sp_io::offchain::local_storage_get(sp_core::offchain::StorageKind::PERSISTENT, &key)
+        .and_then(|value| decode_snapshot::<T>(&value))
+}
+
+/// Write a snapshot of the todo's current state to the offchain database.
+pub(crate) fn index_todo<T: Config>(who: &T::AccountId, id: u64, action: SnapshotAction) {
+    let todo = Todos::<T>::get(who).into_iter().find(|t| t.id == id);
+    index_snapshot::<T>(who, id, action, todo);
+}
+
+/// Write a snapshot of a todo that is no longer on the list, such as one being archived.
+pub(crate) fn index_snapshot<T: Config>(
+    who: &T::AccountId,
+    id: u64,
+    action: SnapshotAction,
+    todo: Option<Todo<T>>,
+) {
+    let block_number = frame_system::Pallet::<T>::block_number();
+    let snapshot = TodoSnapshot::<T> {
+        who: who.clone(),
+        id,
+        block_number,
+        action,
+        todo,
+        due: DueDates::<T>::get(who, id).map(|d| d.due),
+    };
+
+    let value = snapshot.to_json();
+    sp_io::offchain_index::set(&latest_snapshot_key::<T>(who, id), &value);
+    sp_io::offchain_index::set(&block_snapshot_key::<T>(who, id, block_number), &value);
+}
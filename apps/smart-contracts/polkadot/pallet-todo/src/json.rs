@@ -0,0 +1,294 @@
+//! A small JSON writer and reader for the offchain snapshots in [`crate::indexing`].
+//!
+//! Only what the snapshots need is supported: objects, arrays, strings, booleans, `null` and
+//! unsigned integers. It works without `std`, so a snapshot is written the same way whether the
+//! runtime executes natively or in Wasm.
+
+use sp_std::prelude::*;
+
+/// Nesting depth the reader accepts
+const MAX_DEPTH: u32 = 8;
+
+/// A JSON value
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// An unsigned integer
+    Number(u128),
+    /// A string, as UTF-8 bytes
+    String(Vec<u8>),
+    Array(Vec<Value>),
+    /// The members of an object in the order they were written
+    Object(Vec<(Vec<u8>, Value)>),
+}
+
+impl Value {
+    /// An object with the given members
+    pub fn object(members: Vec<(&str, Value)>) -> Self {
+        Value::Object(members.into_iter().map(|(key, value)| (key.as_bytes().to_vec(), value)).collect())
+    }
+
+    /// A member of an object
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k.as_slice() == key.as_bytes()).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            Value::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::String(s) => Some(s.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Encode the value as compact JSON
+    pub fn to_json(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write(&mut out);
+        out
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Value::Null => out.extend_from_slice(b"null"),
+            Value::Bool(true) => out.extend_from_slice(b"true"),
+            Value::Bool(false) => out.extend_from_slice(b"false"),
+            Value::Number(n) => write_number(*n, out),
+            Value::String(s) => write_string(s, out),
+            Value::Array(items) => {
+                out.push(b'[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    item.write(out);
+                }
+                out.push(b']');
+            },
+            Value::Object(members) => {
+                out.push(b'{');
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    write_string(key, out);
+                    out.push(b':');
+                    value.write(out);
+                }
+                out.push(b'}');
+            },
+        }
+    }
+}
+
+fn write_number(mut n: u128, out: &mut Vec<u8>) {
+    let mut digits = [0u8; 39];
+    let mut len = 0;
+    loop {
+        digits[len] = b'0' + (n % 10) as u8;
+        len += 1;
+        n /= 10;
+        if n == 0 {
+            break
+        }
+    }
+    out.extend(digits[..len].iter().rev());
+}
+
+fn write_string(s: &[u8], out: &mut Vec<u8>) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    out.push(b'"');
+    for &byte in s {
+        match byte {
+            b'"' => out.extend_from_slice(b"\\\""),
+            b'\\' => out.extend_from_slice(b"\\\\"),
+            b'\n' => out.extend_from_slice(b"\\n"),
+            b'\r' => out.extend_from_slice(b"\\r"),
+            b'\t' => out.extend_from_slice(b"\\t"),
+            0..=0x1f => out.extend_from_slice(&[b'\\', b'u', b'0', b'0', HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]),
+            _ => out.push(byte),
+        }
+    }
+    out.push(b'"');
+}
+
+/// Parse a JSON document, or `None` if it is malformed or uses what the reader does not support
+pub fn parse(input: &[u8]) -> Option<Value> {
+    let mut parser = Parser { input, pos: 0 };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    (parser.pos == input.len()).then_some(value)
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn bump(&mut self) -> Option<u8> {
+        let byte = *self.input.get(self.pos)?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.input.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip whitespace and consume `byte` if it comes next
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.input.get(self.pos) == Some(&byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn literal(&mut self, literal: &[u8], value: Value) -> Option<Value> {
+        let found = self.input.get(self.pos..self.pos + literal.len())? == literal;
+        self.pos += literal.len();
+        found.then_some(value)
+    }
+
+    fn value(&mut self, depth: u32) -> Option<Value> {
+        if depth > MAX_DEPTH {
+            return None
+        }
+        self.skip_whitespace();
+        match *self.input.get(self.pos)? {
+            b'n' => self.literal(b"null", Value::Null),
+            b't' => self.literal(b"true", Value::Bool(true)),
+            b'f' => self.literal(b"false", Value::Bool(false)),
+            b'0'..=b'9' => self.number().map(Value::Number),
+            b'"' => self.string().map(Value::String),
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if !self.eat(b']') {
+                    loop {
+                        items.push(self.value(depth + 1)?);
+                        if self.eat(b']') {
+                            break
+                        }
+                        if !self.eat(b',') {
+                            return None
+                        }
+                    }
+                }
+                Some(Value::Array(items))
+            },
+            b'{' => {
+                self.pos += 1;
+                let mut members = Vec::new();
+                if !self.eat(b'}') {
+                    loop {
+                        self.skip_whitespace();
+                        let key = self.string()?;
+                        if !self.eat(b':') {
+                            return None
+                        }
+                        members.push((key, self.value(depth + 1)?));
+                        if self.eat(b'}') {
+                            break
+                        }
+                        if !self.eat(b',') {
+                            return None
+                        }
+                    }
+                }
+                Some(Value::Object(members))
+            },
+            _ => None,
+        }
+    }
+
+    fn number(&mut self) -> Option<u128> {
+        let start = self.pos;
+        let mut n: u128 = 0;
+        while let Some(digit @ b'0'..=b'9') = self.input.get(self.pos).copied() {
+            n = n.checked_mul(10)?.checked_add((digit - b'0') as u128)?;
+            self.pos += 1;
+        }
+
+        // Leading zeros, fractions and exponents are not supported
+        let leading_zero = self.input[start] == b'0' && self.pos - start > 1;
+        if leading_zero || matches!(self.input.get(self.pos), Some(b'.' | b'e' | b'E')) {
+            return None
+        }
+        Some(n)
+    }
+
+    fn string(&mut self) -> Option<Vec<u8>> {
+        if self.bump()? != b'"' {
+            return None
+        }
+        let mut out = Vec::new();
+        loop {
+            match self.bump()? {
+                b'"' => break,
+                b'\\' => {
+                    let c = match self.bump()? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.escaped_char()?,
+                        _ => return None,
+                    };
+                    let mut buf = [0u8; 4];
+                    out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                },
+                0..=0x1f => return None,
+                byte => out.push(byte),
+            }
+        }
+        core::str::from_utf8(&out).ok()?;
+        Some(out)
+    }
+
+    /// The character of a `\u` escape, joining surrogate pairs
+    fn escaped_char(&mut self) -> Option<char> {
+        let high = self.hex4()?;
+        let code = match high {
+            0xd800..=0xdbff => {
+                if self.bump()? != b'\\' || self.bump()? != b'u' {
+                    return None
+                }
+                let low = self.hex4()?;
+                if !(0xdc00..=0xdfff).contains(&low) {
+                    return None
+                }
+                0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+            },
+            _ => high,
+        };
+        char::from_u32(code)
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let mut code = 0;
+        for _ in 0..4 {
+            code = (code << 4) | (self.bump()? as char).to_digit(16)?;
+        }
+        Some(code)
+    }
+}
@@ -4,6 +4,43 @@
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// <https://docs.substrate.io/reference/frame-pallets/>
 pub use pallet::*;
+use sp_core::crypto::KeyTypeId;
+
+/// Key type of the keys the offchain worker signs due-date reminders with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"todo");
+
+/// Crypto used to sign due-date reminders; a node holding a `todo` key in its keystore
+/// submits them from its offchain worker.
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    /// Identifier used by the runtime to sign reminders with the `todo` key
+    pub struct TodoAuthId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TodoAuthId {
+        type RuntimeAppPublic = Public;
+        type GenericPublic = Sr25519Public;
+        type GenericSignature = Sr25519Signature;
+    }
+}
+
+pub mod indexing;
+pub mod migrations;
+
+mod json;
+
+mod extensions;
+pub use extensions::{CheckRateLimit, CheckTodoCall, ValidityError};
+
+mod traits;
+pub use traits::TodoCompletionHandler;
 
 #[cfg(test)]
 mod mock;
@@ -14,21 +51,44 @@ mod tests;
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
+        dispatch::GetDispatchInfo,
         pallet_prelude::*,
-        traits::Time,
+        serde::{Deserialize, Serialize},
+        traits::{
+            schedule::{
+                v3::{Named as ScheduleNamed, TaskName},
+                DispatchTime, LOWEST_PRIORITY,
+            },
+            Bounded, Time,
+        },
         weights::Weight,
     };
-    use frame_system::pallet_prelude::*;
-    use sp_std::prelude::*;
+    use frame_system::{
+        offchain::{
+            AppCrypto, SendTransactionTypes, SendUnsignedTransaction, SignedPayload, Signer,
+            SigningTypes,
+        },
+        pallet_prelude::*,
+    };
+    use sp_runtime::{
+        traits::{AtLeast32BitUnsigned, Hash, IdentifyAccount, Saturating, Zero},
+        RuntimeAppPublic,
+    };
+    use sp_std::{cmp::Reverse, prelude::*};
+
+    use crate::{
+        indexing::{self, SnapshotAction},
+        TodoCompletionHandler,
+    };
 
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> + SigningTypes {
         /// Because this pallet emits events, it depends on the runtime's definition of an event.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         
         /// The type used to represent timestamps in the runtime
-        type Moment: Parameter + Default + Copy + MaxEncodedLen;
+        type Moment: Parameter + Default + Copy + MaxEncodedLen + AtLeast32BitUnsigned;
         
         /// The time provider
         type TimeProvider: Time<Moment = Self::Moment>;
@@ -44,14 +104,214 @@ pub mod pallet {
         /// Maximum number of todos per account
         #[pallet::constant]
         type MaxTodosPerAccount: Get<u32>;
+
+        /// Handler notified when a todo is completed, reopened or removed
+        type CompletionHandler: TodoCompletionHandler<Self::AccountId>;
+
+        /// Origin allowed to moderate other accounts' todos
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Number of fee-free calls each account may make per `FreeCallPeriod`
+        #[pallet::constant]
+        type FreeCallsPerPeriod: Get<u32>;
+
+        /// Number of blocks the free call quota is counted over
+        #[pallet::constant]
+        type FreeCallPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of `create_todo`/`update_todo` calls an account may make per window
+        #[pallet::constant]
+        type MaxOpsPerWindow: Get<u32>;
+
+        /// Length of the rate limiting window, in blocks
+        #[pallet::constant]
+        type RateLimitWindow: Get<BlockNumberFor<Self>>;
+
+        /// The identifier type for the offchain worker's reminder key
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+        /// How long before its due date a todo is raised to high priority
+        #[pallet::constant]
+        type ReminderLeadTime: Get<Self::Moment>;
+
+        /// Maximum number of todos covered by a single reminder transaction
+        #[pallet::constant]
+        type MaxRemindersPerBlock: Get<u32>;
+
+        /// Transaction priority of the offchain worker's reminder transactions
+        #[pallet::constant]
+        type UnsignedPriority: Get<TransactionPriority>;
+
+        /// Maximum number of accounts whose `todo` key may sign reminders
+        #[pallet::constant]
+        type MaxReminderAuthorities: Get<u32>;
+
+        /// Maximum length of an encrypted todo payload
+        #[pallet::constant]
+        type MaxCiphertextLength: Get<u32>;
+
+        /// Maximum number of recipients of an encrypted todo
+        #[pallet::constant]
+        type MaxRecipients: Get<u32>;
+
+        /// The overarching call type, which scheduled todo calls are dispatched as
+        type RuntimeCall: Parameter
+            + From<Call<Self>>
+            + IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+        /// The caller origin that scheduled calls are dispatched with
+        type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+        /// The scheduler that dispatches scheduled todo calls
+        type Scheduler: ScheduleNamed<
+            BlockNumberFor<Self>,
+            <Self as Config>::RuntimeCall,
+            Self::PalletsOrigin,
+        >;
+
+        /// Maximum number of pending scheduled todo calls per account
+        #[pallet::constant]
+        type MaxScheduledPerAccount: Get<u32>;
+
+        /// Minimum number of blocks between two runs of a periodic scheduled todo call
+        #[pallet::constant]
+        type MinSchedulePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of runs of a periodic scheduled todo call
+        #[pallet::constant]
+        type MaxScheduleRepetitions: Get<u32>;
+
+        /// Number of blocks a claim on a board task lasts
+        #[pallet::constant]
+        type ClaimDuration: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of tasks an account may have on the board at a time
+        #[pallet::constant]
+        type MaxBoardTasksPerAccount: Get<u32>;
+
+        /// Number of revisions kept per todo; older revisions are dropped
+        #[pallet::constant]
+        type MaxRevisions: Get<u32>;
+
+        /// Number of blocks a deleted todo can be restored for before it is purged
+        #[pallet::constant]
+        type DeletionGracePeriod: Get<BlockNumberFor<Self>>;
     }
 
-    /// Priority level for a todo item
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    /// Priority level for a todo item, from least to most pressing.
+    ///
+    /// Each priority is a band of the 0 to 255 urgency scale: 0-63 is low, 64-127 medium,
+    /// 128-191 high and 192-255 urgent.
+    #[derive(
+        Clone, Copy, Encode, Decode, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo,
+        MaxEncodedLen, Serialize, Deserialize,
+    )]
+    #[serde(crate = "frame_support::serde")]
     pub enum Priority {
         Low,
         Medium,
         High,
+        Urgent,
+    }
+
+    impl Priority {
+        /// Urgency a todo is given when it is set to this priority, the middle of the band
+        pub const fn urgency(self) -> u8 {
+            match self {
+                Priority::Low => 32,
+                Priority::Medium => 96,
+                Priority::High => 160,
+                Priority::Urgent => 224,
+            }
+        }
+
+        /// The priority whose band an urgency falls in
+        pub const fn from_urgency(urgency: u8) -> Self {
+            match urgency {
+                0..=63 => Priority::Low,
+                64..=127 => Priority::Medium,
+                128..=191 => Priority::High,
+                _ => Priority::Urgent,
+            }
+        }
+    }
+
+    /// Where a todo is in its workflow
+    #[derive(
+        Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+        Serialize, Deserialize,
+    )]
+    #[serde(crate = "frame_support::serde")]
+    pub enum Status {
+        /// Not started yet
+        Backlog,
+        /// Being worked on
+        InProgress,
+        /// Waiting on something else
+        Blocked,
+        /// Completed
+        Done,
+        /// Dropped without being completed
+        Cancelled,
+    }
+
+    impl Status {
+        /// Whether a todo with this status still has to be done
+        pub fn is_open(self) -> bool {
+            matches!(self, Status::Backlog | Status::InProgress | Status::Blocked)
+        }
+
+        /// Whether a todo may move from this status to `next`.
+        ///
+        /// Blocked todos have to be unblocked before they are done, done todos can only be
+        /// reopened and cancelled todos can only be restored to the backlog.
+        pub fn can_move_to(self, next: Status) -> bool {
+            use Status::*;
+            
+            matches!(
+                (self, next),
+                (Backlog, InProgress | Blocked | Done | Cancelled) |
+                    (InProgress, Backlog | Blocked | Done | Cancelled) |
+                    (Blocked, Backlog | InProgress | Cancelled) |
+                    (Done, Backlog | InProgress) |
+                    (Cancelled, Backlog)
+            )
+        }
+    }
+
+    /// When a todo last entered each status
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+    pub struct StatusTimestamps<Moment> {
+        pub backlog: Option<Moment>,
+        pub in_progress: Option<Moment>,
+        pub blocked: Option<Moment>,
+        pub done: Option<Moment>,
+        pub cancelled: Option<Moment>,
+    }
+
+    impl<Moment: Copy> StatusTimestamps<Moment> {
+        /// When the todo last entered `status`, if it ever did
+        pub fn get(&self, status: Status) -> Option<Moment> {
+            match status {
+                Status::Backlog => self.backlog,
+                Status::InProgress => self.in_progress,
+                Status::Blocked => self.blocked,
+                Status::Done => self.done,
+                Status::Cancelled => self.cancelled,
+            }
+        }
+
+        /// Record that the todo entered `status` at `now`
+        pub fn set(&mut self, status: Status, now: Moment) {
+            let entered_at = match status {
+                Status::Backlog => &mut self.backlog,
+                Status::InProgress => &mut self.in_progress,
+                Status::Blocked => &mut self.blocked,
+                Status::Done => &mut self.done,
+                Status::Cancelled => &mut self.cancelled,
+            };
+            *entered_at = Some(now);
+        }
     }
 
     /// A todo item
@@ -64,32 +324,352 @@ pub mod pallet {
         pub title: BoundedVec<u8, T::MaxTitleLength>,
         /// Description of the todo
         pub description: BoundedVec<u8, T::MaxDescriptionLength>,
-        /// Whether the todo is completed
-        pub completed: bool,
-        /// Priority level of the todo
+        /// Where the todo is in its workflow
+        pub status: Status,
+        /// Priority level of the todo, the band its urgency falls in
         pub priority: Priority,
+        /// Urgency of the todo from 0 to 255, for ordering todos of the same priority
+        pub urgency: u8,
         /// When the todo was created
         pub created_at: T::Moment,
         /// When the todo was last updated
         pub updated_at: T::Moment,
-        /// When the todo was completed (if completed)
-        pub completed_at: Option<T::Moment>,
+        /// When the todo last entered each status
+        pub entered_at: StatusTimestamps<T::Moment>,
+    }
+
+    impl<T: Config> Todo<T> {
+        /// A new todo in the backlog
+        pub fn new(
+            id: u64,
+            title: BoundedVec<u8, T::MaxTitleLength>,
+            description: BoundedVec<u8, T::MaxDescriptionLength>,
+            priority: Priority,
+            now: T::Moment,
+        ) -> Self {
+            Todo {
+                id,
+                title,
+                description,
+                status: Status::Backlog,
+                priority,
+                urgency: priority.urgency(),
+                created_at: now,
+                updated_at: now,
+                entered_at: StatusTimestamps { backlog: Some(now), ..Default::default() },
+            }
+        }
+
+        /// Whether the todo is done
+        pub fn is_completed(&self) -> bool {
+            self.status == Status::Done
+        }
+
+        /// When the todo was completed, if it is done
+        pub fn completed_at(&self) -> Option<T::Moment> {
+            self.entered_at.done.filter(|_| self.is_completed())
+        }
+
+        /// A revision keeping the todo's current title, description and priority
+        pub fn revision(
+            &self,
+            changed_by: Option<T::AccountId>,
+            changed_at: T::Moment,
+        ) -> TodoRevision<T> {
+            TodoRevision {
+                changed_by,
+                changed_at,
+                title: self.title.clone(),
+                description_hash: T::Hashing::hash(&self.description),
+                priority: self.priority,
+            }
+        }
+
+        /// Move the todo to `status`, without checking the transition
+        pub fn set_status(&mut self, status: Status, now: T::Moment) {
+            self.status = status;
+            self.entered_at.set(status, now);
+            self.updated_at = now;
+        }
+
+        /// Move the todo to another priority; an urgency within the same band is kept
+        pub fn set_priority(&mut self, priority: Priority) {
+            if self.priority != priority {
+                self.priority = priority;
+                self.urgency = priority.urgency();
+            }
+        }
+
+        /// Set the todo's urgency, moving it to the priority of its band
+        pub fn set_urgency(&mut self, urgency: u8) {
+            self.urgency = urgency;
+            self.priority = Priority::from_urgency(urgency);
+        }
+    }
+
+    /// A todo's title, description and priority before a change, and who made the change
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct TodoRevision<T: Config> {
+        /// Account that made the change, or `None` for changes made by the chain itself
+        pub changed_by: Option<T::AccountId>,
+        /// When the change was made
+        pub changed_at: T::Moment,
+        /// Title before the change
+        pub title: BoundedVec<u8, T::MaxTitleLength>,
+        /// Hash of the description before the change
+        pub description_hash: T::Hash,
+        /// Priority before the change
+        pub priority: Priority,
+    }
+
+    impl<T: Config> TodoRevision<T> {
+        /// Whether `todo` differs from this revision in its title, description or priority
+        pub fn differs_from(&self, todo: &Todo<T>) -> bool {
+            self.title != todo.title ||
+                self.description_hash != T::Hashing::hash(&todo.description) ||
+                self.priority != todo.priority
+        }
     }
 
     /// Todo statistics
-    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+    #[derive(
+        Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default,
+        Serialize, Deserialize,
+    )]
+    #[serde(crate = "frame_support::serde")]
     pub struct TodoStatistics {
         /// Total number of todos
         pub total: u32,
-        /// Number of completed todos
+        /// Number of done todos
         pub completed: u32,
-        /// Number of pending todos
+        /// Number of todos still to be done: in the backlog, in progress or blocked
         pub pending: u32,
-        /// Number of high priority todos
+        /// Number of todos in progress
+        pub in_progress: u32,
+        /// Number of blocked todos
+        pub blocked: u32,
+        /// Number of cancelled todos
+        pub cancelled: u32,
+        /// Number of pending low priority todos
+        pub low_priority: u32,
+        /// Number of pending medium priority todos
+        pub medium_priority: u32,
+        /// Number of pending high priority todos
         pub high_priority: u32,
+        /// Number of pending urgent todos
+        pub urgent_priority: u32,
+    }
+
+    impl TodoStatistics {
+        /// Compute the statistics of a todo list
+        pub fn of<T: Config>(todos: &[Todo<T>]) -> Self {
+            let mut stats = TodoStatistics { total: todos.len() as u32, ..Default::default() };
+            
+            for todo in todos {
+                match todo.status {
+                    Status::Done => stats.completed += 1,
+                    Status::Cancelled => stats.cancelled += 1,
+                    Status::InProgress => stats.in_progress += 1,
+                    Status::Blocked => stats.blocked += 1,
+                    Status::Backlog => {},
+                }
+                if !todo.status.is_open() {
+                    continue
+                }
+                
+                stats.pending += 1;
+                match todo.priority {
+                    Priority::Low => stats.low_priority += 1,
+                    Priority::Medium => stats.medium_priority += 1,
+                    Priority::High => stats.high_priority += 1,
+                    Priority::Urgent => stats.urgent_priority += 1,
+                }
+            }
+            
+            stats
+        }
+    }
+
+    /// Order in which an account's todos are returned by the runtime API
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+    #[serde(crate = "frame_support::serde")]
+    pub enum TodoSort {
+        /// By ID, oldest first
+        Id,
+        /// Most urgent first; todos of the same urgency by ID
+        Urgency,
+        /// Earliest due date first; todos without a due date last, by ID
+        DueDate,
+    }
+
+    /// Fee-free calls an account made in a period
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+    pub struct FreeCallsUsed<BlockNumber> {
+        /// Index of the period, counted in `FreeCallPeriod` blocks
+        pub period: BlockNumber,
+        /// Number of free calls made in `period`
+        pub used: u32,
+    }
+
+    /// Rate limiting state of an account
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+    pub struct RateLimitState<BlockNumber> {
+        /// Block at which the current window started
+        pub window_start: BlockNumber,
+        /// Number of operations made in the current window
+        pub ops: u32,
+    }
+
+    /// A deleted todo that can still be restored
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct DeletedTodo<T: Config> {
+        /// The todo as it was when it was deleted
+        pub todo: Todo<T>,
+        /// Block from which the todo is purged
+        pub purge_at: BlockNumberFor<T>,
+    }
+
+    /// What a retention policy does with the todos it prunes
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum RetentionAction {
+        /// Remove the todos for good
+        Delete,
+        /// Move the todos to the account's archive
+        Archive,
+    }
+
+    /// How a list handed over with `transfer_list` joins the recipient's todos
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum TransferMode {
+        /// Add the todos after the recipient's own
+        Merge,
+        /// Remove the recipient's todos for good and keep only the transferred ones
+        Replace,
+    }
+
+    /// An account's policy for pruning its completed todos
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct RetentionPolicy<Moment> {
+        /// How long a todo stays on the list after it was completed
+        pub max_age: Moment,
+        /// What happens to todos completed longer than `max_age` ago
+        pub action: RetentionAction,
+    }
+
+    /// Due date of a todo
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct DueDate<Moment> {
+        /// When the todo falls due
+        pub due: Moment,
+        /// Whether the offchain worker has already raised the todo's priority
+        pub reminded: bool,
+    }
+
+    /// Todos that are about to fall due, as reported by an offchain worker
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct ReminderPayload<T: Config> {
+        /// Block the offchain worker ran at
+        pub block_number: BlockNumberFor<T>,
+        /// Owners and ids of the todos to remind
+        pub todos: BoundedVec<(T::AccountId, u64), T::MaxRemindersPerBlock>,
+        /// Key that signed the payload
+        pub public: T::Public,
+    }
+
+    impl<T: Config> SignedPayload<T> for ReminderPayload<T> {
+        fn public(&self) -> T::Public {
+            self.public.clone()
+        }
+    }
+
+    /// The content key of an encrypted todo, wrapped for one recipient
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct WrappedKey {
+        /// The recipient's x25519 public key
+        pub recipient: [u8; 32],
+        /// The content key, encrypted for the recipient (32-byte key and 16-byte tag)
+        pub wrapped_key: [u8; 48],
+    }
+
+    /// The encrypted content of a todo.
+    ///
+    /// The pallet only stores the payload and enforces its size bounds; the encryption scheme
+    /// is implemented by the `todo-e2e` client crate.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct EncryptedPayload<T: Config> {
+        /// The sender's x25519 public key
+        pub sender: [u8; 32],
+        /// XChaCha20-Poly1305 nonce
+        pub nonce: [u8; 24],
+        /// The encrypted title and description
+        pub ciphertext: BoundedVec<u8, T::MaxCiphertextLength>,
+        /// The content key, wrapped for each recipient
+        pub recipients: BoundedVec<WrappedKey, T::MaxRecipients>,
+    }
+
+    /// A todo call scheduled by an account
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct ScheduledTodo<BlockNumber> {
+        /// ID of the scheduled task, unique per account
+        pub id: u64,
+        /// Block the call is first dispatched at
+        pub when: BlockNumber,
+        /// Period and total number of dispatches, for repeating calls
+        pub maybe_periodic: Option<(BlockNumber, u32)>,
     }
 
+    /// State of a task on the community board
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum BoardTaskStatus<AccountId, BlockNumber> {
+        /// Waiting to be claimed
+        Open,
+        /// Claimed by an account; the claim lapses at `expires_at`
+        Claimed { claimant: AccountId, expires_at: BlockNumber },
+        /// Reported done by the claimant and waiting for the poster's review
+        Submitted { claimant: AccountId },
+    }
+
+    impl<AccountId, BlockNumber: PartialOrd> BoardTaskStatus<AccountId, BlockNumber> {
+        /// The account working on the task as of block `now`, if any
+        pub fn active_claimant(&self, now: &BlockNumber) -> Option<&AccountId> {
+            match self {
+                Self::Open => None,
+                Self::Claimed { claimant, expires_at } if now < expires_at => Some(claimant),
+                Self::Claimed { .. } => None,
+                Self::Submitted { claimant } => Some(claimant),
+            }
+        }
+    }
+
+    /// A task on the community board
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct BoardTask<T: Config> {
+        /// Unique identifier for the task
+        pub id: u64,
+        /// Account that posted the task
+        pub poster: T::AccountId,
+        /// Title of the task
+        pub title: BoundedVec<u8, T::MaxTitleLength>,
+        /// Description of the task
+        pub description: BoundedVec<u8, T::MaxDescriptionLength>,
+        /// Priority level of the task
+        pub priority: Priority,
+        /// Who is working on the task
+        pub status: BoardTaskStatus<T::AccountId, BlockNumberFor<T>>,
+        /// When the task was posted
+        pub created_at: T::Moment,
+    }
+
+    /// The in-code storage version
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// Storage for todos, keyed by account ID
@@ -125,95 +705,459 @@ pub mod pallet {
         ValueQuery,
     >;
 
-    // Pallets use events to inform users when important changes are made.
-    #[pallet::event]
-    #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
-        /// A todo was created
-        TodoCreated { who: T::AccountId, id: u64 },
-        /// A todo was updated
-        TodoUpdated { who: T::AccountId, id: u64 },
-        /// A todo's completion status was toggled
-        TodoCompletionToggled { who: T::AccountId, id: u64, completed: bool },
-        /// A todo was deleted
-        TodoDeleted { who: T::AccountId, id: u64 },
-    }
+    /// Fee-free calls used in the current period, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn free_todo_calls)]
+    pub type FreeTodoCalls<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        FreeCallsUsed<BlockNumberFor<T>>,
+        ValueQuery,
+    >;
 
-    // Errors inform users that something went wrong.
-    #[pallet::error]
-    pub enum Error<T> {
-        /// The todo list is full
-        TodoListFull,
-        /// The todo title is too long
-        TitleTooLong,
-        /// The todo description is too long
-        DescriptionTooLong,
-        /// The todo was not found
-        TodoNotFound,
-    }
+    /// Rate limiting state, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn rate_limits)]
+    pub type RateLimits<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        RateLimitState<BlockNumberFor<T>>,
+        ValueQuery,
+    >;
 
-    // Dispatchable functions allows users to interact with the pallet and invoke state changes.
-    // These functions materialize as "extrinsics", which are often compared to transactions.
-    // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Create a new todo
-        #[pallet::call_index(0)]
-        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
-        pub fn create_todo(
-            origin: OriginFor<T>,
-            title: Vec<u8>,
-            description: Vec<u8>,
-            priority: Priority,
-        ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            
-            // Check title length
-            let title = BoundedVec::<u8, T::MaxTitleLength>::try_from(title)
-                .map_err(|_| Error::<T>::TitleTooLong)?;
-            
-            // Check description length
-            let description = BoundedVec::<u8, T::MaxDescriptionLength>::try_from(description)
-                .map_err(|_| Error::<T>::DescriptionTooLong)?;
-            
-            // Get current time
-            let now = T::TimeProvider::now();
-            
-            // Get next ID
-            let id = Self::next_id(&who);
-            
-            // Create new todo
-            let todo = Todo {
-                id,
-                title,
-                description,
-                completed: false,
-                priority,
-                created_at: now,
-                updated_at: now,
-                completed_at: None,
-            };
-            
-            // Add todo to storage
-            Todos::<T>::try_mutate(&who, |todos| {
-                todos.try_push(todo).map_err(|_| Error::<T>::TodoListFull)
-            })?;
-            
-            // Increment next ID
-            NextId::<T>::insert(&who, id + 1);
+    /// Due dates, keyed by account ID and todo ID
+    #[pallet::storage]
+    #[pallet::getter(fn due_dates)]
+    pub type DueDates<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u64,
+        DueDate<T::Moment>,
+        OptionQuery,
+    >;
+
+    /// Encrypted payloads of todos, keyed by account ID and todo ID
+    #[pallet::storage]
+    #[pallet::getter(fn encrypted_payloads)]
+    pub type EncryptedPayloads<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u64,
+        EncryptedPayload<T>,
+        OptionQuery,
+    >;
+
+    /// Revisions of todos, oldest first, keyed by account ID and todo ID
+    #[pallet::storage]
+    #[pallet::getter(fn todo_history)]
+    pub type TodoHistory<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u64,
+        BoundedVec<TodoRevision<T>, T::MaxRevisions>,
+        ValueQuery,
+    >;
+
+    /// Deleted todos that can still be restored, oldest deletion first, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn deleted_todos)]
+    pub type DeletedTodos<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<DeletedTodo<T>, T::MaxTodosPerAccount>,
+        ValueQuery,
+    >;
+
+    /// Deleted todos in the order they are purged, keyed by position in the queue
+    #[pallet::storage]
+    pub type PurgeQueue<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        (T::AccountId, u64, BlockNumberFor<T>),
+        OptionQuery,
+    >;
+
+    /// Position of the first entry of `PurgeQueue` and of the next one to be added
+    #[pallet::storage]
+    pub type PurgeQueueBounds<T: Config> = StorageValue<_, (u64, u64), ValueQuery>;
+
+    /// Retention policies accounts opted in to, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn retention_policy)]
+    pub type RetentionPolicies<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        RetentionPolicy<T::Moment>,
+        OptionQuery,
+    >;
+
+    /// Last account the retention sweep got to; the next sweep carries on after it
+    #[pallet::storage]
+    pub type RetentionCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// Pending list transfers, keyed by the account offering its list, to the account it is offered to
+    #[pallet::storage]
+    #[pallet::getter(fn list_transfer)]
+    pub type ListTransfers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+
+    /// Accounts whose `todo` key may sign due-date reminders
+    #[pallet::storage]
+    #[pallet::getter(fn reminder_authorities)]
+    pub type ReminderAuthorities<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxReminderAuthorities>, ValueQuery>;
+
+    /// Todos archived by a retention policy, oldest first, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn archived_todos)]
+    pub type ArchivedTodos<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<Todo<T>, T::MaxTodosPerAccount>,
+        ValueQuery,
+    >;
+
+    /// Pending scheduled todo calls, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_todos)]
+    pub type ScheduledTodos<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<ScheduledTodo<BlockNumberFor<T>>, T::MaxScheduledPerAccount>,
+        ValueQuery,
+    >;
+
+    /// Next ID for scheduled todo calls, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn next_schedule_id)]
+    pub type NextScheduleId<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        u64,
+        ValueQuery,
+    >;
+
+    /// Tasks on the community board, keyed by task ID
+    #[pallet::storage]
+    #[pallet::getter(fn board_tasks)]
+    pub type BoardTasks<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        BoardTask<T>,
+        OptionQuery,
+    >;
+
+    /// Next ID for board tasks
+    #[pallet::storage]
+    #[pallet::getter(fn next_board_task_id)]
+    pub type NextBoardTaskId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Number of tasks each account has on the board, keyed by account ID
+    #[pallet::storage]
+    #[pallet::getter(fn board_task_count)]
+    pub type BoardTaskCount<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
+        ValueQuery,
+    >;
+
+    /// A todo created at genesis: owner, title, description, priority and status
+    pub type GenesisTodo<AccountId> = (AccountId, Vec<u8>, Vec<u8>, Priority, Status);
+
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Todos to create at genesis, in order, so each account's ids start at 0
+        pub todos: Vec<GenesisTodo<T::AccountId>>,
+        /// Accounts whose `todo` key may sign due-date reminders
+        pub reminder_authorities: Vec<T::AccountId>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            let authorities = BoundedVec::<T::AccountId, T::MaxReminderAuthorities>::try_from(
+                self.reminder_authorities.clone(),
+            )
+            .expect("genesis reminder authorities exceed MaxReminderAuthorities");
+            ReminderAuthorities::<T>::put(authorities);
+
+            for (who, title, description, priority, status) in &self.todos {
+                let title = BoundedVec::<u8, T::MaxTitleLength>::try_from(title.clone())
+                    .expect("genesis todo title exceeds MaxTitleLength");
+                let description =
+                    BoundedVec::<u8, T::MaxDescriptionLength>::try_from(description.clone())
+                        .expect("genesis todo description exceeds MaxDescriptionLength");
+
+                let now = T::TimeProvider::now();
+                let id = NextId::<T>::get(who);
+                let mut todo = Todo::new(id, title, description, *priority, now);
+                if *status != Status::Backlog {
+                    todo.set_status(*status, now);
+                }
+
+                Todos::<T>::try_mutate(who, |todos| todos.try_push(todo).map_err(|_| ()))
+                    .expect("genesis todos exceed MaxTodosPerAccount");
+                NextId::<T>::insert(who, id + 1);
+                Pallet::<T>::update_stats(who);
+            }
+        }
+    }
+
+    // Pallets use events to inform users when important changes are made.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A todo was created
+        TodoCreated { who: T::AccountId, id: u64 },
+        /// A todo was updated
+        TodoUpdated { who: T::AccountId, id: u64 },
+        /// A todo was completed or reopened; follows `TodoStatusChanged` for moves into or
+        /// out of `Done`
+        TodoCompletionToggled { who: T::AccountId, id: u64, completed: bool },
+        /// A todo was deleted; it can be restored until it is purged
+        TodoDeleted { who: T::AccountId, id: u64 },
+        /// A todo was deleted by the admin origin
+        TodoForceDeleted { who: T::AccountId, id: u64 },
+        /// All todos of an account were removed by the admin origin
+        AccountForceCleared { who: T::AccountId, removed: u32 },
+        /// An account's statistics were recomputed by the admin origin
+        StatsForceSet { who: T::AccountId, stats: TodoStatistics },
+        /// A todo's due date was set or cleared
+        DueDateSet { who: T::AccountId, id: u64, due: Option<T::Moment> },
+        /// A todo is about to fall due and was raised to at least high priority
+        TodoDueSoon { who: T::AccountId, id: u64, due: T::Moment },
+        /// The encrypted payload of a todo was set
+        EncryptedPayloadSet { who: T::AccountId, id: u64 },
+        /// A todo call was scheduled
+        TodoScheduled {
+            who: T::AccountId,
+            task_id: u64,
+            when: BlockNumberFor<T>,
+            maybe_periodic: Option<(BlockNumberFor<T>, u32)>,
+        },
+        /// A scheduled todo call was cancelled
+        ScheduledTodoCancelled { who: T::AccountId, task_id: u64 },
+        /// A task was posted on the community board
+        BoardTaskPosted { poster: T::AccountId, id: u64 },
+        /// A board task was claimed
+        BoardTaskClaimed { claimant: T::AccountId, id: u64, expires_at: BlockNumberFor<T> },
+        /// A board task was reported done by its claimant
+        BoardTaskSubmitted { claimant: T::AccountId, id: u64 },
+        /// The poster approved a board task's completion and the task left the board
+        BoardTaskApproved { poster: T::AccountId, id: u64, claimant: T::AccountId },
+        /// The poster rejected a board task's completion and handed it back to the claimant
+        BoardTaskRejected { poster: T::AccountId, id: u64, claimant: T::AccountId },
+        /// The poster removed a board task
+        BoardTaskRemoved { poster: T::AccountId, id: u64 },
+        /// A todo moved to another status
+        TodoStatusChanged { who: T::AccountId, id: u64, from: Status, to: Status },
+        /// A deleted todo was restored
+        TodoRestored { who: T::AccountId, id: u64 },
+        /// A deleted, archived or expired todo was removed for good
+        TodoPurged { who: T::AccountId, id: u64 },
+        /// An account set or removed its retention policy
+        RetentionPolicySet { who: T::AccountId, policy: Option<RetentionPolicy<T::Moment>> },
+        /// A completed todo was moved to the archive by the account's retention policy
+        TodoArchived { who: T::AccountId, id: u64 },
+        /// An account removed all of its todos and reset its IDs
+        AllTodosCleared { who: T::AccountId, removed: u32 },
+        /// An account offered its todo list to another account
+        ListTransferOffered { from: T::AccountId, to: T::AccountId },
+        /// An account withdrew the offer of its todo list
+        ListTransferCancelled { from: T::AccountId, to: T::AccountId },
+        /// A todo moved to another account's list under a new ID
+        TodoTransferred { from: T::AccountId, old_id: u64, to: T::AccountId, new_id: u64 },
+        /// An account accepted another account's todo list
+        ListTransferred { from: T::AccountId, to: T::AccountId, mode: TransferMode, count: u32 },
+        /// The accounts allowed to sign due-date reminders were replaced
+        ReminderAuthoritiesSet { count: u32 },
+    }
+
+    // Errors inform users that something went wrong.
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The todo list is full
+        TodoListFull,
+        /// The todo title is too long
+        TitleTooLong,
+        /// The todo description is too long
+        DescriptionTooLong,
+        /// The todo was not found
+        TodoNotFound,
+        /// The account made too many operations in the current window
+        RateLimited,
+        /// The encrypted payload is too long
+        CiphertextTooLong,
+        /// The encrypted todo has too many recipients
+        TooManyRecipients,
+        /// The encrypted todo has no recipients
+        NoRecipients,
+        /// The account has too many pending scheduled todo calls
+        TooManyScheduledTodos,
+        /// The scheduled todo call was not found
+        ScheduledTodoNotFound,
+        /// The call cannot be scheduled
+        CallNotSchedulable,
+        /// The period of a scheduled call is shorter than `MinSchedulePeriod`
+        SchedulePeriodTooShort,
+        /// A scheduled call repeats more than `MaxScheduleRepetitions` times
+        TooManyScheduleRepetitions,
+        /// The board task was not found
+        BoardTaskNotFound,
+        /// The account has too many tasks on the board
+        TooManyBoardTasks,
+        /// Someone else is working on the board task
+        BoardTaskAlreadyClaimed,
+        /// Posters cannot claim their own board tasks
+        CannotClaimOwnTask,
+        /// The caller does not hold an active claim on the board task
+        NotClaimant,
+        /// Only the poster can review or remove a board task
+        NotPoster,
+        /// The board task has not been reported done
+        BoardTaskNotSubmitted,
+        /// The todo cannot move from its current status to the requested one
+        InvalidStatusTransition,
+        /// The todo is not among the account's deleted todos, or it has been purged
+        TodoNotDeleted,
+        /// An account cannot transfer its list to itself
+        CannotTransferToSelf,
+        /// The account has not offered its list to the caller
+        NoListTransfer,
+        /// More reminder authorities than `MaxReminderAuthorities`
+        TooManyReminderAuthorities,
+        /// The encoded call is too large for the scheduler to keep inline
+        ScheduledCallTooLarge,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Purge deleted todos whose grace period is over, then apply retention policies, as far
+        /// as the block has room
+        fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let used = Self::purge_deleted_todos(now, remaining_weight);
+            used.saturating_add(Self::apply_retention_policies(remaining_weight.saturating_sub(used)))
+        }
+        
+        /// Look for todos that are about to fall due and report them in a transaction signed
+        /// with the node's `todo` key. Nodes without a key among the reminder authorities do
+        /// nothing.
+        fn offchain_worker(block_number: BlockNumberFor<T>) {
+            let todos = Self::todos_due_for_reminder();
+            if todos.is_empty() {
+                return
+            }
             
-            // Update statistics
-            Self::update_stats(&who);
+            // Only sign with keys the pool will accept
+            let keys = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+                .into_iter()
+                .map(|key| <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key).into())
+                .filter(Self::is_reminder_authority)
+                .collect::<Vec<T::Public>>();
+            if keys.is_empty() {
+                return
+            }
             
-            // Emit event
-            Self::deposit_event(Event::TodoCreated { who, id });
+            let signer = Signer::<T, T::AuthorityId>::any_account().with_filter(keys);
+            let result = signer.send_unsigned_transaction(
+                |account| ReminderPayload {
+                    block_number,
+                    todos: todos.clone(),
+                    public: account.public.clone(),
+                },
+                |payload, signature| Call::remind_due_todos { payload, signature },
+            );
+            
+            if let Some((_, Err(()))) = result {
+                log::error!(target: "runtime::todo", "Failed to submit due-date reminders");
+            }
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            let Call::remind_due_todos { payload, signature } = call else {
+                return InvalidTransaction::Call.into()
+            };
+            
+            if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+                return InvalidTransaction::BadProof.into()
+            }
+            
+            // Only reminder authorities may report due todos
+            if !Self::is_reminder_authority(&payload.public) {
+                return InvalidTransaction::BadSigner.into()
+            }
+            
+            if payload.block_number > frame_system::Pallet::<T>::block_number() {
+                return InvalidTransaction::Future.into()
+            }
+            
+            // Reject reports that no longer change anything
+            if !payload.todos.iter().any(|(who, id)| Self::is_due_for_reminder(who, *id)) {
+                return InvalidTransaction::Stale.into()
+            }
+            
+            // One batch per block, whichever authority signed it
+            ValidTransaction::with_tag_prefix("TodoReminders")
+                .priority(T::UnsignedPriority::get())
+                .and_provides(payload.block_number)
+                .longevity(5)
+                .propagate(true)
+                .build()
+        }
+    }
+
+    // Dispatchable functions allows users to interact with the pallet and invoke state changes.
+    // These functions materialize as "extrinsics", which are often compared to transactions.
+    // Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a new todo
+        #[pallet::call_index(0)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+        pub fn create_todo(
+            origin: OriginFor<T>,
+            title: Vec<u8>,
+            description: Vec<u8>,
+            priority: Priority,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            Self::do_create_todo(who, title, description, priority)?;
             
             Ok(())
         }
         
-        /// Update a todo
+        /// Update a todo; the overwritten title, description and priority are kept as a revision
         #[pallet::call_index(1)]
-        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(2)))]
         pub fn update_todo(
             origin: OriginFor<T>,
             id: u64,
@@ -223,13 +1167,19 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             
+            // Count the operation against the rate limit
+            Self::note_operation(&who)?;
+            
             // Get current time
             let now = T::TimeProvider::now();
             
             // Update todo
-            Todos::<T>::try_mutate(&who, |todos| -> DispatchResult {
+            let revision = Todos::<T>::try_mutate(&who, |todos| -> Result<_, DispatchError> {
                 let todo = todos.iter_mut().find(|t| t.id == id).ok_or(Error::<T>::TodoNotFound)?;
                 
+                // Keep the fields about to be overwritten
+                let revision = todo.revision(Some(who.clone()), now);
+                
                 // Update title if provided
                 if let Some(new_title) = title {
                     let new_title = BoundedVec::<u8, T::MaxTitleLength>::try_from(new_title)
@@ -246,73 +1196,113 @@ pub mod pallet {
                 
                 // Update priority if provided
                 if let Some(new_priority) = priority {
-                    todo.priority = new_priority;
+                    todo.set_priority(new_priority);
                 }
                 
                 // Update timestamp
                 todo.updated_at = now;
                 
-                Ok(())
+                Ok(revision.differs_from(todo).then_some(revision))
             })?;
             
+            // Record the revision
+            if let Some(revision) = revision {
+                Self::record_revision(&who, id, revision);
+            }
+            
             // Update statistics
             Self::update_stats(&who);
             
+            // Index a snapshot of the todo
+            indexing::index_todo::<T>(&who, id, SnapshotAction::Updated);
+            
             // Emit event
             Self::deposit_event(Event::TodoUpdated { who, id });
             
             Ok(())
         }
         
-        /// Toggle the completion status of a todo
+        /// Toggle the completion status of a todo.
+        ///
+        /// Kept for clients that predate `set_status`: a done todo goes back to the backlog and
+        /// any other todo is marked done, as far as the status transitions allow.
         #[pallet::call_index(2)]
-        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                .saturating_add(T::DbWeight::get().writes(1))
+                .saturating_add(T::CompletionHandler::weight())
+        )]
         pub fn toggle_todo_completion(
             origin: OriginFor<T>,
             id: u64,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             
-            // Get current time
-            let now = T::TimeProvider::now();
+            // Pick the status on the other side of `Done`
+            let todos = Self::todos(&who);
+            let todo = todos.iter().find(|t| t.id == id).ok_or(Error::<T>::TodoNotFound)?;
+            let status = if todo.is_completed() { Status::Backlog } else { Status::Done };
             
-            // Toggle completion status
-            let mut completed = false;
-            Todos::<T>::try_mutate(&who, |todos| -> DispatchResult {
-                let todo = todos.iter_mut().find(|t| t.id == id).ok_or(Error::<T>::TodoNotFound)?;
-                
-                // Toggle completion status
-                todo.completed = !todo.completed;
-                completed = todo.completed;
-                
-                // Update timestamps
-                todo.updated_at = now;
-                if todo.completed {
-                    todo.completed_at = Some(now);
-                } else {
-                    todo.completed_at = None;
-                }
-                
-                Ok(())
+            Self::do_set_status(who, id, status)
+        }
+        
+        /// Delete a todo.
+        ///
+        /// The todo can be restored with `undelete_todo` for `DeletionGracePeriod` blocks, after
+        /// which it is purged. Deleting while `MaxTodosPerAccount` todos await purging purges the
+        /// oldest of them right away.
+        #[pallet::call_index(3)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 8)))]
+        pub fn delete_todo(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Take the todo off the list
+            let todo = Todos::<T>::try_mutate(&who, |todos| -> Result<_, DispatchError> {
+                let index = todos.iter().position(|t| t.id == id).ok_or(Error::<T>::TodoNotFound)?;
+                Ok(todos.remove(index))
             })?;
             
+            // Keep it until the grace period is over, making room by purging the oldest deletion
+            let purge_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::DeletionGracePeriod::get());
+            let mut deleted = DeletedTodos::<T>::get(&who);
+            if deleted.len() as u32 >= T::MaxTodosPerAccount::get() && !deleted.is_empty() {
+                let oldest = deleted.remove(0);
+                Self::purge_todo(&who, oldest.todo.id);
+            }
+            let _ = deleted.try_push(DeletedTodo { todo, purge_at });
+            DeletedTodos::<T>::insert(&who, deleted);
+            
+            // Queue the purge
+            PurgeQueueBounds::<T>::mutate(|(_, next)| {
+                PurgeQueue::<T>::insert(*next, (who.clone(), id, purge_at));
+                *next += 1;
+            });
+            
             // Update statistics
             Self::update_stats(&who);
             
+            // Index a snapshot of the todo
+            indexing::index_todo::<T>(&who, id, SnapshotAction::Deleted);
+            
             // Emit event
-            Self::deposit_event(Event::TodoCompletionToggled { who, id, completed });
+            Self::deposit_event(Event::TodoDeleted { who, id });
             
             Ok(())
         }
         
-        /// Delete a todo
-        #[pallet::call_index(3)]
+        /// Delete another account's todo
+        #[pallet::call_index(4)]
         #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
-        pub fn delete_todo(
+        pub fn force_delete_todo(
             origin: OriginFor<T>,
+            who: T::AccountId,
             id: u64,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            T::AdminOrigin::ensure_origin(origin)?;
             
             // Delete todo
             Todos::<T>::try_mutate(&who, |todos| -> DispatchResult {
@@ -321,36 +1311,1275 @@ pub mod pallet {
                 Ok(())
             })?;
             
+            // Remove the due date, encrypted payload and history
+            DueDates::<T>::remove(&who, id);
+            EncryptedPayloads::<T>::remove(&who, id);
+            TodoHistory::<T>::remove(&who, id);
+            
             // Update statistics
             Self::update_stats(&who);
             
+            // Notify the completion handler
+            T::CompletionHandler::on_removed(&who, id);
+            
+            // Index a snapshot of the todo
+            indexing::index_todo::<T>(&who, id, SnapshotAction::Deleted);
+            
             // Emit event
-            Self::deposit_event(Event::TodoDeleted { who, id });
+            Self::deposit_event(Event::TodoForceDeleted { who, id });
             
             Ok(())
         }
-    }
-
-    impl<T: Config> Pallet<T> {
-        /// Update todo statistics for an account
-        fn update_stats(who: &T::AccountId) {
-            let todos = Self::todos(who);
-            
-            let total = todos.len() as u32;
-            let completed = todos.iter().filter(|t| t.completed).count() as u32;
-            let pending = total - completed;
-            let high_priority = todos.iter()
-                .filter(|t| matches!(t.priority, Priority::High) && !t.completed)
-                .count() as u32;
-            
-            let stats = TodoStatistics {
-                total,
-                completed,
-                pending,
-                high_priority,
-            };
+        
+        /// Remove every todo of an account
+        #[pallet::call_index(5)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                .saturating_add(Weight::from_parts(3_000, 0).saturating_mul(T::MaxTodosPerAccount::get() as u64))
+                .saturating_add(T::DbWeight::get().writes(4))
+        )]
+        pub fn force_clear_account(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            
+            // Remove todos; `NextId` is kept so ids are never reused
+            let removed = Self::clear_todos(&who);
+            
+            // Emit event
+            Self::deposit_event(Event::AccountForceCleared { who, removed });
+            
+            Ok(())
+        }
+        
+        /// Recompute an account's statistics from its todos
+        #[pallet::call_index(6)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+        pub fn force_set_stats(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            
+            // Update statistics
+            let stats = Self::update_stats(&who);
+            
+            // Emit event
+            Self::deposit_event(Event::StatsForceSet { who, stats });
+            
+            Ok(())
+        }
+        
+        /// Set or clear the due date of a todo
+        #[pallet::call_index(7)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+        pub fn set_due_date(
+            origin: OriginFor<T>,
+            id: u64,
+            due: Option<T::Moment>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Check that the todo exists
+            ensure!(Self::todos(&who).iter().any(|t| t.id == id), Error::<T>::TodoNotFound);
+            
+            // Update the due date; a new date can be reminded about again
+            match due {
+                Some(due) => DueDates::<T>::insert(&who, id, DueDate { due, reminded: false }),
+                None => DueDates::<T>::remove(&who, id),
+            }
+            
+            // Index a snapshot of the todo
+            indexing::index_todo::<T>(&who, id, SnapshotAction::DueDateChanged);
+            
+            // Emit event
+            Self::deposit_event(Event::DueDateSet { who, id, due });
+            
+            Ok(())
+        }
+        
+        /// Raise todos that are about to fall due to high priority; urgent todos stay urgent.
+        ///
+        /// Submitted by the offchain worker as an unsigned transaction with a signed payload.
+        /// Todos that are not actually due are skipped.
+        #[pallet::call_index(8)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                .saturating_add(T::DbWeight::get().reads_writes(2, 4).saturating_mul(payload.todos.len() as u64))
+        )]
+        pub fn remind_due_todos(
+            origin: OriginFor<T>,
+            payload: ReminderPayload<T>,
+            _signature: T::Signature,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            
+            let now = T::TimeProvider::now();
+            
+            for (who, id) in payload.todos {
+                if !Self::is_due_for_reminder(&who, id) {
+                    continue
+                }
+                
+                // Raise the priority
+                let raised = Todos::<T>::mutate(&who, |todos| {
+                    todos.iter_mut().find(|t| t.id == id).map(|todo| {
+                        let revision = todo.revision(None, now);
+                        if todo.priority < Priority::High {
+                            todo.set_priority(Priority::High);
+                        }
+                        todo.updated_at = now;
+                        revision.differs_from(todo).then_some(revision)
+                    })
+                });
+                let Some(revision) = raised else {
+                    continue
+                };
+                
+                // Record the revision, made by the chain rather than an account
+                if let Some(revision) = revision {
+                    Self::record_revision(&who, id, revision);
+                }
+                
+                // Remember the reminder
+                let Some(due_date) = DueDates::<T>::mutate(&who, id, |due_date| {
+                    due_date.as_mut().map(|d| {
+                        d.reminded = true;
+                        d.due
+                    })
+                }) else {
+                    continue
+                };
+                
+                // Update statistics
+                Self::update_stats(&who);
+                
+                // Index a snapshot of the todo
+                indexing::index_todo::<T>(&who, id, SnapshotAction::Updated);
+                
+                // Emit event
+                Self::deposit_event(Event::TodoDueSoon { who, id, due: due_date });
+            }
+            
+            Ok(())
+        }
+        
+        /// Create a todo whose content is end-to-end encrypted.
+        ///
+        /// The todo's title and description stay empty; the content lives in the payload.
+        #[pallet::call_index(9)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(2)))]
+        pub fn create_encrypted_todo(
+            origin: OriginFor<T>,
+            sender: [u8; 32],
+            nonce: [u8; 24],
+            ciphertext: Vec<u8>,
+            recipients: Vec<WrappedKey>,
+            priority: Priority,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Check payload size
+            let payload = Self::bound_payload(sender, nonce, ciphertext, recipients)?;
+            
+            // Create the todo and store its payload
+            let id = Self::do_create_todo(who.clone(), Vec::new(), Vec::new(), priority)?;
+            EncryptedPayloads::<T>::insert(&who, id, payload);
+            
+            // Emit event
+            Self::deposit_event(Event::EncryptedPayloadSet { who, id });
+            
+            Ok(())
+        }
+        
+        /// Replace the encrypted payload of a todo
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2)))]
+        pub fn update_encrypted_todo(
+            origin: OriginFor<T>,
+            id: u64,
+            sender: [u8; 32],
+            nonce: [u8; 24],
+            ciphertext: Vec<u8>,
+            recipients: Vec<WrappedKey>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Count the operation against the rate limit
+            Self::note_operation(&who)?;
+            
+            // Check payload size
+            let payload = Self::bound_payload(sender, nonce, ciphertext, recipients)?;
+            
+            // Update timestamp
+            let now = T::TimeProvider::now();
+            Todos::<T>::try_mutate(&who, |todos| -> DispatchResult {
+                let todo = todos.iter_mut().find(|t| t.id == id).ok_or(Error::<T>::TodoNotFound)?;
+                todo.updated_at = now;
+                Ok(())
+            })?;
+            
+            // Store the payload
+            EncryptedPayloads::<T>::insert(&who, id, payload);
+            
+            // Index a snapshot of the todo
+            indexing::index_todo::<T>(&who, id, SnapshotAction::Updated);
+            
+            // Emit event
+            Self::deposit_event(Event::EncryptedPayloadSet { who, id });
+            
+            Ok(())
+        }
+        
+        /// Schedule a todo call to be dispatched on the caller's behalf.
+        ///
+        /// The call is dispatched at block `when` and, with `maybe_periodic` set to
+        /// `(period, count)`, repeated every `period` blocks for `count` dispatches in total. The
+        /// period must be at least `MinSchedulePeriod` blocks and the count at most
+        /// `MaxScheduleRepetitions`.
+        ///
+        /// Scheduled dispatches pay no fees of their own, so the weight of every dispatch is
+        /// charged here, up front. The call must fit inline in the scheduler's agenda.
+        #[pallet::call_index(11)]
+        #[pallet::weight({
+            let dispatches = maybe_periodic.as_ref().map_or(1, |(_, count)| (*count).max(1));
+            Weight::from_parts(10_000, 0)
+                .saturating_add(T::DbWeight::get().reads(T::MaxScheduledPerAccount::get() as u64))
+                .saturating_add(T::DbWeight::get().reads_writes(2, 5))
+                .saturating_add(call.get_dispatch_info().weight.saturating_mul(dispatches as u64))
+        })]
+        pub fn schedule_todo(
+            origin: OriginFor<T>,
+            call: Box<Call<T>>,
+            when: BlockNumberFor<T>,
+            maybe_periodic: Option<(BlockNumberFor<T>, u32)>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Only calls that manage the caller's own todos can be scheduled
+            ensure!(Self::is_schedulable(&call), Error::<T>::CallNotSchedulable);
+            
+            // Periodic calls cannot run too often or too many times
+            if let Some((period, count)) = maybe_periodic {
+                ensure!(
+                    !period.is_zero() && period >= T::MinSchedulePeriod::get(),
+                    Error::<T>::SchedulePeriodTooShort
+                );
+                ensure!(count <= T::MaxScheduleRepetitions::get(), Error::<T>::TooManyScheduleRepetitions);
+            }
+            
+            // Forget calls the scheduler is done with
+            Self::prune_scheduled_todos(&who);
+            
+            // Get next ID
+            let task_id = Self::next_schedule_id(&who);
+            
+            // Record the scheduled call
+            ScheduledTodos::<T>::try_mutate(&who, |tasks| {
+                tasks
+                    .try_push(ScheduledTodo { id: task_id, when, maybe_periodic })
+                    .map_err(|_| Error::<T>::TooManyScheduledTodos)
+            })?;
+            
+            // Increment next ID
+            NextScheduleId::<T>::insert(&who, task_id + 1);
+            
+            // Only calls the scheduler can keep inline are accepted, so none needs a preimage
+            let call = <T as Config>::RuntimeCall::from(*call).encode();
+            let call = Bounded::Inline(call.try_into().map_err(|_| Error::<T>::ScheduledCallTooLarge)?);
+            
+            // Hand the call to the scheduler
+            let origin = frame_system::RawOrigin::Signed(who.clone()).into();
+            T::Scheduler::schedule_named(
+                Self::scheduled_task_name(&who, task_id),
+                DispatchTime::At(when),
+                maybe_periodic,
+                LOWEST_PRIORITY,
+                origin,
+                call,
+            )?;
+            
+            // Emit event
+            Self::deposit_event(Event::TodoScheduled { who, task_id, when, maybe_periodic });
+            
+            Ok(())
+        }
+        
+        /// Cancel a pending scheduled todo call
+        #[pallet::call_index(12)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                .saturating_add(T::DbWeight::get().reads(T::MaxScheduledPerAccount::get() as u64))
+                .saturating_add(T::DbWeight::get().reads_writes(2, 3))
+        )]
+        pub fn cancel_scheduled_todo(
+            origin: OriginFor<T>,
+            task_id: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Forget calls the scheduler is done with
+            Self::prune_scheduled_todos(&who);
+            
+            // Remove the record
+            ScheduledTodos::<T>::try_mutate(&who, |tasks| -> DispatchResult {
+                let index = tasks.iter().position(|t| t.id == task_id)
+                    .ok_or(Error::<T>::ScheduledTodoNotFound)?;
+                tasks.remove(index);
+                Ok(())
+            })?;
+            
+            // Cancel the call in the scheduler
+            T::Scheduler::cancel_named(Self::scheduled_task_name(&who, task_id))?;
+            
+            // Emit event
+            Self::deposit_event(Event::ScheduledTodoCancelled { who, task_id });
+            
+            Ok(())
+        }
+        
+        /// Post a task on the community board
+        #[pallet::call_index(13)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 4)))]
+        pub fn post_board_task(
+            origin: OriginFor<T>,
+            title: Vec<u8>,
+            description: Vec<u8>,
+            priority: Priority,
+        ) -> DispatchResult {
+            let poster = ensure_signed(origin)?;
+            
+            // Count the operation against the rate limit
+            Self::note_operation(&poster)?;
+            
+            // Check title length
+            let title = BoundedVec::<u8, T::MaxTitleLength>::try_from(title)
+                .map_err(|_| Error::<T>::TitleTooLong)?;
+            
+            // Check description length
+            let description = BoundedVec::<u8, T::MaxDescriptionLength>::try_from(description)
+                .map_err(|_| Error::<T>::DescriptionTooLong)?;
+            
+            // Check the poster's quota
+            BoardTaskCount::<T>::try_mutate(&poster, |count| -> DispatchResult {
+                ensure!(*count < T::MaxBoardTasksPerAccount::get(), Error::<T>::TooManyBoardTasks);
+                *count += 1;
+                Ok(())
+            })?;
+            
+            // Get next ID
+            let id = Self::next_board_task_id();
+            
+            // Add task to the board
+            let task = BoardTask {
+                id,
+                poster: poster.clone(),
+                title,
+                description,
+                priority,
+                status: BoardTaskStatus::Open,
+                created_at: T::TimeProvider::now(),
+            };
+            BoardTasks::<T>::insert(id, task);
+            
+            // Increment next ID
+            NextBoardTaskId::<T>::put(id + 1);
+            
+            // Emit event
+            Self::deposit_event(Event::BoardTaskPosted { poster, id });
+            
+            Ok(())
+        }
+        
+        /// Claim a board task for `ClaimDuration` blocks.
+        ///
+        /// Open tasks and tasks whose previous claim has lapsed can be claimed.
+        #[pallet::call_index(14)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+        pub fn claim_board_task(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResult {
+            let claimant = ensure_signed(origin)?;
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            let expires_at = now.saturating_add(T::ClaimDuration::get());
+            
+            // Claim the task
+            BoardTasks::<T>::try_mutate(id, |task| -> DispatchResult {
+                let task = task.as_mut().ok_or(Error::<T>::BoardTaskNotFound)?;
+                ensure!(task.poster != claimant, Error::<T>::CannotClaimOwnTask);
+                ensure!(task.status.active_claimant(&now).is_none(), Error::<T>::BoardTaskAlreadyClaimed);
+                
+                task.status = BoardTaskStatus::Claimed { claimant: claimant.clone(), expires_at };
+                Ok(())
+            })?;
+            
+            // Emit event
+            Self::deposit_event(Event::BoardTaskClaimed { claimant, id, expires_at });
+            
+            Ok(())
+        }
+        
+        /// Report a claimed board task as done, for the poster to review
+        #[pallet::call_index(15)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+        pub fn submit_board_task(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResult {
+            let claimant = ensure_signed(origin)?;
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            
+            // Hand the task over for review
+            BoardTasks::<T>::try_mutate(id, |task| -> DispatchResult {
+                let task = task.as_mut().ok_or(Error::<T>::BoardTaskNotFound)?;
+                let holds_claim = matches!(
+                    &task.status,
+                    BoardTaskStatus::Claimed { claimant: holder, expires_at }
+                        if *holder == claimant && now < *expires_at
+                );
+                ensure!(holds_claim, Error::<T>::NotClaimant);
+                
+                task.status = BoardTaskStatus::Submitted { claimant: claimant.clone() };
+                Ok(())
+            })?;
+            
+            // Emit event
+            Self::deposit_event(Event::BoardTaskSubmitted { claimant, id });
+            
+            Ok(())
+        }
+        
+        /// Approve a submitted board task; the task is done and leaves the board
+        #[pallet::call_index(16)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2)))]
+        pub fn approve_board_task(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResult {
+            let poster = ensure_signed(origin)?;
+            
+            // Check the task is waiting for this poster's review
+            let task = Self::board_tasks(id).ok_or(Error::<T>::BoardTaskNotFound)?;
+            ensure!(task.poster == poster, Error::<T>::NotPoster);
+            let BoardTaskStatus::Submitted { claimant } = task.status else {
+                return Err(Error::<T>::BoardTaskNotSubmitted.into())
+            };
+            
+            // Remove the task from the board
+            Self::remove_from_board(&poster, id);
+            
+            // Emit event
+            Self::deposit_event(Event::BoardTaskApproved { poster, id, claimant });
+            
+            Ok(())
+        }
+        
+        /// Reject a submitted board task; the claimant gets a fresh claim to finish it
+        #[pallet::call_index(17)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
+        pub fn reject_board_task(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResult {
+            let poster = ensure_signed(origin)?;
+            
+            let now = frame_system::Pallet::<T>::block_number();
+            let expires_at = now.saturating_add(T::ClaimDuration::get());
+            
+            // Hand the task back to the claimant
+            let claimant = BoardTasks::<T>::try_mutate(id, |task| -> Result<T::AccountId, DispatchError> {
+                let task = task.as_mut().ok_or(Error::<T>::BoardTaskNotFound)?;
+                ensure!(task.poster == poster, Error::<T>::NotPoster);
+                let BoardTaskStatus::Submitted { claimant } = &task.status else {
+                    return Err(Error::<T>::BoardTaskNotSubmitted.into())
+                };
+                
+                let claimant = claimant.clone();
+                task.status = BoardTaskStatus::Claimed { claimant: claimant.clone(), expires_at };
+                Ok(claimant)
+            })?;
+            
+            // Emit event
+            Self::deposit_event(Event::BoardTaskRejected { poster, id, claimant });
+            
+            Ok(())
+        }
+        
+        /// Remove a board task nobody is working on
+        #[pallet::call_index(18)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(1, 2)))]
+        pub fn remove_board_task(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResult {
+            let poster = ensure_signed(origin)?;
+            
+            // Check the task can be removed
+            let now = frame_system::Pallet::<T>::block_number();
+            let task = Self::board_tasks(id).ok_or(Error::<T>::BoardTaskNotFound)?;
+            ensure!(task.poster == poster, Error::<T>::NotPoster);
+            ensure!(task.status.active_claimant(&now).is_none(), Error::<T>::BoardTaskAlreadyClaimed);
+            
+            // Remove the task from the board
+            Self::remove_from_board(&poster, id);
+            
+            // Emit event
+            Self::deposit_event(Event::BoardTaskRemoved { poster, id });
+            
+            Ok(())
+        }
+        
+        /// Set the urgency of a todo from 0 to 255.
+        ///
+        /// The todo moves to the priority whose band the urgency falls in; a change of priority
+        /// is kept as a revision.
+        #[pallet::call_index(19)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(2)))]
+        pub fn set_urgency(
+            origin: OriginFor<T>,
+            id: u64,
+            urgency: u8,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Count the operation against the rate limit
+            Self::note_operation(&who)?;
+            
+            // Get current time
+            let now = T::TimeProvider::now();
+            
+            // Update urgency
+            let revision = Todos::<T>::try_mutate(&who, |todos| -> Result<_, DispatchError> {
+                let todo = todos.iter_mut().find(|t| t.id == id).ok_or(Error::<T>::TodoNotFound)?;
+                let revision = todo.revision(Some(who.clone()), now);
+                todo.set_urgency(urgency);
+                todo.updated_at = now;
+                Ok(revision.differs_from(todo).then_some(revision))
+            })?;
+            
+            // Record the revision
+            if let Some(revision) = revision {
+                Self::record_revision(&who, id, revision);
+            }
+            
+            // Update statistics
+            Self::update_stats(&who);
+            
+            // Index a snapshot of the todo
+            indexing::index_todo::<T>(&who, id, SnapshotAction::Updated);
+            
+            // Emit event
+            Self::deposit_event(Event::TodoUpdated { who, id });
+            
+            Ok(())
+        }
+        
+        /// Move a todo to another status.
+        ///
+        /// Only the transitions allowed by [`Status::can_move_to`] are accepted; the time the
+        /// todo entered the new status is recorded.
+        #[pallet::call_index(20)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                .saturating_add(T::DbWeight::get().writes(1))
+                .saturating_add(T::CompletionHandler::weight())
+        )]
+        pub fn set_status(
+            origin: OriginFor<T>,
+            id: u64,
+            status: Status,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            Self::do_set_status(who, id, status)
+        }
+        
+        /// Restore a deleted todo that has not been purged yet
+        #[pallet::call_index(21)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().reads_writes(2, 3)))]
+        pub fn undelete_todo(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Find the deleted todo
+            let mut deleted = DeletedTodos::<T>::get(&who);
+            let index = deleted.iter().position(|d| d.todo.id == id).ok_or(Error::<T>::TodoNotDeleted)?;
+            let todo = deleted.remove(index).todo;
+            
+            // Put it back in its place on the list
+            Todos::<T>::try_mutate(&who, |todos| {
+                let position = todos.partition_point(|t| t.id < id);
+                todos.try_insert(position, todo).map_err(|_| Error::<T>::TodoListFull)
+            })?;
+            DeletedTodos::<T>::insert(&who, deleted);
+            
+            // Update statistics
+            Self::update_stats(&who);
+            
+            // Index a snapshot of the todo
+            indexing::index_todo::<T>(&who, id, SnapshotAction::Restored);
+            
+            // Emit event
+            Self::deposit_event(Event::TodoRestored { who, id });
+            
+            Ok(())
+        }
+        
+        /// Opt in to a retention policy, or out of it with `None`.
+        ///
+        /// Completed todos older than the policy's `max_age` are deleted or archived by a sweep
+        /// that runs when blocks have weight to spare, so they may stay a little longer.
+        #[pallet::call_index(22)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+        pub fn set_retention_policy(
+            origin: OriginFor<T>,
+            policy: Option<RetentionPolicy<T::Moment>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Set or remove the policy
+            RetentionPolicies::<T>::set(&who, policy.clone());
+            
+            // Emit event
+            Self::deposit_event(Event::RetentionPolicySet { who, policy });
+            
+            Ok(())
+        }
+        
+        /// Remove all of your todos, including deleted and archived ones, and your statistics.
+        ///
+        /// Unlike `force_clear_account` this also resets `NextId`, so IDs start from 0 again.
+        /// Pending scheduled calls and the offer of the list, which refer to the old IDs, are
+        /// cancelled.
+        #[pallet::call_index(23)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                .saturating_add(Weight::from_parts(3_000, 0).saturating_mul(T::MaxTodosPerAccount::get() as u64))
+                .saturating_add(T::DbWeight::get().reads_writes(2, 2).saturating_mul(T::MaxScheduledPerAccount::get() as u64))
+                .saturating_add(T::DbWeight::get().writes(7))
+        )]
+        pub fn clear_all_todos(
+            origin: OriginFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Remove todos and reset IDs
+            let removed = Self::clear_todos(&who);
+            NextId::<T>::remove(&who);
+            
+            // Cancel what still refers to the old IDs
+            Self::cancel_scheduled_todos(&who);
+            if let Some(to) = ListTransfers::<T>::take(&who) {
+                Self::deposit_event(Event::ListTransferCancelled { from: who.clone(), to });
+            }
+            
+            // Emit event
+            Self::deposit_event(Event::AllTodosCleared { who, removed });
+            
+            Ok(())
+        }
+        
+        /// Offer your todo list to another account, replacing any earlier offer.
+        ///
+        /// Nothing moves until the recipient accepts with `accept_list_transfer`.
+        #[pallet::call_index(24)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+        pub fn transfer_list(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(who != to, Error::<T>::CannotTransferToSelf);
+            
+            // Record the offer
+            ListTransfers::<T>::insert(&who, &to);
+            
+            // Emit event
+            Self::deposit_event(Event::ListTransferOffered { from: who, to });
+            
+            Ok(())
+        }
+        
+        /// Accept the todo list `from` offered you.
+        ///
+        /// The todos get new IDs from your `NextId`, in their original order, and keep their due
+        /// dates, encrypted payloads and history. Deleted and archived todos stay with `from`.
+        #[pallet::call_index(25)]
+        #[pallet::weight(
+            Weight::from_parts(10_000, 0)
+                .saturating_add(T::DbWeight::get().reads_writes(3, 9).saturating_mul(T::MaxTodosPerAccount::get() as u64))
+                .saturating_add(T::CompletionHandler::weight().saturating_mul(T::MaxTodosPerAccount::get() as u64))
+                .saturating_add(T::DbWeight::get().reads_writes(2, 2).saturating_mul(T::MaxScheduledPerAccount::get() as u64))
+                .saturating_add(T::DbWeight::get().reads_writes(4, 8))
+        )]
+        pub fn accept_list_transfer(
+            origin: OriginFor<T>,
+            from: T::AccountId,
+            mode: TransferMode,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::list_transfer(&from).as_ref() == Some(&who), Error::<T>::NoListTransfer);
+            
+            // Check that the todos fit
+            let offered = Todos::<T>::get(&from);
+            let mut todos = match mode {
+                TransferMode::Merge => Todos::<T>::get(&who),
+                TransferMode::Replace => BoundedVec::default(),
+            };
+            ensure!(
+                todos.len() + offered.len() <= T::MaxTodosPerAccount::get() as usize,
+                Error::<T>::TodoListFull
+            );
+            
+            // Remove the replaced todos for good
+            if mode == TransferMode::Replace {
+                for todo in Todos::<T>::take(&who).iter() {
+                    Self::purge_todo(&who, todo.id);
+                    indexing::index_todo::<T>(&who, todo.id, SnapshotAction::Deleted);
+                }
+            }
+            
+            // Move the todos under new IDs, with what is kept next to them
+            ListTransfers::<T>::remove(&from);
+            Todos::<T>::remove(&from);
+            let mut next_id = Self::next_id(&who);
+            let mut moved = Vec::with_capacity(offered.len());
+            for mut todo in offered.into_iter() {
+                let old_id = todo.id;
+                todo.id = next_id;
+                next_id += 1;
+                
+                if let Some(due) = DueDates::<T>::take(&from, old_id) {
+                    DueDates::<T>::insert(&who, todo.id, due);
+                }
+                if let Some(payload) = EncryptedPayloads::<T>::take(&from, old_id) {
+                    EncryptedPayloads::<T>::insert(&who, todo.id, payload);
+                }
+                let history = TodoHistory::<T>::take(&from, old_id);
+                if !history.is_empty() {
+                    TodoHistory::<T>::insert(&who, todo.id, history);
+                }
+                
+                // Whatever a completed todo earned follows it
+                T::CompletionHandler::on_transferred(&from, old_id, &who, todo.id);
+                
+                moved.push((old_id, todo.id));
+                let _ = todos.try_push(todo);
+            }
+            NextId::<T>::insert(&who, next_id);
+            Todos::<T>::insert(&who, todos);
+            
+            // The sender's scheduled calls refer to IDs that are gone
+            Self::cancel_scheduled_todos(&from);
+            
+            // Update statistics
+            Self::update_stats(&from);
+            Self::update_stats(&who);
+            
+            // Index snapshots and emit events for every todo
+            for (old_id, new_id) in moved.iter().copied() {
+                indexing::index_todo::<T>(&from, old_id, SnapshotAction::Deleted);
+                indexing::index_todo::<T>(&who, new_id, SnapshotAction::Transferred);
+                Self::deposit_event(Event::TodoTransferred {
+                    from: from.clone(),
+                    old_id,
+                    to: who.clone(),
+                    new_id,
+                });
+            }
+            
+            // Emit event
+            Self::deposit_event(Event::ListTransferred { from, to: who, mode, count: moved.len() as u32 });
+            
+            Ok(())
+        }
+        
+        /// Withdraw the offer of your todo list
+        #[pallet::call_index(26)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+        pub fn cancel_list_transfer(
+            origin: OriginFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            // Remove the offer
+            let to = ListTransfers::<T>::take(&who).ok_or(Error::<T>::NoListTransfer)?;
+            
+            // Emit event
+            Self::deposit_event(Event::ListTransferCancelled { from: who, to });
+            
+            Ok(())
+        }
+        
+        /// Replace the accounts whose `todo` key may sign due-date reminders
+        #[pallet::call_index(27)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(T::DbWeight::get().writes(1)))]
+        pub fn set_reminder_authorities(
+            origin: OriginFor<T>,
+            authorities: Vec<T::AccountId>,
+        ) -> DispatchResult {
+            T::AdminOrigin::ensure_origin(origin)?;
+            
+            // Check the number of authorities
+            let authorities = BoundedVec::<T::AccountId, T::MaxReminderAuthorities>::try_from(authorities)
+                .map_err(|_| Error::<T>::TooManyReminderAuthorities)?;
+            let count = authorities.len() as u32;
+            
+            // Store the authorities
+            ReminderAuthorities::<T>::put(authorities);
+            
+            // Emit event
+            Self::deposit_event(Event::ReminderAuthoritiesSet { count });
+            
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Create a todo for an account and return its ID
+        fn do_create_todo(
+            who: T::AccountId,
+            title: Vec<u8>,
+            description: Vec<u8>,
+            priority: Priority,
+        ) -> Result<u64, DispatchError> {
+            // Count the operation against the rate limit
+            Self::note_operation(&who)?;
+            
+            // Check title length
+            let title = BoundedVec::<u8, T::MaxTitleLength>::try_from(title)
+                .map_err(|_| Error::<T>::TitleTooLong)?;
+            
+            // Check description length
+            let description = BoundedVec::<u8, T::MaxDescriptionLength>::try_from(description)
+                .map_err(|_| Error::<T>::DescriptionTooLong)?;
+            
+            // Get current time
+            let now = T::TimeProvider::now();
+            
+            // Get next ID
+            let id = Self::next_id(&who);
+            
+            // Create new todo
+            let todo = Todo::new(id, title, description, priority, now);
+            
+            // Add todo to storage
+            Todos::<T>::try_mutate(&who, |todos| {
+                todos.try_push(todo).map_err(|_| Error::<T>::TodoListFull)
+            })?;
+            
+            // Increment next ID
+            NextId::<T>::insert(&who, id + 1);
+            
+            // Update statistics
+            Self::update_stats(&who);
+            
+            // Index a snapshot of the todo
+            indexing::index_todo::<T>(&who, id, SnapshotAction::Created);
+            
+            // Emit event
+            Self::deposit_event(Event::TodoCreated { who, id });
+            
+            Ok(id)
+        }
+        
+        /// Keep `revision` as a todo's latest revision, dropping the oldest once the history is full
+        fn record_revision(who: &T::AccountId, id: u64, revision: TodoRevision<T>) {
+            TodoHistory::<T>::mutate(who, id, |history| {
+                if history.len() as u32 >= T::MaxRevisions::get() && !history.is_empty() {
+                    history.remove(0);
+                }
+                let _ = history.try_push(revision);
+            });
+        }
+        
+        /// Purge deleted todos whose grace period is over, oldest first, within `remaining_weight`.
+        ///
+        /// Returns the weight used.
+        fn purge_deleted_todos(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let db = T::DbWeight::get();
+            let mut used = db.reads_writes(1, 1);
+            let per_entry = db.reads_writes(3, 6).saturating_add(T::CompletionHandler::weight());
+            if remaining_weight.any_lt(used) {
+                return Weight::zero()
+            }
+            
+            let (mut first, next) = PurgeQueueBounds::<T>::get();
+            let start = first;
+            while first < next && !remaining_weight.any_lt(used.saturating_add(per_entry)) {
+                used = used.saturating_add(per_entry);
+                
+                // Stop at the first todo still in its grace period
+                let entry = PurgeQueue::<T>::get(first);
+                if let Some((_, _, purge_at)) = &entry {
+                    if *purge_at > now {
+                        break
+                    }
+                }
+                PurgeQueue::<T>::remove(first);
+                first += 1;
+                
+                // Skip todos that were restored or already purged
+                let Some((who, id, purge_at)) = entry else { continue };
+                let mut deleted = DeletedTodos::<T>::get(&who);
+                let Some(index) = deleted.iter().position(|d| d.todo.id == id && d.purge_at == purge_at) else {
+                    continue
+                };
+                deleted.remove(index);
+                DeletedTodos::<T>::insert(&who, deleted);
+                Self::purge_todo(&who, id);
+            }
+            
+            if first != start {
+                PurgeQueueBounds::<T>::put((first, next));
+            }
+            
+            used
+        }
+        
+        /// Apply the retention policies of as many accounts as fit in `remaining_weight`, carrying on
+        /// from `RetentionCursor`.
+        ///
+        /// Returns the weight used.
+        fn apply_retention_policies(remaining_weight: Weight) -> Weight {
+            let db = T::DbWeight::get();
+            let mut used = db.reads_writes(1, 1);
+            // Each todo may be indexed, purged and reported, and the oldest archived todo purged
+            // to make room for it
+            let per_todo = db.reads_writes(2, 5).saturating_add(T::CompletionHandler::weight());
+            let per_account = db
+                .reads_writes(3, 3)
+                .saturating_add(per_todo.saturating_mul(T::MaxTodosPerAccount::get() as u64));
+            if remaining_weight.any_lt(used.saturating_add(per_account)) {
+                return Weight::zero()
+            }
+            
+            // Carry on after the last account of the previous sweep
+            let mut policies = match RetentionCursor::<T>::get() {
+                Some(last) => RetentionPolicies::<T>::iter_from(RetentionPolicies::<T>::hashed_key_for(last)),
+                None => RetentionPolicies::<T>::iter(),
+            };
+            let now = T::TimeProvider::now();
+            let mut cursor = None;
+            while !remaining_weight.any_lt(used.saturating_add(per_account)) {
+                let Some((who, policy)) = policies.next() else { break };
+                used = used.saturating_add(per_account);
+                Self::apply_retention_policy(&who, &policy, now);
+                cursor = Some(who);
+            }
+            
+            // Start over once every account was swept
+            match cursor {
+                Some(last) if policies.next().is_some() => RetentionCursor::<T>::put(last),
+                _ => RetentionCursor::<T>::kill(),
+            }
+            
+            used
+        }
+        
+        /// Delete or archive the todos of an account completed longer than its policy allows
+        fn apply_retention_policy(who: &T::AccountId, policy: &RetentionPolicy<T::Moment>, now: T::Moment) {
+            // Split off the todos that are too old
+            let (expired, kept): (Vec<_>, Vec<_>) = Todos::<T>::get(who).into_inner().into_iter().partition(|todo| {
+                todo.completed_at().is_some_and(|at| now.saturating_sub(at) > policy.max_age)
+            });
+            if expired.is_empty() {
+                return
+            }
+            Todos::<T>::insert(who, BoundedVec::truncate_from(kept));
+            
+            match policy.action {
+                RetentionAction::Delete => {
+                    for todo in expired.iter() {
+                        indexing::index_todo::<T>(who, todo.id, SnapshotAction::Deleted);
+                        Self::purge_todo(who, todo.id);
+                    }
+                },
+                RetentionAction::Archive => {
+                    // Make room in the archive by purging its oldest todos
+                    let mut archive = ArchivedTodos::<T>::get(who);
+                    for todo in expired {
+                        let id = todo.id;
+                        if archive.len() as u32 >= T::MaxTodosPerAccount::get() && !archive.is_empty() {
+                            let oldest = archive.remove(0);
+                            Self::purge_todo(who, oldest.id);
+                        }
+                        indexing::index_snapshot::<T>(who, id, SnapshotAction::Archived, Some(todo.clone()));
+                        let _ = archive.try_push(todo);
+                        Self::deposit_event(Event::TodoArchived { who: who.clone(), id });
+                    }
+                    ArchivedTodos::<T>::insert(who, archive);
+                },
+            }
+            
+            // Update statistics
+            Self::update_stats(who);
+        }
+        
+        /// Remove the todos, deleted and archived todos and statistics of an account with what is
+        /// kept next to them, returning how many todos were on its list
+        fn clear_todos(who: &T::AccountId) -> u32 {
+            let todos = Todos::<T>::take(who);
+            let deleted = DeletedTodos::<T>::take(who);
+            let archived = ArchivedTodos::<T>::take(who);
+            TodoStats::<T>::remove(who);
+            let limit = T::MaxTodosPerAccount::get().saturating_mul(3);
+            let _ = DueDates::<T>::clear_prefix(who, limit, None);
+            let _ = EncryptedPayloads::<T>::clear_prefix(who, limit, None);
+            let _ = TodoHistory::<T>::clear_prefix(who, limit, None);
+            
+            // Notify the completion handler and index the deletions
+            for todo in todos.iter() {
+                T::CompletionHandler::on_removed(who, todo.id);
+                indexing::index_todo::<T>(who, todo.id, SnapshotAction::Deleted);
+            }
+            for id in deleted.iter().map(|d| d.todo.id).chain(archived.iter().map(|t| t.id)) {
+                T::CompletionHandler::on_removed(who, id);
+            }
+            
+            todos.len() as u32
+        }
+        
+        /// Remove what is kept of a deleted todo besides the todo itself
+        fn purge_todo(who: &T::AccountId, id: u64) {
+            // Remove the due date, encrypted payload and history
+            DueDates::<T>::remove(who, id);
+            EncryptedPayloads::<T>::remove(who, id);
+            TodoHistory::<T>::remove(who, id);
+            
+            // Notify the completion handler
+            T::CompletionHandler::on_removed(who, id);
+            
+            // Emit event
+            Self::deposit_event(Event::TodoPurged { who: who.clone(), id });
+        }
+        
+        /// Move a todo of an account to `status`
+        fn do_set_status(who: T::AccountId, id: u64, status: Status) -> DispatchResult {
+            // Count the operation against the rate limit
+            Self::note_operation(&who)?;
+            
+            // Get current time
+            let now = T::TimeProvider::now();
+            
+            // Update status
+            let (from, priority) = Todos::<T>::try_mutate(&who, |todos| -> Result<_, DispatchError> {
+                let todo = todos.iter_mut().find(|t| t.id == id).ok_or(Error::<T>::TodoNotFound)?;
+                let from = todo.status;
+                ensure!(from.can_move_to(status), Error::<T>::InvalidStatusTransition);
+                
+                todo.set_status(status, now);
+                Ok((from, todo.priority))
+            })?;
+            
+            // Update statistics
+            Self::update_stats(&who);
+            
+            // Notify the completion handler
+            let completion_changed = from == Status::Done || status == Status::Done;
+            if status == Status::Done {
+                T::CompletionHandler::on_completed(&who, id, priority);
+            } else if from == Status::Done {
+                T::CompletionHandler::on_reopened(&who, id, priority);
+            }
+            
+            // Index a snapshot of the todo
+            let action = if completion_changed {
+                SnapshotAction::CompletionToggled
+            } else {
+                SnapshotAction::StatusChanged
+            };
+            indexing::index_todo::<T>(&who, id, action);
+            
+            // Emit events
+            Self::deposit_event(Event::TodoStatusChanged { who: who.clone(), id, from, to: status });
+            if completion_changed {
+                let completed = status == Status::Done;
+                Self::deposit_event(Event::TodoCompletionToggled { who, id, completed });
+            }
+            
+            Ok(())
+        }
+        
+        /// Check the size bounds of an encrypted payload
+        fn bound_payload(
+            sender: [u8; 32],
+            nonce: [u8; 24],
+            ciphertext: Vec<u8>,
+            recipients: Vec<WrappedKey>,
+        ) -> Result<EncryptedPayload<T>, DispatchError> {
+            ensure!(!recipients.is_empty(), Error::<T>::NoRecipients);
+            
+            let ciphertext = BoundedVec::<u8, T::MaxCiphertextLength>::try_from(ciphertext)
+                .map_err(|_| Error::<T>::CiphertextTooLong)?;
+            let recipients = BoundedVec::<WrappedKey, T::MaxRecipients>::try_from(recipients)
+                .map_err(|_| Error::<T>::TooManyRecipients)?;
+            
+            Ok(EncryptedPayload { sender, nonce, ciphertext, recipients })
+        }
+        
+        /// Remove a task from the board and free its slot in the poster's quota
+        fn remove_from_board(poster: &T::AccountId, id: u64) {
+            BoardTasks::<T>::remove(id);
+            BoardTaskCount::<T>::mutate_exists(poster, |count| {
+                *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+            });
+        }
+        
+        /// Name of a scheduled todo call in the scheduler
+        pub fn scheduled_task_name(who: &T::AccountId, task_id: u64) -> TaskName {
+            (b"pallet-todo::schedule", who, task_id).using_encoded(sp_io::hashing::blake2_256)
+        }
+        
+        /// Whether a call may be scheduled with `schedule_todo`: only calls that create or work on
+        /// a single todo, never ones that clear or hand over the whole list
+        fn is_schedulable(call: &Call<T>) -> bool {
+            matches!(
+                call,
+                Call::create_todo { .. } |
+                    Call::update_todo { .. } |
+                    Call::toggle_todo_completion { .. } |
+                    Call::set_status { .. }
+            )
+        }
+        
+        /// Cancel every pending scheduled call of an account
+        fn cancel_scheduled_todos(who: &T::AccountId) {
+            for task in ScheduledTodos::<T>::take(who) {
+                // Calls the scheduler is done with are gone already
+                if T::Scheduler::cancel_named(Self::scheduled_task_name(who, task.id)).is_ok() {
+                    Self::deposit_event(Event::ScheduledTodoCancelled { who: who.clone(), task_id: task.id });
+                }
+            }
+        }
+        
+        /// Drop records of scheduled calls the scheduler has dispatched for the last time
+        fn prune_scheduled_todos(who: &T::AccountId) {
+            ScheduledTodos::<T>::mutate(who, |tasks| {
+                tasks.retain(|task| {
+                    T::Scheduler::next_dispatch_time(Self::scheduled_task_name(who, task.id)).is_ok()
+                })
+            });
+        }
+        
+        /// Whether a todo is open, falls due within `ReminderLeadTime` and has not been reminded yet
+        pub fn is_due_for_reminder(who: &T::AccountId, id: u64) -> bool {
+            let Some(due_date) = Self::due_dates(who, id) else { return false };
+            if due_date.reminded {
+                return false
+            }
+            
+            let open = Self::todos(who).iter().any(|t| t.id == id && t.status.is_open());
+            let horizon = T::TimeProvider::now().saturating_add(T::ReminderLeadTime::get());
+            
+            open && due_date.due <= horizon
+        }
+        
+        /// Whether a `todo` key belongs to one of the reminder authorities
+        fn is_reminder_authority(public: &T::Public) -> bool {
+            ReminderAuthorities::<T>::get().contains(&public.clone().into_account())
+        }
+        
+        /// Todos the offchain worker should report, up to `MaxRemindersPerBlock`
+        pub fn todos_due_for_reminder() -> BoundedVec<(T::AccountId, u64), T::MaxRemindersPerBlock> {
+            let due = DueDates::<T>::iter_keys()
+                .filter(|(who, id)| Self::is_due_for_reminder(who, *id))
+                .take(T::MaxRemindersPerBlock::get() as usize)
+                .collect::<Vec<_>>();
+            
+            BoundedVec::truncate_from(due)
+        }
+        
+        /// Rate limiting state of an account as of the current block, with an expired window reset
+        fn current_rate_limit(who: &T::AccountId) -> RateLimitState<BlockNumberFor<T>> {
+            let now = frame_system::Pallet::<T>::block_number();
+            let state = Self::rate_limits(who);
+            
+            if now >= state.window_start.saturating_add(T::RateLimitWindow::get()) {
+                RateLimitState { window_start: now, ops: 0 }
+            } else {
+                state
+            }
+        }
+        
+        /// Whether the account has used up its operations for the current window
+        pub fn is_rate_limited(who: &T::AccountId) -> bool {
+            Self::current_rate_limit(who).ops >= T::MaxOpsPerWindow::get()
+        }
+        
+        /// Whether the runtime may waive the fee of `call` while the caller has free calls left.
+        ///
+        /// Only the cheap calls that manage a single todo qualify.
+        pub fn is_free_call(call: &Call<T>) -> bool {
+            matches!(
+                call,
+                Call::create_todo { .. } |
+                    Call::update_todo { .. } |
+                    Call::toggle_todo_completion { .. } |
+                    Call::set_status { .. }
+            )
+        }
+        
+        /// Number of fee-free calls an account has left in the current period
+        pub fn free_calls_left(who: &T::AccountId) -> u32 {
+            let period = frame_system::Pallet::<T>::block_number() / T::FreeCallPeriod::get();
+            let record = FreeTodoCalls::<T>::get(who);
+            let used = if record.period == period { record.used } else { 0 };
+            
+            T::FreeCallsPerPeriod::get().saturating_sub(used)
+        }
+        
+        /// Use up one of the account's fee-free calls for the current period
+        pub fn use_free_call(who: &T::AccountId) {
+            let period = frame_system::Pallet::<T>::block_number() / T::FreeCallPeriod::get();
+            FreeTodoCalls::<T>::mutate(who, |record| {
+                if record.period != period {
+                    *record = FreeCallsUsed { period, used: 0 };
+                }
+                record.used = record.used.saturating_add(1);
+            });
+        }
+        
+        /// Count an operation against the account's rate limit
+        fn note_operation(who: &T::AccountId) -> DispatchResult {
+            let mut state = Self::current_rate_limit(who);
+            ensure!(state.ops < T::MaxOpsPerWindow::get(), Error::<T>::RateLimited);
+            
+            state.ops += 1;
+            RateLimits::<T>::insert(who, state);
+            
+            Ok(())
+        }
+        
+        /// Update todo statistics for an account
+        fn update_stats(who: &T::AccountId) -> TodoStatistics {
+            let stats = TodoStatistics::of(&Self::todos(who));
+            
+            TodoStats::<T>::insert(who, stats.clone());
+            
+            stats
+        }
+        
+        /// An account's todos in the given order
+        pub fn sorted_todos(who: &T::AccountId, sort: TodoSort) -> Vec<Todo<T>> {
+            let mut todos = Self::todos(who).into_inner();
+            
+            match sort {
+                TodoSort::Id => todos.sort_by_key(|t| t.id),
+                TodoSort::Urgency => todos.sort_by_key(|t| (Reverse(t.urgency), t.id)),
+                TodoSort::DueDate => todos.sort_by_key(|t| {
+                    let due = Self::due_dates(who, t.id).map(|d| d.due);
+                    (due.is_none(), due, t.id)
+                }),
+            }
             
-            TodoStats::<T>::insert(who, stats);
+            todos
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,324 @@
+//! Storage migrations of the todo pallet.
+//!
+//! Each migration checks the pallet's on-chain storage version and does nothing once it has
+//! run, so it can stay in the runtime's `Executive` until the next release. Migrations only
+//! use the layouts of the versions they migrate between, so they can run one after another.
+
+/// Version 1 adds the 0 to 255 urgency scale.
+///
+/// Every todo gets the urgency of its priority, and the statistics gain a count of pending
+/// todos per priority.
+pub mod v1 {
+    use crate::{Config, Pallet, Priority};
+    use codec::{Decode, Encode};
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        weights::Weight,
+    };
+    use sp_std::{marker::PhantomData, vec::Vec};
+
+    /// A todo as stored in version 0, without an urgency
+    #[derive(Encode, Decode)]
+    pub struct TodoV0<T: Config> {
+        pub id: u64,
+        pub title: BoundedVec<u8, T::MaxTitleLength>,
+        pub description: BoundedVec<u8, T::MaxDescriptionLength>,
+        pub completed: bool,
+        pub priority: Priority,
+        pub created_at: T::Moment,
+        pub updated_at: T::Moment,
+        pub completed_at: Option<T::Moment>,
+    }
+
+    /// A todo as stored in version 1
+    #[derive(Encode, Decode)]
+    pub struct TodoV1<T: Config> {
+        pub id: u64,
+        pub title: BoundedVec<u8, T::MaxTitleLength>,
+        pub description: BoundedVec<u8, T::MaxDescriptionLength>,
+        pub completed: bool,
+        pub priority: Priority,
+        pub urgency: u8,
+        pub created_at: T::Moment,
+        pub updated_at: T::Moment,
+        pub completed_at: Option<T::Moment>,
+    }
+
+    impl<T: Config> From<TodoV0<T>> for TodoV1<T> {
+        fn from(old: TodoV0<T>) -> Self {
+            TodoV1 {
+                id: old.id,
+                title: old.title,
+                description: old.description,
+                completed: old.completed,
+                priority: old.priority,
+                urgency: old.priority.urgency(),
+                created_at: old.created_at,
+                updated_at: old.updated_at,
+                completed_at: old.completed_at,
+            }
+        }
+    }
+
+    /// Todo statistics as stored in version 0, counting only high priority todos
+    #[derive(Encode, Decode)]
+    pub struct TodoStatisticsV0 {
+        pub total: u32,
+        pub completed: u32,
+        pub pending: u32,
+        pub high_priority: u32,
+    }
+
+    /// Todo statistics as stored in version 1
+    #[derive(Encode, Decode, Eq, PartialEq, Default)]
+    pub struct TodoStatisticsV1 {
+        pub total: u32,
+        pub completed: u32,
+        pub pending: u32,
+        pub low_priority: u32,
+        pub medium_priority: u32,
+        pub high_priority: u32,
+        pub urgent_priority: u32,
+    }
+
+    impl TodoStatisticsV1 {
+        /// Statistics of a version 1 todo list
+        pub fn of<T: Config>(todos: &[TodoV1<T>]) -> Self {
+            let mut stats = Self { total: todos.len() as u32, ..Default::default() };
+            for todo in todos {
+                if todo.completed {
+                    stats.completed += 1;
+                    continue
+                }
+
+                stats.pending += 1;
+                match todo.priority {
+                    Priority::Low => stats.low_priority += 1,
+                    Priority::Medium => stats.medium_priority += 1,
+                    Priority::High => stats.high_priority += 1,
+                    Priority::Urgent => stats.urgent_priority += 1,
+                }
+            }
+            stats
+        }
+    }
+
+    /// `Todos` as stored in version 1
+    #[frame_support::storage_alias]
+    pub type Todos<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        BoundedVec<TodoV1<T>, <T as Config>::MaxTodosPerAccount>,
+        ValueQuery,
+    >;
+
+    /// `TodoStats` as stored in version 1
+    #[frame_support::storage_alias]
+    pub type TodoStats<T: Config> = StorageMap<
+        Pallet<T>,
+        Blake2_128Concat,
+        <T as frame_system::Config>::AccountId,
+        TodoStatisticsV1,
+        ValueQuery,
+    >;
+
+    /// Migrates `Todos` and `TodoStats` from version 0 to version 1
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 0 {
+                log::info!(
+                    target: "runtime::todo",
+                    "Skipping migration to v1, storage is at {:?}",
+                    on_chain,
+                );
+                return T::DbWeight::get().reads(1)
+            }
+
+            // Give every todo the urgency of its priority
+            let mut lists = 0u64;
+            Todos::<T>::translate::<BoundedVec<TodoV0<T>, T::MaxTodosPerAccount>, _>(|_, old| {
+                lists += 1;
+                let todos = old.into_iter().map(TodoV1::from).collect::<Vec<_>>();
+                Some(BoundedVec::truncate_from(todos))
+            });
+
+            // Recompute the statistics from the migrated todos
+            let mut stats = 0u64;
+            TodoStats::<T>::translate::<TodoStatisticsV0, _>(|who, _| {
+                stats += 1;
+                Some(TodoStatisticsV1::of(&Todos::<T>::get(&who)))
+            });
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+            log::info!(
+                target: "runtime::todo",
+                "Migrated {} todo lists and {} statistics to v1",
+                lists,
+                stats,
+            );
+
+            T::DbWeight::get().reads_writes(1 + lists + stats * 2, 1 + lists + stats)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            // The length prefix of a list decodes whatever the item type
+            let todos = Todos::<T>::iter_keys()
+                .map(|who| Todos::<T>::decode_len(&who).unwrap_or(0) as u64)
+                .sum::<u64>();
+
+            Ok(todos.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let expected = u64::decode(&mut &state[..])
+                .map_err(|_| "the pre-upgrade state is a todo count")?;
+
+            let mut todos = 0u64;
+            for (who, list) in Todos::<T>::iter() {
+                todos += list.len() as u64;
+                ensure!(
+                    list.iter().all(|t| t.urgency == t.priority.urgency()),
+                    "todos have the urgency of their priority"
+                );
+                ensure!(
+                    TodoStats::<T>::get(&who) == TodoStatisticsV1::of(&list),
+                    "statistics match the todos"
+                );
+            }
+            ensure!(todos == expected, "no todo was lost");
+            ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage is at v1");
+
+            Ok(())
+        }
+    }
+}
+
+/// Version 2 replaces the completed flag with the status workflow.
+///
+/// Completed todos become `Done`, entered when they were completed, and all other todos go to
+/// the backlog, entered when they were created. The statistics gain the per-status counts.
+pub mod v2 {
+    use super::v1::{TodoStatisticsV1, TodoV1};
+    use crate::{
+        Config, Pallet, Status, StatusTimestamps, Todo, TodoStatistics, TodoStats, Todos,
+    };
+    use codec::{Decode, Encode};
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+        weights::Weight,
+    };
+    use sp_std::{marker::PhantomData, vec::Vec};
+
+    impl<T: Config> From<TodoV1<T>> for Todo<T> {
+        fn from(old: TodoV1<T>) -> Self {
+            let mut entered_at =
+                StatusTimestamps { backlog: Some(old.created_at), ..Default::default() };
+            let status = if old.completed {
+                // Todos completed before `completed_at` was kept were last updated on completion
+                entered_at.done = old.completed_at.or(Some(old.updated_at));
+                Status::Done
+            } else {
+                Status::Backlog
+            };
+
+            Todo {
+                id: old.id,
+                title: old.title,
+                description: old.description,
+                status,
+                priority: old.priority,
+                urgency: old.urgency,
+                created_at: old.created_at,
+                updated_at: old.updated_at,
+                entered_at,
+            }
+        }
+    }
+
+    /// Migrates `Todos` and `TodoStats` from version 1 to version 2
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!(
+                    target: "runtime::todo",
+                    "Skipping migration to v2, storage is at {:?}",
+                    on_chain,
+                );
+                return T::DbWeight::get().reads(1)
+            }
+
+            // Move every todo into the status matching its completed flag
+            let mut lists = 0u64;
+            Todos::<T>::translate::<BoundedVec<TodoV1<T>, T::MaxTodosPerAccount>, _>(|_, old| {
+                lists += 1;
+                let todos = old.into_iter().map(Todo::from).collect::<Vec<_>>();
+                Some(BoundedVec::truncate_from(todos))
+            });
+
+            // Recompute the statistics from the migrated todos
+            let mut stats = 0u64;
+            TodoStats::<T>::translate::<TodoStatisticsV1, _>(|who, _| {
+                stats += 1;
+                Some(TodoStatistics::of(&Todos::<T>::get(&who)))
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            log::info!(
+                target: "runtime::todo",
+                "Migrated {} todo lists and {} statistics to v2",
+                lists,
+                stats,
+            );
+
+            T::DbWeight::get().reads_writes(1 + lists + stats * 2, 1 + lists + stats)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            // Count the todos and how many of them are completed
+            let (mut todos, mut completed) = (0u64, 0u64);
+            for (_, list) in super::v1::Todos::<T>::iter() {
+                todos += list.len() as u64;
+                completed += list.iter().filter(|t| t.completed).count() as u64;
+            }
+
+            Ok((todos, completed).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (expected, expected_done) = <(u64, u64)>::decode(&mut &state[..])
+                .map_err(|_| "the pre-upgrade state is a todo and completed count")?;
+
+            let (mut todos, mut done) = (0u64, 0u64);
+            for (who, list) in Todos::<T>::iter() {
+                todos += list.len() as u64;
+                done += list.iter().filter(|t| t.is_completed()).count() as u64;
+                ensure!(
+                    list.iter().all(|t| t.entered_at.get(t.status).is_some()),
+                    "todos know when they entered their status"
+                );
+                ensure!(
+                    TodoStats::<T>::get(&who) == TodoStatistics::of(&list),
+                    "statistics match the todos"
+                );
+            }
+            ensure!(todos == expected, "no todo was lost");
+            ensure!(done == expected_done, "completed todos are done");
+            ensure!(Pallet::<T>::on_chain_storage_version() == 2, "storage is at v2");
+
+            Ok(())
+        }
+    }
+}
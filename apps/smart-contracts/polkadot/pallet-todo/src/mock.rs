@@ -1,11 +1,13 @@
 use crate as pallet_todo;
 use frame_support::{
     parameter_types,
-    traits::{ConstU16, ConstU32, ConstU64},
+    traits::{ConstU16, ConstU32, ConstU64, EqualPrivilegeOnly},
+    weights::Weight,
 };
-use frame_system as system;
+use frame_system::{self as system, EnsureRoot};
 use sp_core::H256;
 use sp_runtime::{
+    testing::{TestSignature, TestXt, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
     BuildStorage,
 };
@@ -16,6 +18,9 @@ type Block = frame_system::mocking::MockBlock<Test>;
 frame_support::construct_runtime!(
     pub enum Test {
         System: frame_system,
+        Balances: pallet_balances,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
         TodoPallet: pallet_todo,
     }
 );
@@ -24,7 +29,7 @@ impl system::Config for Test {
     type BaseCallFilter = frame_support::traits::Everything;
     type BlockWeights = ();
     type BlockLength = ();
-    type DbWeight = ();
+    type DbWeight = frame_support::weights::constants::RocksDbWeight;
     type RuntimeOrigin = RuntimeOrigin;
     type RuntimeCall = RuntimeCall;
     type Nonce = u64;
@@ -37,7 +42,7 @@ impl system::Config for Test {
     type BlockHashCount = ConstU64<250>;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -48,6 +53,73 @@ impl system::Config for Test {
 
 parameter_types! {
     pub const MinimumPeriod: u64 = 5;
+    pub static MaxOpsPerWindow: u32 = 100;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU64<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type RuntimeHoldReason = ();
+    type MaxHolds = ();
+}
+
+impl pallet_preimage::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<u64>;
+    type BaseDeposit = ConstU64<0>;
+    type ByteDeposit = ConstU64<0>;
+}
+
+parameter_types! {
+    pub MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+}
+
+impl pallet_scheduler::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type PalletsOrigin = OriginCaller;
+    type RuntimeCall = RuntimeCall;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<u64>;
+    type MaxScheduledPerBlock = ConstU32<10>;
+    type WeightInfo = ();
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type Preimages = Preimage;
+}
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = Extrinsic;
+}
+
+/// Signs reminders with `UintAuthorityId` test keys
+pub struct TestAuthId;
+
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+    type RuntimeAppPublic = UintAuthorityId;
+    type GenericPublic = UintAuthorityId;
+    type GenericSignature = TestSignature;
 }
 
 pub struct MockTime;
@@ -67,9 +139,42 @@ impl pallet_todo::Config for Test {
     type MaxTitleLength = ConstU32<100>;
     type MaxDescriptionLength = ConstU32<500>;
     type MaxTodosPerAccount = ConstU32<50>;
+    type FreeCallsPerPeriod = ConstU32<10>;
+    type FreeCallPeriod = ConstU64<100>;
+    type CompletionHandler = ();
+    type AdminOrigin = EnsureRoot<u64>;
+    type MaxOpsPerWindow = MaxOpsPerWindow;
+    type RateLimitWindow = ConstU64<10>;
+    type AuthorityId = TestAuthId;
+    type ReminderLeadTime = ConstU64<5_000>;
+    type MaxRemindersPerBlock = ConstU32<10>;
+    type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+    type MaxReminderAuthorities = ConstU32<4>;
+    type MaxCiphertextLength = ConstU32<1024>;
+    type MaxRecipients = ConstU32<4>;
+    type RuntimeCall = RuntimeCall;
+    type PalletsOrigin = OriginCaller;
+    type Scheduler = Scheduler;
+    type MaxScheduledPerAccount = ConstU32<3>;
+    type MinSchedulePeriod = ConstU64<3>;
+    type MaxScheduleRepetitions = ConstU32<5>;
+    type ClaimDuration = ConstU64<10>;
+    type MaxBoardTasksPerAccount = ConstU32<2>;
+    type MaxRevisions = ConstU32<3>;
+    type DeletionGracePeriod = ConstU64<10>;
 }
 
+/// Account of the `UintAuthorityId` key allowed to sign reminders
+pub const REMINDER_AUTHORITY: u64 = 7;
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+    let mut storage = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    crate::GenesisConfig::<Test> {
+        reminder_authorities: vec![REMINDER_AUTHORITY],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    storage.into()
 }
\ No newline at end of file
@@ -1,6 +1,24 @@
-use crate::{mock::*, Error, Event, Priority, Todo};
-use frame_support::{assert_noop, assert_ok};
-use sp_runtime::traits::BlakeTwo256;
+use crate::{
+    mock::*, CheckRateLimit, CheckTodoCall, Error, Event, Priority, RetentionAction,
+    RetentionPolicy, Status, Todo, TransferMode, ValidityError,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::DispatchInfo,
+    traits::{Get, OffchainWorker, OnIdle, OnInitialize},
+    weights::Weight,
+};
+use sp_core::offchain::{
+    testing::{TestOffchainExt, TestTransactionPoolExt},
+    OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_runtime::{
+    testing::{TestSignature, UintAuthorityId},
+    traits::{BlakeTwo256, Hash, SignedExtension, ValidateUnsigned},
+    transaction_validity::{InvalidTransaction, TransactionSource},
+    BoundedVec, BuildStorage,
+};
 
 // Helper function to create a todo
 fn create_test_todo(account: u64, title: &[u8], description: &[u8], priority: Priority) {
@@ -12,6 +30,25 @@ fn create_test_todo(account: u64, title: &[u8], description: &[u8], priority: Pr
     ));
 }
 
+// Helper function to advance blocks, running the scheduler and the purge of deleted todos on each
+fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        let next = System::block_number() + 1;
+        System::set_block_number(next);
+        Scheduler::on_initialize(next);
+        TodoPallet::on_idle(next, Weight::MAX);
+    }
+}
+
+// Helper function to build a `create_todo` call for scheduling
+fn create_todo_call(title: &[u8]) -> Box<crate::Call<Test>> {
+    Box::new(crate::Call::create_todo {
+        title: title.to_vec(),
+        description: b"Scheduled".to_vec(),
+        priority: Priority::Low,
+    })
+}
+
 #[test]
 fn create_todo_works() {
     new_test_ext().execute_with(|| {
@@ -35,10 +72,11 @@ fn create_todo_works() {
         assert_eq!(todo.title.as_slice(), title);
         assert_eq!(todo.description.as_slice(), description);
         assert_eq!(todo.priority, priority);
-        assert_eq!(todo.completed, false);
+        assert_eq!(todo.status, Status::Backlog);
         assert_eq!(todo.created_at, 1000);
         assert_eq!(todo.updated_at, 1000);
-        assert_eq!(todo.completed_at, None);
+        assert_eq!(todo.entered_at.backlog, Some(1000));
+        assert_eq!(todo.completed_at(), None);
 
         // Check that the next ID was incremented
         assert_eq!(TodoPallet::next_id(account), 1);
@@ -172,6 +210,69 @@ fn update_todo_fails_with_nonexistent_id() {
     });
 }
 
+#[test]
+fn update_todo_keeps_a_bounded_history() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Version 0", b"This is a test todo", Priority::Medium);
+
+        // Rename the todo at blocks 2 to 5; the mock keeps 3 revisions
+        for version in 1..=4u64 {
+            System::set_block_number(version + 1);
+            let title = format!("Version {version}").into_bytes();
+            assert_ok!(TodoPallet::update_todo(RuntimeOrigin::signed(account), 0, Some(title), None, None));
+        }
+
+        // The oldest revision was dropped
+        let history = TodoPallet::todo_history(account, 0);
+        assert_eq!(
+            history.iter().map(|r| (r.title.to_vec(), r.changed_at)).collect::<Vec<_>>(),
+            vec![(b"Version 1".to_vec(), 3000), (b"Version 2".to_vec(), 4000), (b"Version 3".to_vec(), 5000)]
+        );
+        assert!(history.iter().all(|r| r.changed_by == Some(account) && r.priority == Priority::Medium));
+        assert_eq!(history[0].description_hash, BlakeTwo256::hash(b"This is a test todo"));
+
+        // Updates that change nothing and urgency changes within a priority leave no revision
+        assert_ok!(TodoPallet::update_todo(RuntimeOrigin::signed(account), 0, Some(b"Version 4".to_vec()), None, None));
+        assert_ok!(TodoPallet::set_urgency(RuntimeOrigin::signed(account), 0, 100));
+        assert_eq!(TodoPallet::todo_history(account, 0), history);
+
+        // Moving to another priority does
+        assert_ok!(TodoPallet::set_urgency(RuntimeOrigin::signed(account), 0, 255));
+        let history = TodoPallet::todo_history(account, 0);
+        assert_eq!(history.len(), 3);
+        assert_eq!((history[2].title.to_vec(), history[2].priority), (b"Version 4".to_vec(), Priority::Medium));
+    });
+}
+
+#[test]
+fn purging_a_todo_drops_its_history() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        for id in [0, 1] {
+            assert_ok!(TodoPallet::update_todo(RuntimeOrigin::signed(account), id, None, None, Some(Priority::High)));
+        }
+
+        // A deleted todo keeps its history until it is purged
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 0));
+        assert_eq!(TodoPallet::todo_history(account, 0).len(), 1);
+        run_to_block(11);
+        assert!(TodoPallet::todo_history(account, 0).is_empty());
+        assert_eq!(TodoPallet::todo_history(account, 1).len(), 1);
+
+        assert_ok!(TodoPallet::force_clear_account(RuntimeOrigin::root(), account));
+        assert!(TodoPallet::todo_history(account, 1).is_empty());
+    });
+}
+
 #[test]
 fn toggle_todo_completion_works() {
     new_test_ext().execute_with(|| {
@@ -198,9 +299,9 @@ fn toggle_todo_completion_works() {
         // Check that the todo was updated
         let todos = TodoPallet::todos(account);
         let todo = &todos[0];
-        assert_eq!(todo.completed, true);
+        assert_eq!(todo.status, Status::Done);
         assert_eq!(todo.updated_at, 2000);
-        assert_eq!(todo.completed_at, Some(2000));
+        assert_eq!(todo.completed_at(), Some(2000));
 
         // Check that the stats were updated
         let stats = TodoPallet::todo_stats(account);
@@ -222,9 +323,9 @@ fn toggle_todo_completion_works() {
         // Check that the todo was updated
         let todos = TodoPallet::todos(account);
         let todo = &todos[0];
-        assert_eq!(todo.completed, false);
+        assert_eq!(todo.status, Status::Backlog);
         assert_eq!(todo.updated_at, 3000);
-        assert_eq!(todo.completed_at, None);
+        assert_eq!(todo.completed_at(), None);
 
         // Check that the stats were updated
         let stats = TodoPallet::todo_stats(account);
@@ -236,6 +337,94 @@ fn toggle_todo_completion_works() {
     });
 }
 
+#[test]
+fn set_status_follows_the_workflow() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        // Start working on it
+        System::set_block_number(2);
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::InProgress));
+        System::assert_has_event(Event::TodoStatusChanged { who: account, id: 0, from: Status::Backlog, to: Status::InProgress }.into());
+
+        // Get blocked
+        System::set_block_number(3);
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::Blocked));
+
+        let todo = &TodoPallet::todos(account)[0];
+        assert_eq!(todo.status, Status::Blocked);
+        assert_eq!(todo.updated_at, 3000);
+        assert_eq!(todo.entered_at.get(Status::Backlog), Some(1000));
+        assert_eq!(todo.entered_at.get(Status::InProgress), Some(2000));
+        assert_eq!(todo.entered_at.get(Status::Blocked), Some(3000));
+
+        let stats = TodoPallet::todo_stats(account);
+        assert_eq!((stats.pending, stats.in_progress, stats.blocked), (1, 0, 1));
+
+        // A blocked todo has to be unblocked before it is done, also through the toggle
+        assert_noop!(
+            TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::Done),
+            Error::<Test>::InvalidStatusTransition
+        );
+        assert_noop!(
+            TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0),
+            Error::<Test>::InvalidStatusTransition
+        );
+
+        // Unblock and finish it
+        System::set_block_number(4);
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::InProgress));
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::Done));
+        System::assert_has_event(Event::TodoCompletionToggled { who: account, id: 0, completed: true }.into());
+
+        let todo = &TodoPallet::todos(account)[0];
+        assert_eq!(todo.completed_at(), Some(4000));
+        assert_eq!(todo.entered_at.in_progress, Some(4000));
+        assert_eq!(TodoPallet::todo_stats(account).completed, 1);
+    });
+}
+
+#[test]
+fn set_status_rejects_disallowed_transitions() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        // Staying in the same status is not a transition
+        assert_noop!(
+            TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::Backlog),
+            Error::<Test>::InvalidStatusTransition
+        );
+
+        // Cancelled todos can only be restored to the backlog
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::Cancelled));
+        for status in [Status::InProgress, Status::Blocked, Status::Done] {
+            assert_noop!(
+                TodoPallet::set_status(RuntimeOrigin::signed(account), 0, status),
+                Error::<Test>::InvalidStatusTransition
+            );
+        }
+        let stats = TodoPallet::todo_stats(account);
+        assert_eq!((stats.pending, stats.cancelled, stats.medium_priority), (0, 1, 0));
+
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::Backlog));
+        assert_eq!(TodoPallet::todo_stats(account).pending, 1);
+
+        // Unknown todos cannot change status
+        assert_noop!(
+            TodoPallet::set_status(RuntimeOrigin::signed(account), 1, Status::Done),
+            Error::<Test>::TodoNotFound
+        );
+    });
+}
+
 #[test]
 fn toggle_todo_completion_fails_with_nonexistent_id() {
     new_test_ext().execute_with(|| {
@@ -282,6 +471,11 @@ fn delete_todo_works() {
         let todos = TodoPallet::todos(account);
         assert_eq!(todos.len(), 1);
         assert_eq!(todos[0].id, 1);
+        
+        // Check that the todo is kept until the grace period is over
+        let deleted = TodoPallet::deleted_todos(account);
+        assert_eq!(deleted.len(), 1);
+        assert_eq!((deleted[0].todo.id, deleted[0].purge_at), (0, 11));
 
         // Check that the stats were updated
         let stats = TodoPallet::todo_stats(account);
@@ -310,67 +504,2044 @@ fn delete_todo_fails_with_nonexistent_id() {
 }
 
 #[test]
-fn todo_list_full_error() {
+fn undelete_todo_restores_a_deleted_todo() {
     new_test_ext().execute_with(|| {
         // Go to block 1 so we have a timestamp
         System::set_block_number(1);
 
         let account = 1;
-        let title = b"Test Todo";
-        let description = b"This is a test todo";
-        let priority = Priority::Medium;
+        create_test_todo(account, b"First", b"This is a test todo", Priority::Medium);
+        create_test_todo(account, b"Second", b"This is a test todo", Priority::High);
+        create_test_todo(account, b"Third", b"This is a test todo", Priority::Low);
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 1));
 
-        // Create the maximum number of todos (50)
+        // Deleted todos leave the statistics
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 1));
+        let stats = TodoPallet::todo_stats(account);
+        assert_eq!((stats.total, stats.completed, stats.pending), (2, 0, 2));
+
+        // Restoring puts the todo back in its place, as it was
+        run_to_block(5);
+        assert_ok!(TodoPallet::undelete_todo(RuntimeOrigin::signed(account), 1));
+        let todos = TodoPallet::todos(account);
+        assert_eq!(todos.iter().map(|t| t.id).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(todos[1].is_completed());
+        assert!(TodoPallet::deleted_todos(account).is_empty());
+        assert_eq!(TodoPallet::todo_stats(account).completed, 1);
+        System::assert_has_event(Event::TodoRestored { who: account, id: 1 }.into());
+
+        // The restored todo is not purged when its grace period ends
+        run_to_block(20);
+        assert_eq!(TodoPallet::todos(account).len(), 3);
+
+        // Only deleted todos can be restored
+        assert_noop!(
+            TodoPallet::undelete_todo(RuntimeOrigin::signed(account), 1),
+            Error::<Test>::TodoNotDeleted
+        );
+    });
+}
+
+#[test]
+fn undelete_todo_fails_with_a_full_list() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        // Fill the list again after deleting a todo
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 0));
         for _ in 0..50 {
-            create_test_todo(account, title, description, priority);
+            create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
         }
 
-        // Try to create one more todo
         assert_noop!(
-            TodoPallet::create_todo(
-                RuntimeOrigin::signed(account),
-                title.to_vec(),
-                description.to_vec(),
-                priority
-            ),
+            TodoPallet::undelete_todo(RuntimeOrigin::signed(account), 0),
             Error::<Test>::TodoListFull
         );
     });
 }
 
 #[test]
-fn multiple_accounts_have_separate_todos() {
+fn deleted_todos_are_purged_after_the_grace_period() {
     new_test_ext().execute_with(|| {
         // Go to block 1 so we have a timestamp
         System::set_block_number(1);
 
-        let account1 = 1;
-        let account2 = 2;
+        let account = 1;
+        create_test_todo(account, b"First", b"This is a test todo", Priority::Medium);
+        create_test_todo(account, b"Second", b"This is a test todo", Priority::Medium);
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 0));
+        run_to_block(3);
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 1));
+
+        // Nothing is purged during the grace period
+        run_to_block(10);
+        assert_eq!(TodoPallet::deleted_todos(account).len(), 2);
+
+        // Each todo is purged once its own grace period is over
+        run_to_block(11);
+        assert_eq!(TodoPallet::deleted_todos(account).iter().map(|d| d.todo.id).collect::<Vec<_>>(), vec![1]);
+        System::assert_has_event(Event::TodoPurged { who: account, id: 0 }.into());
+        assert_noop!(
+            TodoPallet::undelete_todo(RuntimeOrigin::signed(account), 0),
+            Error::<Test>::TodoNotDeleted
+        );
 
-        // Create a todo for account 1
-        create_test_todo(account1, b"Account 1 Todo", b"This is a todo for account 1", Priority::Medium);
+        run_to_block(13);
+        assert!(TodoPallet::deleted_todos(account).is_empty());
+        assert_eq!(crate::PurgeQueueBounds::<Test>::get(), (2, 2));
+    });
+}
 
-        // Create a todo for account 2
-        create_test_todo(account2, b"Account 2 Todo", b"This is a todo for account 2", Priority::High);
+#[test]
+fn purge_respects_the_remaining_weight() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
 
-        // Check that each account has its own todo
-        let todos1 = TodoPallet::todos(account1);
-        let todos2 = TodoPallet::todos(account2);
+        let account = 1;
+        for _ in 0..3 {
+            create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        }
+        for id in 0..3 {
+            assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), id));
+        }
 
-        assert_eq!(todos1.len(), 1);
-        assert_eq!(todos2.len(), 1);
+        // Without room for a single purge nothing happens
+        System::set_block_number(11);
+        assert_eq!(TodoPallet::on_idle(11, Weight::zero()), Weight::zero());
+        assert_eq!(TodoPallet::deleted_todos(account).len(), 3);
 
-        assert_eq!(todos1[0].title.as_slice(), b"Account 1 Todo");
-        assert_eq!(todos2[0].title.as_slice(), b"Account 2 Todo");
+        // With room for two purges, two todos are purged and the third waits for the next block
+        let db = <Test as frame_system::Config>::DbWeight::get();
+        let room = db.reads_writes(1, 1).saturating_add(db.reads_writes(3, 6).saturating_mul(2));
+        assert_eq!(TodoPallet::on_idle(11, room), room);
+        assert_eq!(TodoPallet::deleted_todos(account).iter().map(|d| d.todo.id).collect::<Vec<_>>(), vec![2]);
 
-        // Check that each account has its own stats
-        let stats1 = TodoPallet::todo_stats(account1);
-        let stats2 = TodoPallet::todo_stats(account2);
+        TodoPallet::on_idle(12, Weight::MAX);
+        assert!(TodoPallet::deleted_todos(account).is_empty());
+    });
+}
 
-        assert_eq!(stats1.total, 1);
-        assert_eq!(stats2.total, 1);
+#[test]
+fn deleting_with_a_full_trash_purges_the_oldest_deletion() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
 
-        assert_eq!(stats1.high_priority, 0);
-        assert_eq!(stats2.high_priority, 1);
+        // The mock keeps at most 50 deleted todos per account
+        let account = 1;
+        for id in 0..51 {
+            create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+            assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), id));
+        }
+
+        let deleted = TodoPallet::deleted_todos(account);
+        assert_eq!(deleted.len(), 50);
+        assert_eq!(deleted[0].todo.id, 1);
+        System::assert_has_event(Event::TodoPurged { who: account, id: 0 }.into());
+
+        // The stale queue entry of the purged todo is skipped
+        run_to_block(11);
+        assert!(TodoPallet::deleted_todos(account).is_empty());
+    });
+}
+
+#[test]
+fn set_retention_policy_works() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        let policy = RetentionPolicy { max_age: 30_000, action: RetentionAction::Archive };
+        assert_ok!(TodoPallet::set_retention_policy(RuntimeOrigin::signed(account), Some(policy.clone())));
+        assert_eq!(TodoPallet::retention_policy(account), Some(policy.clone()));
+        System::assert_has_event(Event::RetentionPolicySet { who: account, policy: Some(policy) }.into());
+
+        // Opt out again
+        assert_ok!(TodoPallet::set_retention_policy(RuntimeOrigin::signed(account), None));
+        assert_eq!(TodoPallet::retention_policy(account), None);
+        System::assert_has_event(Event::RetentionPolicySet { who: account, policy: None }.into());
+    });
+}
+
+#[test]
+fn retention_policy_deletes_old_completed_todos() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        let policy = RetentionPolicy { max_age: 5_000, action: RetentionAction::Delete };
+        assert_ok!(TodoPallet::set_retention_policy(RuntimeOrigin::signed(account), Some(policy)));
+        for _ in 0..3 {
+            create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        }
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(10_000)));
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0));
+        run_to_block(4);
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 1));
+
+        // Nothing is pruned before `max_age` has passed
+        run_to_block(6);
+        assert_eq!(TodoPallet::todos(account).len(), 3);
+
+        // Only the todo completed long enough ago is removed, for good
+        run_to_block(7);
+        let todos = TodoPallet::todos(account);
+        assert_eq!(todos.iter().map(|t| t.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(TodoPallet::deleted_todos(account).is_empty());
+        assert_eq!(TodoPallet::due_dates(account, 0), None);
+        assert_eq!(TodoPallet::todo_stats(account).total, 2);
+        System::assert_has_event(Event::TodoPurged { who: account, id: 0 }.into());
+    });
+}
+
+#[test]
+fn retention_policy_archives_old_completed_todos() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        let policy = RetentionPolicy { max_age: 5_000, action: RetentionAction::Archive };
+        assert_ok!(TodoPallet::set_retention_policy(RuntimeOrigin::signed(account), Some(policy)));
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(10_000)));
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0));
+
+        // The completed todo moves to the archive with its due date
+        run_to_block(7);
+        let todos = TodoPallet::todos(account);
+        assert_eq!(todos.iter().map(|t| t.id).collect::<Vec<_>>(), vec![1]);
+        let archived = TodoPallet::archived_todos(account);
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].id, 0);
+        assert!(archived[0].is_completed());
+        assert!(TodoPallet::due_dates(account, 0).is_some());
+        assert_eq!(TodoPallet::todo_stats(account).completed, 0);
+        System::assert_has_event(Event::TodoArchived { who: account, id: 0 }.into());
+    });
+}
+
+#[test]
+fn retention_sweep_carries_on_from_its_cursor() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        // Three accounts with a completed todo each, pruned as soon as possible
+        let accounts = [1, 2, 3];
+        let policy = RetentionPolicy { max_age: 0, action: RetentionAction::Delete };
+        for account in accounts {
+            assert_ok!(TodoPallet::set_retention_policy(RuntimeOrigin::signed(account), Some(policy.clone())));
+            create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+            assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0));
+        }
+        System::set_block_number(2);
+
+        // With room for two accounts, two are swept and the cursor is kept
+        let db = <Test as frame_system::Config>::DbWeight::get();
+        let per_account = db.reads_writes(3, 3).saturating_add(db.reads_writes(2, 5).saturating_mul(50));
+        let room = db.reads_writes(1, 1).saturating_mul(2).saturating_add(per_account.saturating_mul(2));
+        assert_eq!(TodoPallet::on_idle(2, room), room);
+        let remaining = |accounts: [u64; 3]| accounts.iter().filter(|a| !TodoPallet::todos(**a).is_empty()).count();
+        assert_eq!(remaining(accounts), 1);
+        assert!(crate::RetentionCursor::<Test>::get().is_some());
+
+        // The next sweep finishes the last account and starts over
+        TodoPallet::on_idle(2, Weight::MAX);
+        assert_eq!(remaining(accounts), 0);
+        assert_eq!(crate::RetentionCursor::<Test>::get(), None);
+    });
+}
+
+#[test]
+fn accept_list_transfer_cancels_the_senders_scheduled_calls() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let (from, to) = (1, 2);
+        create_test_todo(from, b"First", b"This is a test todo", Priority::Medium);
+        let complete = Box::new(crate::Call::set_status { id: 0, status: Status::Done });
+        assert_ok!(TodoPallet::schedule_todo(RuntimeOrigin::signed(from), complete, 3, None));
+
+        assert_ok!(TodoPallet::transfer_list(RuntimeOrigin::signed(from), to));
+        assert_ok!(TodoPallet::accept_list_transfer(RuntimeOrigin::signed(to), from, TransferMode::Merge));
+
+        // The call referred to a todo that now belongs to the recipient
+        assert!(TodoPallet::scheduled_todos(from).is_empty());
+        System::assert_has_event(Event::ScheduledTodoCancelled { who: from, task_id: 0 }.into());
+
+        // The scheduler no longer knows the call
+        let name = TodoPallet::scheduled_task_name(&from, 0);
+        assert!(<Scheduler as frame_support::traits::schedule::v3::Named<_, _, _>>::next_dispatch_time(name).is_err());
+        run_to_block(3);
+        assert_eq!(TodoPallet::todos(to)[0].status, Status::Backlog);
+    });
+}
+
+#[test]
+fn clear_all_todos_resets_the_account() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        for _ in 0..3 {
+            create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        }
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(10_000)));
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 1));
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 2));
+        assert_ok!(TodoPallet::schedule_todo(
+            RuntimeOrigin::signed(account),
+            create_todo_call(b"Scheduled"),
+            5,
+            None
+        ));
+        assert_ok!(TodoPallet::transfer_list(RuntimeOrigin::signed(account), 2));
+
+        assert_ok!(TodoPallet::clear_all_todos(RuntimeOrigin::signed(account)));
+
+        // Check that nothing of the account is left
+        assert!(TodoPallet::todos(account).is_empty());
+        assert!(TodoPallet::deleted_todos(account).is_empty());
+        assert_eq!(TodoPallet::due_dates(account, 0), None);
+        assert!(!crate::NextId::<Test>::contains_key(account));
+        assert!(!crate::TodoStats::<Test>::contains_key(account));
+        System::assert_has_event(Event::AllTodosCleared { who: account, removed: 2 }.into());
+
+        // The scheduled call and the offer of the list are cancelled
+        assert!(TodoPallet::scheduled_todos(account).is_empty());
+        assert_eq!(TodoPallet::list_transfer(account), None);
+        System::assert_has_event(Event::ScheduledTodoCancelled { who: account, task_id: 0 }.into());
+        System::assert_has_event(Event::ListTransferCancelled { from: account, to: 2 }.into());
+
+        // IDs start from 0 again
+        run_to_block(2);
+        for _ in 0..3 {
+            create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        }
+        assert_eq!(TodoPallet::todos(account)[0].id, 0);
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 2));
+
+        // The scheduled call never runs, and the old deletion does not purge the new todo 2
+        run_to_block(11);
+        assert_eq!(TodoPallet::todos(account).len(), 2);
+        assert_eq!(TodoPallet::deleted_todos(account)[0].todo.id, 2);
+        run_to_block(12);
+        assert!(TodoPallet::deleted_todos(account).is_empty());
+    });
+}
+
+#[test]
+fn transfer_list_merges_with_new_ids() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let (from, to) = (1, 2);
+        create_test_todo(from, b"First", b"This is a test todo", Priority::Medium);
+        create_test_todo(from, b"Second", b"This is a test todo", Priority::High);
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(from), 0));
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(from), 1, Some(10_000)));
+        create_test_todo(to, b"Own", b"This is a test todo", Priority::Low);
+
+        assert_ok!(TodoPallet::transfer_list(RuntimeOrigin::signed(from), to));
+        assert_eq!(TodoPallet::list_transfer(from), Some(to));
+        System::assert_has_event(Event::ListTransferOffered { from, to }.into());
+
+        // Only the recipient can accept
+        assert_noop!(
+            TodoPallet::accept_list_transfer(RuntimeOrigin::signed(3), from, TransferMode::Merge),
+            Error::<Test>::NoListTransfer
+        );
+
+        assert_ok!(TodoPallet::accept_list_transfer(RuntimeOrigin::signed(to), from, TransferMode::Merge));
+
+        // The todos follow the recipient's own under new IDs, with their due dates
+        let todos = TodoPallet::todos(to);
+        assert_eq!(todos.iter().map(|t| t.id).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(todos[1].title.to_vec(), b"First".to_vec());
+        assert!(todos[1].is_completed());
+        assert_eq!(todos[2].title.to_vec(), b"Second".to_vec());
+        assert_eq!(TodoPallet::due_dates(to, 2).map(|d| d.due), Some(10_000));
+        assert_eq!(TodoPallet::next_id(to), 3);
+        assert_eq!(TodoPallet::todo_stats(to).total, 3);
+
+        // The sender is left with an empty list and keeps its IDs
+        assert!(TodoPallet::todos(from).is_empty());
+        assert_eq!(TodoPallet::due_dates(from, 1), None);
+        assert_eq!(TodoPallet::todo_stats(from).total, 0);
+        assert_eq!(TodoPallet::next_id(from), 2);
+
+        System::assert_has_event(Event::TodoTransferred { from, old_id: 0, to, new_id: 1 }.into());
+        System::assert_has_event(
+            Event::ListTransferred { from, to, mode: TransferMode::Merge, count: 2 }.into(),
+        );
+
+        // The offer is used up
+        assert_eq!(TodoPallet::list_transfer(from), None);
+        assert_noop!(
+            TodoPallet::accept_list_transfer(RuntimeOrigin::signed(to), from, TransferMode::Merge),
+            Error::<Test>::NoListTransfer
+        );
+    });
+}
+
+#[test]
+fn transfer_list_replaces_the_recipient_list() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let (from, to) = (1, 2);
+        create_test_todo(from, b"Transferred", b"This is a test todo", Priority::Medium);
+        create_test_todo(to, b"Own", b"This is a test todo", Priority::Low);
+        create_test_todo(to, b"Own", b"This is a test todo", Priority::Low);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(to), 0, Some(10_000)));
+
+        assert_ok!(TodoPallet::transfer_list(RuntimeOrigin::signed(from), to));
+        assert_ok!(TodoPallet::accept_list_transfer(RuntimeOrigin::signed(to), from, TransferMode::Replace));
+
+        // The recipient's todos are removed for good and IDs are not reused
+        let todos = TodoPallet::todos(to);
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].id, 2);
+        assert_eq!(todos[0].title.to_vec(), b"Transferred".to_vec());
+        assert_eq!(TodoPallet::due_dates(to, 0), None);
+        System::assert_has_event(Event::TodoPurged { who: to, id: 0 }.into());
+        System::assert_has_event(
+            Event::ListTransferred { from, to, mode: TransferMode::Replace, count: 1 }.into(),
+        );
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn transfer_list_checks() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let (from, to) = (1, 2);
+        assert_noop!(
+            TodoPallet::transfer_list(RuntimeOrigin::signed(from), from),
+            Error::<Test>::CannotTransferToSelf
+        );
+
+        // Merging must not overflow the recipient's list
+        create_test_todo(from, b"Test Todo", b"This is a test todo", Priority::Medium);
+        for _ in 0..50 {
+            create_test_todo(to, b"Test Todo", b"This is a test todo", Priority::Medium);
+        }
+        assert_ok!(TodoPallet::transfer_list(RuntimeOrigin::signed(from), to));
+        assert_noop!(
+            TodoPallet::accept_list_transfer(RuntimeOrigin::signed(to), from, TransferMode::Merge),
+            Error::<Test>::TodoListFull
+        );
+
+        // A cancelled offer cannot be accepted
+        assert_ok!(TodoPallet::cancel_list_transfer(RuntimeOrigin::signed(from)));
+        System::assert_has_event(Event::ListTransferCancelled { from, to }.into());
+        assert_noop!(
+            TodoPallet::accept_list_transfer(RuntimeOrigin::signed(to), from, TransferMode::Replace),
+            Error::<Test>::NoListTransfer
+        );
+        assert_noop!(
+            TodoPallet::cancel_list_transfer(RuntimeOrigin::signed(from)),
+            Error::<Test>::NoListTransfer
+        );
+    });
+}
+
+#[test]
+fn todo_list_full_error() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        let title = b"Test Todo";
+        let description = b"This is a test todo";
+        let priority = Priority::Medium;
+
+        // Create the maximum number of todos (50)
+        for _ in 0..50 {
+            create_test_todo(account, title, description, priority);
+        }
+
+        // Try to create one more todo
+        assert_noop!(
+            TodoPallet::create_todo(
+                RuntimeOrigin::signed(account),
+                title.to_vec(),
+                description.to_vec(),
+                priority
+            ),
+            Error::<Test>::TodoListFull
+        );
+    });
+}
+
+#[test]
+fn multiple_accounts_have_separate_todos() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account1 = 1;
+        let account2 = 2;
+
+        // Create a todo for account 1
+        create_test_todo(account1, b"Account 1 Todo", b"This is a todo for account 1", Priority::Medium);
+
+        // Create a todo for account 2
+        create_test_todo(account2, b"Account 2 Todo", b"This is a todo for account 2", Priority::High);
+
+        // Check that each account has its own todo
+        let todos1 = TodoPallet::todos(account1);
+        let todos2 = TodoPallet::todos(account2);
+
+        assert_eq!(todos1.len(), 1);
+        assert_eq!(todos2.len(), 1);
+
+        assert_eq!(todos1[0].title.as_slice(), b"Account 1 Todo");
+        assert_eq!(todos2[0].title.as_slice(), b"Account 2 Todo");
+
+        // Check that each account has its own stats
+        let stats1 = TodoPallet::todo_stats(account1);
+        let stats2 = TodoPallet::todo_stats(account2);
+
+        assert_eq!(stats1.total, 1);
+        assert_eq!(stats2.total, 1);
+
+        assert_eq!(stats1.high_priority, 0);
+        assert_eq!(stats2.high_priority, 1);
+    });
+}
+#[test]
+fn force_delete_todo_works() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+
+        // Create two todos
+        create_test_todo(account, b"Spam", b"Spam spam spam", Priority::High);
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        // Only the admin origin can force-delete
+        assert_noop!(
+            TodoPallet::force_delete_todo(RuntimeOrigin::signed(2), account, 0),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TodoPallet::force_delete_todo(RuntimeOrigin::root(), account, 0));
+
+        // Check that the todo was deleted
+        let todos = TodoPallet::todos(account);
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].id, 1);
+
+        // Check that the stats were updated
+        let stats = TodoPallet::todo_stats(account);
+        assert_eq!(stats.total, 1);
+        assert_eq!(stats.high_priority, 0);
+
+        // Check that the event was emitted
+        System::assert_has_event(Event::TodoForceDeleted { who: account, id: 0 }.into());
+
+        // Deleting a missing todo fails
+        assert_noop!(
+            TodoPallet::force_delete_todo(RuntimeOrigin::root(), account, 0),
+            Error::<Test>::TodoNotFound
+        );
+    });
+}
+
+#[test]
+fn force_clear_account_works() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+
+        // Create a few todos
+        for _ in 0..3 {
+            create_test_todo(account, b"Spam", b"Spam spam spam", Priority::Low);
+        }
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 2));
+
+        // Only the admin origin can clear an account
+        assert_noop!(
+            TodoPallet::force_clear_account(RuntimeOrigin::signed(account), account),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TodoPallet::force_clear_account(RuntimeOrigin::root(), account));
+
+        // Check that todos, deleted todos and stats were removed but ids are not reused
+        assert!(TodoPallet::todos(account).is_empty());
+        assert!(TodoPallet::deleted_todos(account).is_empty());
+        assert_eq!(TodoPallet::todo_stats(account).total, 0);
+        assert_eq!(TodoPallet::next_id(account), 3);
+
+        // Check that the event was emitted
+        System::assert_has_event(Event::AccountForceCleared { who: account, removed: 2 }.into());
+    });
+}
+
+#[test]
+fn force_set_stats_recomputes_statistics() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::High);
+
+        // Corrupt the statistics
+        crate::TodoStats::<Test>::insert(account, crate::TodoStatistics::default());
+
+        // Only the admin origin can reset statistics
+        assert_noop!(
+            TodoPallet::force_set_stats(RuntimeOrigin::signed(account), account),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TodoPallet::force_set_stats(RuntimeOrigin::root(), account));
+
+        let stats = TodoPallet::todo_stats(account);
+        assert_eq!(stats.total, 1);
+        assert_eq!(stats.pending, 1);
+        assert_eq!(stats.high_priority, 1);
+
+        // Check that the event was emitted
+        System::assert_has_event(Event::StatsForceSet { who: account, stats }.into());
+    });
+}
+
+#[test]
+fn free_call_quota_resets_every_period() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        let per_period: u32 = <Test as crate::Config>::FreeCallsPerPeriod::get();
+
+        // Only the cheap todo calls are eligible
+        assert!(TodoPallet::is_free_call(&crate::Call::toggle_todo_completion { id: 0 }));
+        assert!(TodoPallet::is_free_call(&crate::Call::set_status { id: 0, status: Status::Done }));
+        assert!(!TodoPallet::is_free_call(&crate::Call::delete_todo { id: 0 }));
+
+        // Using a free call lowers the quota until it runs out
+        for _ in 0..per_period {
+            TodoPallet::use_free_call(&account);
+        }
+        assert_eq!(TodoPallet::free_calls_left(&account), 0);
+        assert_eq!(TodoPallet::free_calls_left(&2), per_period);
+
+        // The next period starts with a full quota
+        System::set_block_number(<Test as crate::Config>::FreeCallPeriod::get());
+        assert_eq!(TodoPallet::free_calls_left(&account), per_period);
+        TodoPallet::use_free_call(&account);
+        assert_eq!(TodoPallet::free_todo_calls(account), crate::FreeCallsUsed { period: 1, used: 1 });
+    });
+}
+
+#[test]
+fn set_urgency_moves_todo_between_priorities() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        assert_eq!(TodoPallet::todos(account)[0].urgency, Priority::Medium.urgency());
+
+        // Go to block 2 for a different timestamp
+        System::set_block_number(2);
+
+        // An urgency in the top band makes the todo urgent
+        assert_ok!(TodoPallet::set_urgency(RuntimeOrigin::signed(account), 0, 250));
+        let todo = &TodoPallet::todos(account)[0];
+        assert_eq!((todo.priority, todo.urgency), (Priority::Urgent, 250));
+        assert_eq!(todo.updated_at, 2000);
+        assert_eq!(TodoPallet::todo_stats(account).urgent_priority, 1);
+        System::assert_has_event(Event::TodoUpdated { who: account, id: 0 }.into());
+
+        // The bands meet at 63 and 64
+        assert_ok!(TodoPallet::set_urgency(RuntimeOrigin::signed(account), 0, 63));
+        assert_eq!(TodoPallet::todos(account)[0].priority, Priority::Low);
+        assert_ok!(TodoPallet::set_urgency(RuntimeOrigin::signed(account), 0, 64));
+        assert_eq!(TodoPallet::todos(account)[0].priority, Priority::Medium);
+
+        // Updating to the same priority keeps the urgency, another priority resets it
+        assert_ok!(TodoPallet::update_todo(RuntimeOrigin::signed(account), 0, None, None, Some(Priority::Medium)));
+        assert_eq!(TodoPallet::todos(account)[0].urgency, 64);
+        assert_ok!(TodoPallet::update_todo(RuntimeOrigin::signed(account), 0, None, None, Some(Priority::Urgent)));
+        assert_eq!(TodoPallet::todos(account)[0].urgency, Priority::Urgent.urgency());
+
+        assert_noop!(
+            TodoPallet::set_urgency(RuntimeOrigin::signed(account), 1, 10),
+            Error::<Test>::TodoNotFound
+        );
+    });
+}
+
+#[test]
+fn stats_count_pending_todos_per_priority() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        for priority in [Priority::Low, Priority::Medium, Priority::Medium, Priority::High, Priority::Urgent] {
+            create_test_todo(account, b"Test Todo", b"This is a test todo", priority);
+        }
+
+        // Completed todos only count as completed
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 1));
+
+        assert_eq!(
+            TodoPallet::todo_stats(account),
+            crate::TodoStatistics {
+                total: 5,
+                completed: 1,
+                pending: 4,
+                low_priority: 1,
+                medium_priority: 1,
+                high_priority: 1,
+                urgent_priority: 1,
+                ..Default::default()
+            }
+        );
+    });
+}
+
+#[test]
+fn sorted_todos_orders_by_urgency_and_due_date() {
+    use crate::TodoSort;
+
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        for priority in [Priority::Low, Priority::High, Priority::Medium, Priority::High] {
+            create_test_todo(account, b"Test Todo", b"This is a test todo", priority);
+        }
+        assert_ok!(TodoPallet::set_urgency(RuntimeOrigin::signed(account), 0, 200));
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 2, Some(20_000)));
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 3, Some(10_000)));
+
+        let ids = |sort| TodoPallet::sorted_todos(&account, sort).iter().map(|t| t.id).collect::<Vec<_>>();
+
+        assert_eq!(ids(TodoSort::Id), vec![0, 1, 2, 3]);
+        // Todos 1 and 3 share an urgency and keep their ID order
+        assert_eq!(ids(TodoSort::Urgency), vec![0, 1, 3, 2]);
+        // Todos without a due date come last
+        assert_eq!(ids(TodoSort::DueDate), vec![3, 2, 0, 1]);
+    });
+}
+
+#[test]
+fn rate_limit_rejects_operations_until_window_resets() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+        MaxOpsPerWindow::set(3);
+
+        let account = 1;
+
+        // Create and update count against the same limit
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        assert_ok!(TodoPallet::update_todo(
+            RuntimeOrigin::signed(account),
+            0,
+            None,
+            None,
+            Some(Priority::High)
+        ));
+
+        assert_noop!(
+            TodoPallet::create_todo(
+                RuntimeOrigin::signed(account),
+                b"Test Todo".to_vec(),
+                b"This is a test todo".to_vec(),
+                Priority::Medium
+            ),
+            Error::<Test>::RateLimited
+        );
+        assert_noop!(
+            TodoPallet::update_todo(RuntimeOrigin::signed(account), 0, None, None, None),
+            Error::<Test>::RateLimited
+        );
+
+        // Other calls and other accounts are not limited
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(5_000)));
+        create_test_todo(2, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        // The counter resets once the window has passed
+        System::set_block_number(11);
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        assert_eq!(TodoPallet::rate_limits(account).ops, 1);
+        assert_eq!(TodoPallet::rate_limits(account).window_start, 11);
+    });
+}
+
+#[test]
+fn status_changes_count_against_the_rate_limit() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+        MaxOpsPerWindow::set(3);
+
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        // Each status change, toggles included, uses up the window once
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::InProgress));
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0));
+        assert_eq!(TodoPallet::rate_limits(account).ops, 3);
+
+        assert_noop!(
+            TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::Backlog),
+            Error::<Test>::RateLimited
+        );
+        assert_noop!(
+            TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0),
+            Error::<Test>::RateLimited
+        );
+
+        // The counter resets once the window has passed
+        System::set_block_number(11);
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0));
+        assert_eq!(TodoPallet::rate_limits(account).ops, 1);
+    });
+}
+
+#[test]
+fn check_rate_limit_rejects_limited_calls_in_validate() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+        MaxOpsPerWindow::set(1);
+
+        let account = 1;
+        let info = DispatchInfo::default();
+        let create_call = RuntimeCall::TodoPallet(crate::Call::create_todo {
+            title: b"Test Todo".to_vec(),
+            description: b"This is a test todo".to_vec(),
+            priority: Priority::Medium,
+        });
+        let toggle_call = RuntimeCall::TodoPallet(crate::Call::toggle_todo_completion { id: 0 });
+        let due_date_call = RuntimeCall::TodoPallet(crate::Call::set_due_date { id: 0, due: None });
+
+        assert_ok!(CheckRateLimit::<Test>::new().validate(&account, &create_call, &info, 0));
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        // The limit is used up, so further creates and status changes are rejected before dispatch
+        assert_eq!(
+            CheckRateLimit::<Test>::new().validate(&account, &create_call, &info, 0),
+            Err(ValidityError::RateLimited.into())
+        );
+        assert_eq!(
+            CheckRateLimit::<Test>::new().validate(&account, &toggle_call, &info, 0),
+            Err(ValidityError::RateLimited.into())
+        );
+        assert_ok!(CheckRateLimit::<Test>::new().validate(&account, &due_date_call, &info, 0));
+    });
+}
+
+#[test]
+fn check_todo_call_rejects_invalid_calls_in_validate() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        let info = DispatchInfo::default();
+        let validate = |call: crate::Call<Test>| {
+            CheckTodoCall::<Test>::new().validate(&account, &RuntimeCall::TodoPallet(call), &info, 0)
+        };
+
+        // Oversized payloads
+        assert_eq!(
+            validate(crate::Call::create_todo {
+                title: vec![b'a'; 101],
+                description: b"This is a test todo".to_vec(),
+                priority: Priority::Medium,
+            }),
+            Err(ValidityError::TitleTooLong.into())
+        );
+        assert_eq!(
+            validate(crate::Call::create_todo {
+                title: b"Test Todo".to_vec(),
+                description: vec![b'a'; 501],
+                priority: Priority::Medium,
+            }),
+            Err(ValidityError::DescriptionTooLong.into())
+        );
+
+        // Calls for ids that don't exist
+        assert_eq!(
+            validate(crate::Call::update_todo { id: 0, title: None, description: None, priority: None }),
+            Err(ValidityError::TodoNotFound.into())
+        );
+        assert_eq!(
+            validate(crate::Call::toggle_todo_completion { id: 0 }),
+            Err(ValidityError::TodoNotFound.into())
+        );
+        assert_eq!(
+            validate(crate::Call::delete_todo { id: 0 }),
+            Err(ValidityError::TodoNotFound.into())
+        );
+
+        // Valid calls pass
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        assert_ok!(validate(crate::Call::toggle_todo_completion { id: 0 }));
+        assert_ok!(validate(crate::Call::update_todo {
+            id: 0,
+            title: Some(b"Updated Todo".to_vec()),
+            description: None,
+            priority: None,
+        }));
+        assert_eq!(
+            validate(crate::Call::update_todo {
+                id: 0,
+                title: Some(vec![b'a'; 101]),
+                description: None,
+                priority: None,
+            }),
+            Err(ValidityError::TitleTooLong.into())
+        );
+    });
+}
+
+#[test]
+fn check_todo_call_rejects_creates_into_a_full_list() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        for _ in 0..50 {
+            create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        }
+
+        let call = RuntimeCall::TodoPallet(crate::Call::create_todo {
+            title: b"Test Todo".to_vec(),
+            description: b"This is a test todo".to_vec(),
+            priority: Priority::Medium,
+        });
+        assert_eq!(
+            CheckTodoCall::<Test>::new().validate(&account, &call, &DispatchInfo::default(), 0),
+            Err(ValidityError::TodoListFull.into())
+        );
+    });
+}
+
+#[test]
+fn set_due_date_works() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(10_000)));
+        assert_eq!(TodoPallet::due_dates(account, 0), Some(crate::DueDate { due: 10_000, reminded: false }));
+        System::assert_has_event(Event::DueDateSet { who: account, id: 0, due: Some(10_000) }.into());
+
+        // Clearing the due date removes it
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, None));
+        assert_eq!(TodoPallet::due_dates(account, 0), None);
+
+        // Purging the deleted todo removes its due date as well
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(10_000)));
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 0));
+        assert!(TodoPallet::due_dates(account, 0).is_some());
+        run_to_block(11);
+        assert_eq!(TodoPallet::due_dates(account, 0), None);
+
+        // Only existing todos can have a due date
+        assert_noop!(
+            TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(10_000)),
+            Error::<Test>::TodoNotFound
+        );
+    });
+}
+
+#[test]
+fn offchain_worker_submits_reminders_for_todos_about_to_fall_due() {
+    let (offchain, _offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+    ext.register_extension(OffchainDbExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+        UintAuthorityId::set_all_keys(vec![7]);
+
+        let account = 1;
+        create_test_todo(account, b"Due soon", b"Falls due within the lead time", Priority::Low);
+        create_test_todo(account, b"Due later", b"Falls due after the lead time", Priority::Low);
+        create_test_todo(account, b"Done", b"Already completed", Priority::Low);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(5_000)));
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 1, Some(60_000)));
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 2, Some(5_000)));
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 2));
+
+        TodoPallet::offchain_worker(1);
+
+        // A single unsigned transaction with a signed payload was submitted
+        let tx = pool_state.write().transactions.pop().unwrap();
+        assert!(pool_state.read().transactions.is_empty());
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature, None);
+
+        let RuntimeCall::TodoPallet(crate::Call::remind_due_todos { payload, signature }) = tx.call else {
+            panic!("unexpected call submitted by the offchain worker");
+        };
+        assert_eq!(payload.todos.into_inner(), vec![(account, 0)]);
+        assert_eq!(payload.public, UintAuthorityId(7));
+        assert_eq!(signature, TestSignature(7, payload.encode()));
+    });
+}
+
+#[test]
+fn offchain_worker_submits_nothing_when_nothing_is_due() {
+    let (offchain, _offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+        UintAuthorityId::set_all_keys(vec![7]);
+
+        create_test_todo(1, b"Test Todo", b"This is a test todo", Priority::Low);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(1), 0, Some(1_000_000)));
+
+        TodoPallet::offchain_worker(1);
+
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+#[test]
+fn remind_due_todos_raises_priority_once() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Due soon", b"Falls due within the lead time", Priority::Low);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(5_000)));
+
+        let payload = crate::ReminderPayload::<Test> {
+            block_number: 1,
+            todos: BoundedVec::truncate_from(vec![(account, 0)]),
+            public: UintAuthorityId(7),
+        };
+        let call = crate::Call::remind_due_todos {
+            payload: payload.clone(),
+            signature: TestSignature(7, payload.encode()),
+        };
+
+        // The signed payload is accepted by the pool, a forged one is not
+        assert_ok!(TodoPallet::validate_unsigned(TransactionSource::External, &call));
+        let forged = crate::Call::remind_due_todos {
+            payload: payload.clone(),
+            signature: TestSignature(8, payload.encode()),
+        };
+        assert_eq!(
+            TodoPallet::validate_unsigned(TransactionSource::External, &forged),
+            InvalidTransaction::BadProof.into()
+        );
+
+        // Signed origins cannot submit reminders
+        assert_noop!(
+            TodoPallet::remind_due_todos(
+                RuntimeOrigin::signed(account),
+                payload.clone(),
+                TestSignature(7, payload.encode())
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(TodoPallet::remind_due_todos(
+            RuntimeOrigin::none(),
+            payload.clone(),
+            TestSignature(7, payload.encode())
+        ));
+
+        // Check that the todo was raised and flagged
+        assert_eq!(TodoPallet::todos(account)[0].priority, Priority::High);
+        assert_eq!(TodoPallet::todo_stats(account).high_priority, 1);
+        assert!(TodoPallet::due_dates(account, 0).unwrap().reminded);
+        System::assert_has_event(Event::TodoDueSoon { who: account, id: 0, due: 5_000 }.into());
+
+        // The raise is kept as a revision made by the chain
+        let history = TodoPallet::todo_history(account, 0);
+        assert_eq!(history.len(), 1);
+        assert_eq!((history[0].changed_by, history[0].priority), (None, Priority::Low));
+
+        // The same report is now stale
+        assert_eq!(
+            TodoPallet::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::Stale.into()
+        );
+    });
+}
+
+#[test]
+fn remind_due_todos_only_accepts_reminder_authorities() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Due soon", b"Falls due within the lead time", Priority::Low);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(5_000)));
+
+        let signed_by = |key: u64| {
+            let payload = crate::ReminderPayload::<Test> {
+                block_number: 1,
+                todos: BoundedVec::truncate_from(vec![(account, 0)]),
+                public: UintAuthorityId(key),
+            };
+            let signature = TestSignature(key, payload.encode());
+            crate::Call::remind_due_todos { payload, signature }
+        };
+
+        // A correctly signed payload from a key outside the set is rejected
+        assert_eq!(TodoPallet::reminder_authorities().into_inner(), vec![REMINDER_AUTHORITY]);
+        assert_eq!(
+            TodoPallet::validate_unsigned(TransactionSource::External, &signed_by(8)),
+            InvalidTransaction::BadSigner.into()
+        );
+
+        // Only the admin origin can change the set, within its bound
+        assert_noop!(
+            TodoPallet::set_reminder_authorities(RuntimeOrigin::signed(account), vec![8]),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            TodoPallet::set_reminder_authorities(RuntimeOrigin::root(), vec![1, 2, 3, 4, 5]),
+            Error::<Test>::TooManyReminderAuthorities
+        );
+        assert_ok!(TodoPallet::set_reminder_authorities(
+            RuntimeOrigin::root(),
+            vec![REMINDER_AUTHORITY, 8]
+        ));
+        System::assert_last_event(Event::ReminderAuthoritiesSet { count: 2 }.into());
+
+        // Reports of the same block from different authorities take the same pool slot
+        let first = TodoPallet::validate_unsigned(TransactionSource::External, &signed_by(REMINDER_AUTHORITY)).unwrap();
+        let second = TodoPallet::validate_unsigned(TransactionSource::External, &signed_by(8)).unwrap();
+        assert_eq!(first.provides, second.provides);
+    });
+}
+
+#[test]
+fn offchain_worker_only_signs_with_reminder_authority_keys() {
+    let (offchain, _offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+    ext.register_extension(OffchainDbExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        create_test_todo(1, b"Due soon", b"Falls due within the lead time", Priority::Low);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(1), 0, Some(5_000)));
+
+        // A node holding only an unknown key submits nothing
+        UintAuthorityId::set_all_keys(vec![8]);
+        TodoPallet::offchain_worker(1);
+        assert!(pool_state.read().transactions.is_empty());
+
+        // With an authority key among its keys, it signs with that one
+        UintAuthorityId::set_all_keys(vec![8, REMINDER_AUTHORITY]);
+        TodoPallet::offchain_worker(1);
+        let tx = Extrinsic::decode(&mut &*pool_state.write().transactions.pop().unwrap()).unwrap();
+        let RuntimeCall::TodoPallet(crate::Call::remind_due_todos { payload, .. }) = tx.call else {
+            panic!("unexpected call submitted by the offchain worker");
+        };
+        assert_eq!(payload.public, UintAuthorityId(REMINDER_AUTHORITY));
+    });
+}
+
+#[test]
+fn remind_due_todos_keeps_urgent_todos_urgent() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Due soon", b"Falls due within the lead time", Priority::Urgent);
+        create_test_todo(account, b"Due soon", b"Falls due within the lead time", Priority::High);
+        assert_ok!(TodoPallet::set_urgency(RuntimeOrigin::signed(account), 1, 190));
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(5_000)));
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 1, Some(5_000)));
+
+        let payload = crate::ReminderPayload::<Test> {
+            block_number: 1,
+            todos: BoundedVec::truncate_from(vec![(account, 0), (account, 1)]),
+            public: UintAuthorityId(7),
+        };
+        assert_ok!(TodoPallet::remind_due_todos(
+            RuntimeOrigin::none(),
+            payload.clone(),
+            TestSignature(7, payload.encode())
+        ));
+
+        // Neither todo is lowered to the middle of the high band
+        let todos = TodoPallet::todos(account);
+        assert_eq!((todos[0].priority, todos[0].urgency), (Priority::Urgent, Priority::Urgent.urgency()));
+        assert_eq!((todos[1].priority, todos[1].urgency), (Priority::High, 190));
+        System::assert_has_event(Event::TodoDueSoon { who: account, id: 0, due: 5_000 }.into());
+        assert!(TodoPallet::todo_history(account, 0).is_empty());
+        assert!(TodoPallet::todo_history(account, 1).is_empty());
+    });
+}
+
+#[test]
+fn mutating_calls_index_todo_snapshots() {
+    use crate::indexing::{block_snapshot_key, decode_snapshot, latest_snapshot_key, SnapshotAction};
+
+    let account = 1;
+    let mut ext = new_test_ext();
+
+    ext.execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        System::set_block_number(2);
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0));
+    });
+    ext.persist_offchain_overlay();
+
+    let db = ext.offchain_db();
+    let read = |key: Vec<u8>| {
+        decode_snapshot::<Test>(&db.get(&key).expect("snapshot should be indexed"))
+            .expect("snapshot should decode")
+    };
+
+    // Snapshots are stored as JSON
+    let value = db.get(&latest_snapshot_key::<Test>(&account, 0)).unwrap();
+    assert!(value.starts_with(br#"{"who":"0x0100000000000000","id":0,"block_number":2,"action":"CompletionToggled","todo":{"#));
+    assert!(value.ends_with(br#""due":null}"#));
+
+    // The latest snapshot reflects the last change
+    let latest = read(latest_snapshot_key::<Test>(&account, 0));
+    assert_eq!(latest.action, SnapshotAction::CompletionToggled);
+    assert_eq!(latest.block_number, 2);
+    assert!(latest.todo.unwrap().is_completed());
+
+    // Earlier blocks keep their own snapshot
+    let created = read(block_snapshot_key::<Test>(&account, 0, 1u64));
+    assert_eq!(created.action, SnapshotAction::Created);
+    let todo = created.todo.unwrap();
+    assert_eq!(todo.title.as_slice(), b"Test Todo");
+    assert!(!todo.is_completed());
+
+    ext.execute_with(|| {
+        System::set_block_number(3);
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 0));
+    });
+    ext.persist_offchain_overlay();
+
+    // Deletions are indexed without the todo
+    let db = ext.offchain_db();
+    let deleted = decode_snapshot::<Test>(&db.get(&latest_snapshot_key::<Test>(&account, 0)).unwrap()).unwrap();
+    assert_eq!(deleted.action, SnapshotAction::Deleted);
+    assert_eq!(deleted.todo, None);
+}
+
+#[test]
+fn snapshots_round_trip_through_json() {
+    use crate::indexing::{decode_snapshot, SnapshotAction, TodoSnapshot};
+
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        // A title that is not valid UTF-8 and a description that needs escaping
+        let account = 1;
+        create_test_todo(account, &[0xff, 0xfe, b'!'], b"\"quoted\"\n\ttab \x01 \xe2\x9c\x93", Priority::High);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(10_000)));
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 0, Status::InProgress));
+
+        let snapshot = TodoSnapshot::<Test> {
+            who: account,
+            id: 0,
+            block_number: 1,
+            action: SnapshotAction::StatusChanged,
+            todo: TodoPallet::todos(account).first().cloned(),
+            due: Some(10_000),
+        };
+        let json = snapshot.to_json();
+        assert!(json.windows(20).any(|w| w == br#""title":[255,254,33]"#));
+        assert_eq!(decode_snapshot::<Test>(&json), Some(snapshot));
+
+        // Values that are not a snapshot are rejected
+        assert_eq!(decode_snapshot::<Test>(b"{}"), None);
+        assert_eq!(decode_snapshot::<Test>(&json[1..]), None);
+    });
+}
+
+#[test]
+fn archived_todos_are_indexed_with_the_todo() {
+    use crate::indexing::{decode_snapshot, latest_snapshot_key, SnapshotAction};
+
+    let account = 1;
+    let mut ext = new_test_ext();
+
+    ext.execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let policy = RetentionPolicy { max_age: 5_000, action: RetentionAction::Archive };
+        assert_ok!(TodoPallet::set_retention_policy(RuntimeOrigin::signed(account), Some(policy)));
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+        assert_ok!(TodoPallet::set_due_date(RuntimeOrigin::signed(account), 0, Some(10_000)));
+        assert_ok!(TodoPallet::toggle_todo_completion(RuntimeOrigin::signed(account), 0));
+        run_to_block(7);
+        assert_eq!(TodoPallet::archived_todos(account).len(), 1);
+    });
+    ext.persist_offchain_overlay();
+
+    // The snapshot holds the todo as it was archived
+    let db = ext.offchain_db();
+    let archived = decode_snapshot::<Test>(&db.get(&latest_snapshot_key::<Test>(&account, 0)).unwrap()).unwrap();
+    assert_eq!(archived.action, SnapshotAction::Archived);
+    assert_eq!(archived.due, Some(10_000));
+    let todo = archived.todo.expect("archived todos keep their todo");
+    assert_eq!(todo.title.as_slice(), b"Test Todo");
+    assert!(todo.is_completed());
+}
+
+fn wrapped_key(recipient: u8) -> crate::WrappedKey {
+    crate::WrappedKey { recipient: [recipient; 32], wrapped_key: [recipient; 48] }
+}
+
+#[test]
+fn create_encrypted_todo_works() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        assert_ok!(TodoPallet::create_encrypted_todo(
+            RuntimeOrigin::signed(account),
+            [1; 32],
+            [2; 24],
+            vec![0xab; 64],
+            vec![wrapped_key(1), wrapped_key(2)],
+            Priority::High
+        ));
+
+        // The todo itself carries no plaintext
+        let todos = TodoPallet::todos(account);
+        assert_eq!(todos.len(), 1);
+        assert!(todos[0].title.is_empty());
+        assert!(todos[0].description.is_empty());
+
+        let payload = TodoPallet::encrypted_payloads(account, 0).unwrap();
+        assert_eq!(payload.sender, [1; 32]);
+        assert_eq!(payload.nonce, [2; 24]);
+        assert_eq!(payload.ciphertext.as_slice(), &[0xab; 64][..]);
+        assert_eq!(payload.recipients.len(), 2);
+
+        System::assert_has_event(Event::TodoCreated { who: account, id: 0 }.into());
+        System::assert_has_event(Event::EncryptedPayloadSet { who: account, id: 0 }.into());
+
+        // Purging the deleted todo removes the payload
+        assert_ok!(TodoPallet::delete_todo(RuntimeOrigin::signed(account), 0));
+        assert!(TodoPallet::encrypted_payloads(account, 0).is_some());
+        run_to_block(11);
+        assert_eq!(TodoPallet::encrypted_payloads(account, 0), None);
+    });
+}
+
+#[test]
+fn encrypted_payloads_are_size_bounded() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        let create = |ciphertext: Vec<u8>, recipients: Vec<crate::WrappedKey>| {
+            TodoPallet::create_encrypted_todo(
+                RuntimeOrigin::signed(account),
+                [1; 32],
+                [2; 24],
+                ciphertext,
+                recipients,
+                Priority::Low,
+            )
+        };
+
+        assert_noop!(create(vec![0; 1025], vec![wrapped_key(1)]), Error::<Test>::CiphertextTooLong);
+        assert_noop!(create(vec![0; 16], (0..5).map(wrapped_key).collect()), Error::<Test>::TooManyRecipients);
+        assert_noop!(create(vec![0; 16], Vec::new()), Error::<Test>::NoRecipients);
+    });
+}
+
+#[test]
+fn update_encrypted_todo_works() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        assert_ok!(TodoPallet::create_encrypted_todo(
+            RuntimeOrigin::signed(account),
+            [1; 32],
+            [2; 24],
+            vec![0xab; 64],
+            vec![wrapped_key(1)],
+            Priority::Medium
+        ));
+
+        // Go to block 2 for a different timestamp
+        System::set_block_number(2);
+
+        assert_ok!(TodoPallet::update_encrypted_todo(
+            RuntimeOrigin::signed(account),
+            0,
+            [1; 32],
+            [3; 24],
+            vec![0xcd; 32],
+            vec![wrapped_key(1), wrapped_key(3)]
+        ));
+
+        let payload = TodoPallet::encrypted_payloads(account, 0).unwrap();
+        assert_eq!(payload.nonce, [3; 24]);
+        assert_eq!(payload.ciphertext.as_slice(), &[0xcd; 32][..]);
+        assert_eq!(payload.recipients.len(), 2);
+        assert_eq!(TodoPallet::todos(account)[0].updated_at, 2000);
+
+        // Updating a missing todo fails
+        assert_noop!(
+            TodoPallet::update_encrypted_todo(
+                RuntimeOrigin::signed(account),
+                1,
+                [1; 32],
+                [3; 24],
+                vec![0xcd; 32],
+                vec![wrapped_key(1)]
+            ),
+            Error::<Test>::TodoNotFound
+        );
+    });
+}
+
+#[test]
+fn scheduled_todo_call_is_dispatched_at_its_block() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        assert_ok!(TodoPallet::schedule_todo(
+            RuntimeOrigin::signed(account),
+            create_todo_call(b"Later"),
+            5,
+            None
+        ));
+        assert_eq!(TodoPallet::scheduled_todos(account).len(), 1);
+        System::assert_has_event(
+            Event::TodoScheduled { who: account, task_id: 0, when: 5, maybe_periodic: None }.into(),
+        );
+
+        // Nothing happens before the block
+        run_to_block(4);
+        assert!(TodoPallet::todos(account).is_empty());
+
+        // The todo is created on the caller's behalf at the block
+        run_to_block(5);
+        let todos = TodoPallet::todos(account);
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title.to_vec(), b"Later".to_vec());
+        assert_eq!(todos[0].created_at, 5000);
+
+        // The finished call no longer counts and can no longer be cancelled
+        assert_noop!(
+            TodoPallet::cancel_scheduled_todo(RuntimeOrigin::signed(account), 0),
+            Error::<Test>::ScheduledTodoNotFound
+        );
+    });
+}
+
+#[test]
+fn schedule_todo_is_weighed_for_every_dispatch() {
+    use frame_support::dispatch::GetDispatchInfo;
+
+    let schedule = |maybe_periodic| crate::Call::<Test>::schedule_todo {
+        call: create_todo_call(b"Weekly review"),
+        when: 2,
+        maybe_periodic,
+    };
+    let inner = create_todo_call(b"Weekly review").get_dispatch_info().weight;
+
+    // Each extra dispatch adds the weight of the call
+    let once = schedule(None).get_dispatch_info().weight;
+    assert_eq!(schedule(Some((3, 1))).get_dispatch_info().weight, once);
+    assert_eq!(schedule(Some((3, 5))).get_dispatch_info().weight, once + inner * 4);
+}
+
+#[test]
+fn periodic_scheduled_todo_call_repeats() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        assert_ok!(TodoPallet::schedule_todo(
+            RuntimeOrigin::signed(account),
+            create_todo_call(b"Weekly review"),
+            2,
+            Some((3, 3))
+        ));
+
+        // Dispatched at blocks 2, 5 and 8, then never again
+        run_to_block(7);
+        assert_eq!(TodoPallet::todos(account).len(), 2);
+        run_to_block(20);
+        assert_eq!(TodoPallet::todos(account).len(), 3);
+    });
+}
+
+#[test]
+fn scheduled_todo_call_can_complete_a_todo() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        create_test_todo(account, b"Test Todo", b"This is a test todo", Priority::Medium);
+
+        let complete = Box::new(crate::Call::toggle_todo_completion { id: 0 });
+        assert_ok!(TodoPallet::schedule_todo(RuntimeOrigin::signed(account), complete, 3, None));
+
+        run_to_block(3);
+        assert!(TodoPallet::todos(account)[0].is_completed());
+        System::assert_has_event(Event::TodoCompletionToggled { who: account, id: 0, completed: true }.into());
+    });
+}
+
+#[test]
+fn cancel_scheduled_todo_works() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        assert_ok!(TodoPallet::schedule_todo(
+            RuntimeOrigin::signed(account),
+            create_todo_call(b"Never"),
+            5,
+            None
+        ));
+
+        // Other accounts cannot cancel it
+        assert_noop!(
+            TodoPallet::cancel_scheduled_todo(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::ScheduledTodoNotFound
+        );
+
+        assert_ok!(TodoPallet::cancel_scheduled_todo(RuntimeOrigin::signed(account), 0));
+        assert!(TodoPallet::scheduled_todos(account).is_empty());
+        System::assert_has_event(Event::ScheduledTodoCancelled { who: account, task_id: 0 }.into());
+
+        // The call is never dispatched
+        run_to_block(10);
+        assert!(TodoPallet::todos(account).is_empty());
+    });
+}
+
+#[test]
+fn scheduled_todo_calls_are_bounded_per_account() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+        for when in 2..5 {
+            assert_ok!(TodoPallet::schedule_todo(
+                RuntimeOrigin::signed(account),
+                create_todo_call(b"Scheduled"),
+                when,
+                None
+            ));
+        }
+
+        // MaxScheduledPerAccount is 3
+        assert_noop!(
+            TodoPallet::schedule_todo(RuntimeOrigin::signed(account), create_todo_call(b"Too many"), 10, None),
+            Error::<Test>::TooManyScheduledTodos
+        );
+
+        // Another account has its own quota
+        assert_ok!(TodoPallet::schedule_todo(RuntimeOrigin::signed(2), create_todo_call(b"Mine"), 10, None));
+
+        // Once a call has run, its slot is free again
+        run_to_block(2);
+        assert_ok!(TodoPallet::schedule_todo(
+            RuntimeOrigin::signed(account),
+            create_todo_call(b"Now there is room"),
+            10,
+            None
+        ));
+        assert_eq!(TodoPallet::scheduled_todos(account).len(), 3);
+    });
+}
+
+#[test]
+fn schedule_todo_rejects_calls_that_cannot_be_scheduled() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let account = 1;
+
+        // Scheduling calls cannot be nested
+        let nested = Box::new(crate::Call::schedule_todo {
+            call: create_todo_call(b"Nested"),
+            when: 5,
+            maybe_periodic: None,
+        });
+        assert_noop!(
+            TodoPallet::schedule_todo(RuntimeOrigin::signed(account), nested, 3, None),
+            Error::<Test>::CallNotSchedulable
+        );
+
+        // Admin calls cannot be scheduled
+        let admin = Box::new(crate::Call::force_clear_account { who: 2 });
+        assert_noop!(
+            TodoPallet::schedule_todo(RuntimeOrigin::signed(account), admin, 3, None),
+            Error::<Test>::CallNotSchedulable
+        );
+        
+        // Neither can calls that clear or hand over the whole list
+        let list_calls: Vec<crate::Call<Test>> = vec![
+            crate::Call::clear_all_todos {},
+            crate::Call::transfer_list { to: 2 },
+            crate::Call::accept_list_transfer { from: 2, mode: TransferMode::Merge },
+        ];
+        for call in list_calls {
+            assert_noop!(
+                TodoPallet::schedule_todo(RuntimeOrigin::signed(account), Box::new(call), 3, None),
+                Error::<Test>::CallNotSchedulable
+            );
+        }
+        
+        // Calls that do not fit inline in the agenda are not stored as preimages
+        assert_noop!(
+            TodoPallet::schedule_todo(RuntimeOrigin::signed(account), create_todo_call(&[b'a'; 128]), 3, None),
+            Error::<Test>::ScheduledCallTooLarge
+        );
+
+        // Periodic calls cannot run too often or too many times
+        assert_noop!(
+            TodoPallet::schedule_todo(RuntimeOrigin::signed(account), create_todo_call(b"Always"), 3, Some((0, 2))),
+            Error::<Test>::SchedulePeriodTooShort
+        );
+        assert_noop!(
+            TodoPallet::schedule_todo(RuntimeOrigin::signed(account), create_todo_call(b"Often"), 3, Some((2, 2))),
+            Error::<Test>::SchedulePeriodTooShort
+        );
+        assert_noop!(
+            TodoPallet::schedule_todo(RuntimeOrigin::signed(account), create_todo_call(b"Forever"), 3, Some((3, 6))),
+            Error::<Test>::TooManyScheduleRepetitions
+        );
+        
+        // Calls cannot be scheduled in the past
+        assert_noop!(
+            TodoPallet::schedule_todo(RuntimeOrigin::signed(account), create_todo_call(b"Past"), 1, None),
+            pallet_scheduler::Error::<Test>::TargetBlockNumberInPast
+        );
+    });
+}
+
+// Helper function to post a board task
+fn post_test_board_task(poster: u64) {
+    assert_ok!(TodoPallet::post_board_task(
+        RuntimeOrigin::signed(poster),
+        b"Fix the docs".to_vec(),
+        b"The README is out of date".to_vec(),
+        Priority::Medium
+    ));
+}
+
+#[test]
+fn board_task_life_cycle_works() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let poster = 1;
+        let claimant = 2;
+        post_test_board_task(poster);
+
+        let task = TodoPallet::board_tasks(0).unwrap();
+        assert_eq!(task.poster, poster);
+        assert_eq!(task.status, crate::BoardTaskStatus::Open);
+        assert_eq!(TodoPallet::board_task_count(poster), 1);
+        System::assert_has_event(Event::BoardTaskPosted { poster, id: 0 }.into());
+
+        // Claim the task; ClaimDuration is 10 blocks
+        assert_ok!(TodoPallet::claim_board_task(RuntimeOrigin::signed(claimant), 0));
+        assert_eq!(
+            TodoPallet::board_tasks(0).unwrap().status,
+            crate::BoardTaskStatus::Claimed { claimant, expires_at: 11 }
+        );
+        System::assert_has_event(Event::BoardTaskClaimed { claimant, id: 0, expires_at: 11 }.into());
+
+        // Report it done
+        assert_ok!(TodoPallet::submit_board_task(RuntimeOrigin::signed(claimant), 0));
+        assert_eq!(TodoPallet::board_tasks(0).unwrap().status, crate::BoardTaskStatus::Submitted { claimant });
+        System::assert_has_event(Event::BoardTaskSubmitted { claimant, id: 0 }.into());
+
+        // Only the poster can approve it
+        assert_noop!(
+            TodoPallet::approve_board_task(RuntimeOrigin::signed(claimant), 0),
+            Error::<Test>::NotPoster
+        );
+        assert_ok!(TodoPallet::approve_board_task(RuntimeOrigin::signed(poster), 0));
+
+        // The task left the board
+        assert_eq!(TodoPallet::board_tasks(0), None);
+        assert_eq!(TodoPallet::board_task_count(poster), 0);
+        System::assert_has_event(Event::BoardTaskApproved { poster, id: 0, claimant }.into());
+    });
+}
+
+#[test]
+fn board_task_has_one_claimant_at_a_time() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let poster = 1;
+        post_test_board_task(poster);
+
+        // Posters cannot claim their own tasks
+        assert_noop!(
+            TodoPallet::claim_board_task(RuntimeOrigin::signed(poster), 0),
+            Error::<Test>::CannotClaimOwnTask
+        );
+
+        assert_ok!(TodoPallet::claim_board_task(RuntimeOrigin::signed(2), 0));
+        assert_noop!(
+            TodoPallet::claim_board_task(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::BoardTaskAlreadyClaimed
+        );
+
+        // Only the claimant can submit it
+        assert_noop!(
+            TodoPallet::submit_board_task(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::NotClaimant
+        );
+
+        // Submitted tasks cannot be claimed either
+        assert_ok!(TodoPallet::submit_board_task(RuntimeOrigin::signed(2), 0));
+        assert_noop!(
+            TodoPallet::claim_board_task(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::BoardTaskAlreadyClaimed
+        );
+
+        // Unknown tasks cannot be claimed
+        assert_noop!(
+            TodoPallet::claim_board_task(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::BoardTaskNotFound
+        );
+    });
+}
+
+#[test]
+fn board_task_claims_expire() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let poster = 1;
+        post_test_board_task(poster);
+        assert_ok!(TodoPallet::claim_board_task(RuntimeOrigin::signed(2), 0));
+
+        // The claim lapses after 10 blocks
+        System::set_block_number(11);
+        assert_noop!(
+            TodoPallet::submit_board_task(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotClaimant
+        );
+
+        // Someone else can take the task over
+        assert_ok!(TodoPallet::claim_board_task(RuntimeOrigin::signed(3), 0));
+        assert_eq!(
+            TodoPallet::board_tasks(0).unwrap().status,
+            crate::BoardTaskStatus::Claimed { claimant: 3, expires_at: 21 }
+        );
+    });
+}
+
+#[test]
+fn rejected_board_task_goes_back_to_the_claimant() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let poster = 1;
+        let claimant = 2;
+        post_test_board_task(poster);
+
+        // Only submitted tasks can be reviewed
+        assert_ok!(TodoPallet::claim_board_task(RuntimeOrigin::signed(claimant), 0));
+        assert_noop!(
+            TodoPallet::reject_board_task(RuntimeOrigin::signed(poster), 0),
+            Error::<Test>::BoardTaskNotSubmitted
+        );
+
+        System::set_block_number(5);
+        assert_ok!(TodoPallet::submit_board_task(RuntimeOrigin::signed(claimant), 0));
+        assert_ok!(TodoPallet::reject_board_task(RuntimeOrigin::signed(poster), 0));
+
+        // The claimant has a fresh claim to finish the work
+        assert_eq!(
+            TodoPallet::board_tasks(0).unwrap().status,
+            crate::BoardTaskStatus::Claimed { claimant, expires_at: 15 }
+        );
+        System::assert_has_event(Event::BoardTaskRejected { poster, id: 0, claimant }.into());
+
+        assert_ok!(TodoPallet::submit_board_task(RuntimeOrigin::signed(claimant), 0));
+        assert_ok!(TodoPallet::approve_board_task(RuntimeOrigin::signed(poster), 0));
+    });
+}
+
+#[test]
+fn board_tasks_are_bounded_and_removable() {
+    new_test_ext().execute_with(|| {
+        // Go to block 1 so we have a timestamp
+        System::set_block_number(1);
+
+        let poster = 1;
+        post_test_board_task(poster);
+        post_test_board_task(poster);
+
+        // MaxBoardTasksPerAccount is 2
+        assert_noop!(
+            TodoPallet::post_board_task(
+                RuntimeOrigin::signed(poster),
+                b"One too many".to_vec(),
+                Vec::new(),
+                Priority::Low
+            ),
+            Error::<Test>::TooManyBoardTasks
+        );
+
+        // Tasks someone is working on cannot be removed
+        assert_ok!(TodoPallet::claim_board_task(RuntimeOrigin::signed(2), 1));
+        assert_noop!(
+            TodoPallet::remove_board_task(RuntimeOrigin::signed(poster), 1),
+            Error::<Test>::BoardTaskAlreadyClaimed
+        );
+
+        // Only the poster can remove a task
+        assert_noop!(
+            TodoPallet::remove_board_task(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotPoster
+        );
+
+        // Removing a task frees up a slot
+        assert_ok!(TodoPallet::remove_board_task(RuntimeOrigin::signed(poster), 0));
+        System::assert_has_event(Event::BoardTaskRemoved { poster, id: 0 }.into());
+        assert_eq!(TodoPallet::board_tasks(0), None);
+        post_test_board_task(poster);
+        assert_eq!(TodoPallet::next_board_task_id(), 3);
+    });
+}
+
+#[test]
+fn genesis_config_creates_todos() {
+    let storage = RuntimeGenesisConfig {
+        todo_pallet: crate::GenesisConfig {
+            todos: vec![
+                (1, b"Open".to_vec(), b"Still to do".to_vec(), Priority::High, Status::InProgress),
+                (1, b"Done".to_vec(), Vec::new(), Priority::Low, Status::Done),
+                (2, b"Bob's".to_vec(), Vec::new(), Priority::Medium, Status::Backlog),
+            ],
+        },
+        ..Default::default()
+    }
+    .build_storage()
+    .unwrap();
+
+    sp_io::TestExternalities::new(storage).execute_with(|| {
+        // Each account's ids start at 0
+        let todos = TodoPallet::todos(1);
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[0].id, 0);
+        assert_eq!(todos[0].title.to_vec(), b"Open".to_vec());
+        assert_eq!(todos[0].status, Status::InProgress);
+        assert_eq!(todos[0].entered_at.in_progress, Some(0));
+        assert_eq!(todos[1].id, 1);
+        assert!(todos[1].is_completed());
+        assert_eq!(todos[1].completed_at(), Some(0));
+        assert_eq!(TodoPallet::next_id(1), 2);
+        assert_eq!(TodoPallet::todos(2)[0].id, 0);
+        assert_eq!(TodoPallet::next_id(2), 1);
+
+        // Statistics are computed for the genesis todos
+        let stats = TodoPallet::todo_stats(1);
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.pending, 1);
+        assert_eq!(stats.in_progress, 1);
+        assert_eq!(stats.high_priority, 1);
+    });
+}
+
+#[test]
+fn migration_to_v1_gives_todos_the_urgency_of_their_priority() {
+    use crate::migrations::v1::{self, MigrateToV1, TodoStatisticsV0, TodoStatisticsV1, TodoV0};
+    use frame_support::{
+        storage::unhashed,
+        traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+    };
+
+    new_test_ext().execute_with(|| {
+        let old_todo = |id, priority, completed| TodoV0::<Test> {
+            id,
+            title: BoundedVec::truncate_from(b"Old Todo".to_vec()),
+            description: BoundedVec::new(),
+            completed,
+            priority,
+            created_at: 1000,
+            updated_at: 1000,
+            completed_at: completed.then_some(1000),
+        };
+
+        // Write the todos and statistics of account 1 as version 0 stored them
+        let account = 1;
+        let todos = vec![
+            old_todo(0, Priority::Low, false),
+            old_todo(1, Priority::High, false),
+            old_todo(2, Priority::High, true),
+        ];
+        let stats = TodoStatisticsV0 { total: 3, completed: 1, pending: 2, high_priority: 1 };
+        unhashed::put_raw(&crate::Todos::<Test>::hashed_key_for(account), &todos.encode());
+        unhashed::put_raw(&crate::TodoStats::<Test>::hashed_key_for(account), &stats.encode());
+        StorageVersion::new(0).put::<TodoPallet>();
+
+        MigrateToV1::<Test>::on_runtime_upgrade();
+
+        let todos = v1::Todos::<Test>::get(account);
+        assert_eq!(
+            todos.iter().map(|t| (t.priority, t.urgency)).collect::<Vec<_>>(),
+            vec![(Priority::Low, 32), (Priority::High, 160), (Priority::High, 160)]
+        );
+        assert_eq!(todos[2].completed_at, Some(1000));
+        assert!(
+            v1::TodoStats::<Test>::get(account) ==
+                TodoStatisticsV1 {
+                    total: 3,
+                    completed: 1,
+                    pending: 2,
+                    low_priority: 1,
+                    high_priority: 1,
+                    ..Default::default()
+                }
+        );
+        assert_eq!(TodoPallet::on_chain_storage_version(), 1);
+
+        // Running the migration again changes nothing
+        v1::Todos::<Test>::mutate(account, |todos| todos[0].urgency = 50);
+        MigrateToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(v1::Todos::<Test>::get(account)[0].urgency, 50);
+    });
+}
+
+#[test]
+fn migration_to_v2_moves_todos_into_the_status_workflow() {
+    use crate::migrations::{
+        v1::{self, TodoStatisticsV1, TodoV1},
+        v2::MigrateToV2,
+    };
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let old_todo = |id, completed| TodoV1::<Test> {
+            id,
+            title: BoundedVec::truncate_from(b"Old Todo".to_vec()),
+            description: BoundedVec::new(),
+            completed,
+            priority: Priority::Urgent,
+            urgency: 240,
+            created_at: 1000,
+            updated_at: 3000,
+            completed_at: completed.then_some(2000),
+        };
+
+        // Write the todos and statistics of account 1 as version 1 stored them
+        let account = 1;
+        v1::Todos::<Test>::insert(
+            account,
+            BoundedVec::truncate_from(vec![old_todo(0, false), old_todo(1, true)]),
+        );
+        v1::TodoStats::<Test>::insert(account, TodoStatisticsV1::default());
+        StorageVersion::new(1).put::<TodoPallet>();
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+
+        // Open todos go to the backlog, completed ones are done
+        let todos = TodoPallet::todos(account);
+        assert_eq!(todos[0].status, Status::Backlog);
+        assert_eq!(todos[0].entered_at.backlog, Some(1000));
+        assert_eq!(todos[1].status, Status::Done);
+        assert_eq!(todos[1].completed_at(), Some(2000));
+        assert_eq!((todos[1].urgency, todos[1].updated_at), (240, 3000));
+        assert_eq!(
+            TodoPallet::todo_stats(account),
+            crate::TodoStatistics {
+                total: 2,
+                completed: 1,
+                pending: 1,
+                urgent_priority: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(TodoPallet::on_chain_storage_version(), 2);
+
+        // The migrated todos follow the workflow, and running the migration again changes nothing
+        assert_ok!(TodoPallet::set_status(RuntimeOrigin::signed(account), 1, Status::InProgress));
+        MigrateToV2::<Test>::on_runtime_upgrade();
+        assert_eq!(TodoPallet::todos(account)[1].status, Status::InProgress);
+    });
+}
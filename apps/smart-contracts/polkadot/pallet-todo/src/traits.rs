@@ -0,0 +1,37 @@
+use crate::Priority;
+use frame_support::weights::Weight;
+
+/// Hooks for reacting to changes in a todo's completion status.
+///
+/// Companion pallets (such as `pallet-todo-reputation`) implement this to follow
+/// completions without having to read `Todos` storage themselves.
+pub trait TodoCompletionHandler<AccountId> {
+    /// A todo was marked as completed
+    fn on_completed(who: &AccountId, id: u64, priority: Priority);
+
+    /// A previously completed todo was marked as incomplete again
+    fn on_reopened(who: &AccountId, id: u64, priority: Priority);
+
+    /// A todo was removed from the account's list
+    fn on_removed(who: &AccountId, id: u64);
+
+    /// A todo moved from one account's list to another's under a new ID
+    fn on_transferred(from: &AccountId, old_id: u64, to: &AccountId, new_id: u64);
+
+    /// Upper bound on the weight of any one of the hooks, charged by the calls that run them
+    fn weight() -> Weight;
+}
+
+impl<AccountId> TodoCompletionHandler<AccountId> for () {
+    fn on_completed(_who: &AccountId, _id: u64, _priority: Priority) {}
+
+    fn on_reopened(_who: &AccountId, _id: u64, _priority: Priority) {}
+
+    fn on_removed(_who: &AccountId, _id: u64) {}
+
+    fn on_transferred(_from: &AccountId, _old_id: u64, _to: &AccountId, _new_id: u64) {}
+
+    fn weight() -> Weight {
+        Weight::zero()
+    }
+}
@@ -0,0 +1,66 @@
+//! Weight- and size-aware fee model.
+//!
+//! Fees are calibrated in `FEE_UNIT`, the existential deposit: the base weight of an extrinsic
+//! costs one unit and heavier calls pay proportionally more. Every encoded byte is charged on top,
+//! so a large todo payload pays for the block space it takes. `SlowAdjustingFeeUpdate` raises the
+//! weight fee while blocks are busier than `TargetBlockFullness` and lets it decay back to the
+//! calibrated level once they are not.
+
+use super::{Balance, EXISTENTIAL_DEPOSIT};
+use frame_support::{
+    parameter_types,
+    weights::{
+        constants::ExtrinsicBaseWeight, ConstantMultiplier, WeightToFeeCoefficient,
+        WeightToFeeCoefficients, WeightToFeePolynomial,
+    },
+};
+use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use smallvec::smallvec;
+use sp_runtime::{traits::Bounded, FixedPointNumber, Perbill, Perquintill};
+
+/// The unit fees are calibrated in.
+pub const FEE_UNIT: Balance = EXISTENTIAL_DEPOSIT;
+
+/// Fee charged for every byte of an encoded extrinsic.
+pub const TRANSACTION_BYTE_FEE: Balance = FEE_UNIT / 100;
+
+/// Converts a weight into a fee, such that `ExtrinsicBaseWeight` costs exactly `FEE_UNIT`.
+pub struct WeightToFee;
+
+impl WeightToFeePolynomial for WeightToFee {
+    type Balance = Balance;
+
+    fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+        let p = FEE_UNIT;
+        let q = Balance::from(ExtrinsicBaseWeight::get().ref_time());
+        smallvec![WeightToFeeCoefficient {
+            degree: 1,
+            negative: false,
+            coeff_frac: Perbill::from_rational(p % q, q),
+            coeff_integer: p / q,
+        }]
+    }
+}
+
+parameter_types! {
+    pub const TransactionByteFee: Balance = TRANSACTION_BYTE_FEE;
+    /// Fees start rising once blocks are more than a quarter full.
+    pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+    /// Fees roughly double after five hours of full blocks.
+    pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 10_000);
+    /// Fees never drop below the calibrated level.
+    pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_integer(1);
+    pub MaximumMultiplier: Multiplier = Bounded::max_value();
+}
+
+/// Converts the encoded length of an extrinsic into a fee.
+pub type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
+
+/// Adjusts the fee multiplier after every block, based on how full the block was.
+pub type SlowAdjustingFeeUpdate<R> = TargetedFeeAdjustment<
+    R,
+    TargetBlockFullness,
+    AdjustmentVariable,
+    MinimumMultiplier,
+    MaximumMultiplier,
+>;
@@ -0,0 +1,172 @@
+//! Named genesis presets, from which the node and the `todo-chain-spec` binary build their
+//! chain specs.
+//!
+//! - `development`: Alice authors blocks alone and is sudo.
+//! - `local_testnet`: Alice and Bob author blocks; Alice is sudo.
+//! - `e2e`: like `development`, with every well-known account funded and a fixed set of todos
+//!   that end-to-end tests can rely on.
+
+use crate::{
+    AccountId, AuraConfig, Balance, BalancesConfig, GrandpaConfig, RuntimeGenesisConfig,
+    SudoConfig, SystemConfig, TodoConfig,
+};
+use pallet_grandpa::AuthorityId as GrandpaId;
+use pallet_todo::{GenesisTodo, Priority, Status};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_keyring::{Ed25519Keyring, Sr25519Keyring};
+
+/// Name of the development preset.
+pub const DEV_RUNTIME_PRESET: &str = "development";
+
+/// Name of the local testnet preset.
+pub const LOCAL_TESTNET_RUNTIME_PRESET: &str = "local_testnet";
+
+/// Name of the end-to-end test preset.
+pub const E2E_RUNTIME_PRESET: &str = "e2e";
+
+/// Balance every pre-funded account starts with.
+const ENDOWMENT: Balance = 1 << 60;
+
+/// Build the genesis config shared by all presets.
+fn testnet_genesis(
+    wasm_binary: &[u8],
+    initial_authorities: Vec<(AuraId, GrandpaId)>,
+    root_key: AccountId,
+    endowed_accounts: Vec<AccountId>,
+    todos: Vec<GenesisTodo<AccountId>>,
+) -> RuntimeGenesisConfig {
+    RuntimeGenesisConfig {
+        system: SystemConfig {
+            // Add Wasm runtime to storage.
+            code: wasm_binary.to_vec(),
+            ..Default::default()
+        },
+        balances: BalancesConfig {
+            balances: endowed_accounts.into_iter().map(|k| (k, ENDOWMENT)).collect(),
+        },
+        aura: AuraConfig {
+            authorities: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+        },
+        grandpa: GrandpaConfig {
+            authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+            ..Default::default()
+        },
+        sudo: SudoConfig {
+            // Assign network admin rights.
+            key: Some(root_key),
+        },
+        todo: TodoConfig {
+            todos,
+            // The node inserts Alice's `todo` key on development chains
+            reminder_authorities: vec![Sr25519Keyring::Alice.to_account_id()],
+        },
+        ..Default::default()
+    }
+}
+
+/// Aura and Grandpa keys of a well-known development account.
+fn authority_keys(aura: Sr25519Keyring, grandpa: Ed25519Keyring) -> (AuraId, GrandpaId) {
+    (aura.public().into(), grandpa.public().into())
+}
+
+/// A todo fixture.
+fn todo(
+    who: Sr25519Keyring,
+    title: &str,
+    description: &str,
+    priority: Priority,
+    status: Status,
+) -> GenesisTodo<AccountId> {
+    (who.to_account_id(), title.into(), description.into(), priority, status)
+}
+
+/// The `development` preset.
+pub fn development_config_genesis(wasm_binary: &[u8]) -> RuntimeGenesisConfig {
+    testnet_genesis(
+        wasm_binary,
+        vec![authority_keys(Sr25519Keyring::Alice, Ed25519Keyring::Alice)],
+        Sr25519Keyring::Alice.to_account_id(),
+        vec![
+            Sr25519Keyring::Alice.to_account_id(),
+            Sr25519Keyring::Bob.to_account_id(),
+            Sr25519Keyring::AliceStash.to_account_id(),
+            Sr25519Keyring::BobStash.to_account_id(),
+        ],
+        vec![todo(
+            Sr25519Keyring::Alice,
+            "Try the todo chain",
+            "Create, complete and delete a todo from the Polkadot.js apps",
+            Priority::Medium,
+            Status::Backlog,
+        )],
+    )
+}
+
+/// The `local_testnet` preset.
+pub fn local_testnet_genesis(wasm_binary: &[u8]) -> RuntimeGenesisConfig {
+    testnet_genesis(
+        wasm_binary,
+        vec![
+            authority_keys(Sr25519Keyring::Alice, Ed25519Keyring::Alice),
+            authority_keys(Sr25519Keyring::Bob, Ed25519Keyring::Bob),
+        ],
+        Sr25519Keyring::Alice.to_account_id(),
+        Sr25519Keyring::iter().map(|k| k.to_account_id()).collect(),
+        vec![
+            todo(Sr25519Keyring::Alice, "Bring up the testnet", "", Priority::High, Status::Done),
+            todo(
+                Sr25519Keyring::Bob,
+                "Check finality",
+                "Grandpa should finalize blocks",
+                Priority::Medium,
+                Status::Backlog,
+            ),
+        ],
+    )
+}
+
+/// The `e2e` preset.
+///
+/// Alice owns an open, an urgent and a completed todo, Bob owns one open todo and every other
+/// account starts with an empty list.
+pub fn e2e_genesis(wasm_binary: &[u8]) -> RuntimeGenesisConfig {
+    testnet_genesis(
+        wasm_binary,
+        vec![authority_keys(Sr25519Keyring::Alice, Ed25519Keyring::Alice)],
+        Sr25519Keyring::Alice.to_account_id(),
+        Sr25519Keyring::iter().map(|k| k.to_account_id()).collect(),
+        vec![
+            todo(
+                Sr25519Keyring::Alice,
+                "Buy groceries",
+                "Milk and eggs",
+                Priority::Medium,
+                Status::Backlog,
+            ),
+            todo(
+                Sr25519Keyring::Alice,
+                "File taxes",
+                "Due end of month",
+                Priority::High,
+                Status::Backlog,
+            ),
+            todo(Sr25519Keyring::Alice, "Read a book", "", Priority::Low, Status::Done),
+            todo(Sr25519Keyring::Bob, "Review pull request", "", Priority::High, Status::Backlog),
+        ],
+    )
+}
+
+/// The genesis config of the preset with the given name, with `wasm_binary` as the runtime code.
+pub fn get_preset(name: &str, wasm_binary: &[u8]) -> Option<RuntimeGenesisConfig> {
+    match name {
+        DEV_RUNTIME_PRESET => Some(development_config_genesis(wasm_binary)),
+        LOCAL_TESTNET_RUNTIME_PRESET => Some(local_testnet_genesis(wasm_binary)),
+        E2E_RUNTIME_PRESET => Some(e2e_genesis(wasm_binary)),
+        _ => None,
+    }
+}
+
+/// List of the supported presets.
+pub fn preset_names() -> Vec<&'static str> {
+    vec![DEV_RUNTIME_PRESET, LOCAL_TESTNET_RUNTIME_PRESET, E2E_RUNTIME_PRESET]
+}
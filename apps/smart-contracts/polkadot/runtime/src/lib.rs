@@ -6,12 +6,14 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
     construct_runtime, parameter_types,
-    traits::{ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem},
-    weights::{
-        constants::WEIGHT_REF_TIME_PER_SECOND, IdentityFee, Weight,
+    traits::{
+        ConstU128, ConstU32, ConstU64, ConstU8, EqualPrivilegeOnly, InstanceFilter,
+        KeyOwnerProofSystem,
     },
+    weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight},
 };
 use frame_system::{
     limits::{BlockLength, BlockWeights},
@@ -27,14 +29,22 @@ use sp_runtime::{
     traits::{
         AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
     },
-    transaction_validity::{TransactionSource, TransactionValidity},
-    ApplyExtrinsicResult, MultiSignature,
+    transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+    ApplyExtrinsicResult, MultiSignature, RuntimeDebug,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
+pub mod fee;
+#[cfg(feature = "std")]
+pub mod genesis_config_presets;
+mod todo_fees;
+pub use todo_fees::{
+    free_todo_calls_left, ChargeTodoTransaction, FREE_TODO_CALLS_PER_DAY, FREE_TODO_CALL_PRIORITY,
+};
+
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
     traits::Currency,
@@ -100,7 +110,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 100,
+    spec_version: 107,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
     transaction_version: 1,
@@ -246,9 +256,9 @@ impl pallet_transaction_payment::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
     type OperationalFeeMultiplier = ConstU8<5>;
-    type WeightToFee = IdentityFee<Balance>;
-    type LengthToFee = IdentityFee<Balance>;
-    type FeeMultiplierUpdate = ();
+    type WeightToFee = fee::WeightToFee;
+    type LengthToFee = fee::LengthToFee;
+    type FeeMultiplierUpdate = fee::SlowAdjustingFeeUpdate<Self>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -257,6 +267,38 @@ impl pallet_sudo::Config for Runtime {
     type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const PreimageBaseDeposit: Balance = 40 * EXISTENTIAL_DEPOSIT;
+    pub const PreimageByteDeposit: Balance = EXISTENTIAL_DEPOSIT;
+}
+
+impl pallet_preimage::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<AccountId>;
+    type BaseDeposit = PreimageBaseDeposit;
+    type ByteDeposit = PreimageByteDeposit;
+}
+
+parameter_types! {
+    /// Scheduled calls may use up to 80% of a block.
+    pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
+}
+
+impl pallet_scheduler::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type PalletsOrigin = OriginCaller;
+    type RuntimeCall = RuntimeCall;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<AccountId>;
+    type MaxScheduledPerBlock = ConstU32<50>;
+    type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type Preimages = Preimage;
+}
+
 // Configure the Todo pallet
 impl pallet_todo::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
@@ -265,6 +307,130 @@ impl pallet_todo::Config for Runtime {
     type MaxTitleLength = ConstU32<100>;
     type MaxDescriptionLength = ConstU32<500>;
     type MaxTodosPerAccount = ConstU32<50>;
+    /// The fee waiver of `ChargeTodoTransaction` covers this many calls a day.
+    type FreeCallsPerPeriod = ConstU32<FREE_TODO_CALLS_PER_DAY>;
+    type FreeCallPeriod = ConstU32<DAYS>;
+    type CompletionHandler = TodoReputation;
+    type AdminOrigin = EnsureRoot<AccountId>;
+    type MaxOpsPerWindow = ConstU32<20>;
+    type RateLimitWindow = ConstU32<MINUTES>;
+    type AuthorityId = pallet_todo::crypto::TodoAuthId;
+    /// Raise todos to high priority an hour before they fall due.
+    type ReminderLeadTime = ConstU64<{ 60 * 60 * 1000 }>;
+    type MaxRemindersPerBlock = ConstU32<20>;
+    type UnsignedPriority = TodoUnsignedPriority;
+    type MaxReminderAuthorities = ConstU32<16>;
+    type MaxCiphertextLength = ConstU32<1024>;
+    type MaxRecipients = ConstU32<16>;
+    type RuntimeCall = RuntimeCall;
+    type PalletsOrigin = OriginCaller;
+    type Scheduler = Scheduler;
+    type MaxScheduledPerAccount = ConstU32<10>;
+    /// Periodic calls run at most hourly, and at most a year of weekly runs.
+    type MinSchedulePeriod = ConstU32<HOURS>;
+    type MaxScheduleRepetitions = ConstU32<52>;
+    /// Claims on board tasks last a day.
+    type ClaimDuration = ConstU32<DAYS>;
+    type MaxBoardTasksPerAccount = ConstU32<20>;
+    type MaxRevisions = ConstU32<10>;
+    type DeletionGracePeriod = ConstU32<{ 7 * DAYS }>;
+}
+
+parameter_types! {
+    pub const TodoUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+    type Public = <Signature as Verify>::Signer;
+    type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+    RuntimeCall: From<C>,
+{
+    type Extrinsic = UncheckedExtrinsic;
+    type OverarchingCall = RuntimeCall;
+}
+
+/// One day in milliseconds, the unit of `pallet_timestamp`.
+pub const MILLISECS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+// Configure the Todo reputation pallet
+impl pallet_todo_reputation::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type PointsPerCompletion = ConstU32<10>;
+    type MomentsPerDay = ConstU64<MILLISECS_PER_DAY>;
+    type MaxLeaderboardSize = ConstU32<100>;
+}
+
+/// The kinds of proxy an account can register with `pallet_proxy`.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+    /// Can dispatch any call on behalf of the proxied account.
+    Any,
+    /// Can only dispatch `pallet_todo` calls, e.g. for an app key that manages the todo list
+    /// but must not move funds.
+    TodoOnly,
+}
+
+impl Default for ProxyType {
+    fn default() -> Self {
+        Self::Any
+    }
+}
+
+impl InstanceFilter<RuntimeCall> for ProxyType {
+    fn filter(&self, call: &RuntimeCall) -> bool {
+        match self {
+            ProxyType::Any => true,
+            ProxyType::TodoOnly => matches!(call, RuntimeCall::Todo(..)),
+        }
+    }
+
+    fn is_superset(&self, other: &Self) -> bool {
+        match (self, other) {
+            (x, y) if x == y => true,
+            (ProxyType::Any, _) => true,
+            _ => false,
+        }
+    }
+}
+
+parameter_types! {
+    // One storage item; key size 32, value size 8.
+    pub const ProxyDepositBase: Balance = 40 * EXISTENTIAL_DEPOSIT;
+    // Additional storage item size of 33 bytes.
+    pub const ProxyDepositFactor: Balance = 33 * EXISTENTIAL_DEPOSIT;
+    pub const AnnouncementDepositBase: Balance = 40 * EXISTENTIAL_DEPOSIT;
+    pub const AnnouncementDepositFactor: Balance = 66 * EXISTENTIAL_DEPOSIT;
+}
+
+impl pallet_proxy::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeCall = RuntimeCall;
+    type Currency = Balances;
+    type ProxyType = ProxyType;
+    type ProxyDepositBase = ProxyDepositBase;
+    type ProxyDepositFactor = ProxyDepositFactor;
+    type MaxProxies = ConstU32<32>;
+    type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+    type MaxPending = ConstU32<32>;
+    type CallHasher = BlakeTwo256;
+    type AnnouncementDepositBase = AnnouncementDepositBase;
+    type AnnouncementDepositFactor = AnnouncementDepositFactor;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -279,6 +445,10 @@ construct_runtime!(
         Sudo: pallet_sudo,
         // Include the custom Todo pallet
         Todo: pallet_todo,
+        TodoReputation: pallet_todo_reputation,
+        Proxy: pallet_proxy,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
     }
 );
 
@@ -297,7 +467,9 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    ChargeTodoTransaction,
+    pallet_todo::CheckRateLimit<Runtime>,
+    pallet_todo::CheckTodoCall<Runtime>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -312,8 +484,15 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
+/// Storage migrations run on the next runtime upgrade; each one skips itself once it has run.
+pub type Migrations = (
+    pallet_todo::migrations::v1::MigrateToV1<Runtime>,
+    pallet_todo::migrations::v2::MigrateToV2<Runtime>,
+);
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -474,6 +653,44 @@ impl_runtime_apis! {
         }
     }
 
+    #[api_version(2)]
+    impl pallet_todo_runtime_api::TodoApi<
+        Block,
+        AccountId,
+        pallet_todo::Todo<Runtime>,
+        pallet_todo::TodoRevision<Runtime>,
+    > for Runtime {
+        fn todos(who: AccountId, sort: pallet_todo::TodoSort) -> Vec<pallet_todo::Todo<Runtime>> {
+            Todo::sorted_todos(&who, sort)
+        }
+
+        fn stats(who: AccountId) -> pallet_todo::TodoStatistics {
+            Todo::todo_stats(who)
+        }
+
+        fn history(who: AccountId, id: u64) -> Vec<pallet_todo::TodoRevision<Runtime>> {
+            Todo::todo_history(who, id).into_inner()
+        }
+    }
+
+    impl pallet_todo_reputation_runtime_api::TodoReputationApi<Block, AccountId> for Runtime {
+        fn points(who: AccountId) -> u64 {
+            TodoReputation::points(who)
+        }
+
+        fn active_streak(who: AccountId) -> u32 {
+            TodoReputation::active_streak(&who)
+        }
+
+        fn longest_streak(who: AccountId) -> u32 {
+            TodoReputation::streaks(who).longest
+        }
+
+        fn leaderboard() -> Vec<(AccountId, u64)> {
+            TodoReputation::leaderboard().into_inner()
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,146 @@
+//! Fee waiver for todo calls.
+//!
+//! `ChargeTodoTransaction` replaces `ChargeTransactionPayment` in the `SignedExtra` tuple. Every
+//! account may submit `FREE_TODO_CALLS_PER_DAY` untipped calls per day without paying fees, as
+//! long as they are calls `pallet_todo::Pallet::is_free_call` accepts: creating, updating,
+//! toggling or moving a single todo. Anything above the quota, and every other call, is charged
+//! as usual. The quota itself is kept by `pallet_todo` in `FreeTodoCalls`.
+//!
+//! Free calls enter the pool at `FREE_TODO_CALL_PRIORITY`, below paying calls, and each provides
+//! a tag made of the sender and its current nonce. An account can therefore have only one free
+//! call waiting in the pool at a time, and a sender without balance cannot queue more calls than
+//! are included.
+
+use super::{AccountId, Balance, BlockNumber, Runtime, RuntimeCall, System, Todo, DAYS};
+use codec::{Decode, Encode};
+use pallet_transaction_payment::ChargeTransactionPayment;
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+    DispatchResult,
+};
+use sp_std::vec;
+
+/// Number of fee-free todo calls each account may make per day.
+pub const FREE_TODO_CALLS_PER_DAY: u32 = 10;
+
+/// Priority of fee-free todo calls in the transaction pool, below any call that pays.
+pub const FREE_TODO_CALL_PRIORITY: TransactionPriority = 0;
+
+/// Prefix of the tag each fee-free todo call provides in the transaction pool.
+const FREE_TODO_CALL_TAG: &[u8] = b"FreeTodoCall";
+
+/// Number of free todo calls an account has left today.
+pub fn free_todo_calls_left(who: &AccountId) -> u32 {
+    Todo::free_calls_left(who)
+}
+
+/// Charges transaction fees like `ChargeTransactionPayment`, except for todo calls within the
+/// sender's daily free quota.
+///
+/// The encoding is identical to `ChargeTransactionPayment`: a compact tip. Tipped calls are always
+/// charged, so the tip keeps its meaning as a priority bid.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub struct ChargeTodoTransaction(#[codec(compact)] Balance);
+
+impl ChargeTodoTransaction {
+    /// Whether `call` from `who` qualifies for a fee waiver
+    fn is_free(&self, who: &AccountId, call: &RuntimeCall) -> bool {
+        self.0 == 0 &&
+            matches!(call, RuntimeCall::Todo(call) if Todo::is_free_call(call)) &&
+            free_todo_calls_left(who) > 0
+    }
+
+    fn inner(&self) -> ChargeTransactionPayment<Runtime> {
+        ChargeTransactionPayment::from(self.0)
+    }
+}
+
+impl From<Balance> for ChargeTodoTransaction {
+    fn from(tip: Balance) -> Self {
+        Self(tip)
+    }
+}
+
+impl sp_std::fmt::Debug for ChargeTodoTransaction {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "ChargeTodoTransaction<{:?}>", self.0)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl SignedExtension for ChargeTodoTransaction {
+    const IDENTIFIER: &'static str = "ChargeTodoTransaction";
+    type AccountId = AccountId;
+    type Call = RuntimeCall;
+    type AdditionalSigned = ();
+    /// `None` when the fee was waived.
+    type Pre = Option<<ChargeTransactionPayment<Runtime> as SignedExtension>::Pre>;
+
+    fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        if self.is_free(who, call) {
+            // One free call per account and nonce, valid until the quota resets
+            let now: BlockNumber = System::block_number();
+            return Ok(ValidTransaction {
+                priority: FREE_TODO_CALL_PRIORITY,
+                provides: vec![(FREE_TODO_CALL_TAG, who, System::account_nonce(who)).encode()],
+                longevity: (DAYS - now % DAYS).into(),
+                ..Default::default()
+            })
+        }
+
+        self.inner().validate(who, call, info, len)
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        if self.is_free(who, call) {
+            Todo::use_free_call(who);
+            return Ok(None)
+        }
+
+        self.inner().pre_dispatch(who, call, info, len).map(Some)
+    }
+
+    fn post_dispatch(
+        pre: Option<Self::Pre>,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        match pre {
+            Some(Some(pre)) => ChargeTransactionPayment::<Runtime>::post_dispatch(
+                Some(pre),
+                info,
+                post_info,
+                len,
+                result,
+            ),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Shared helpers for runtime integration tests.
+//!
+//! Extrinsics are signed with the well-known development keys and applied through
+//! `Executive::apply_extrinsic`, exactly as block import would.
+
+#![allow(dead_code)]
+
+use codec::Encode;
+use frame_support::traits::Currency;
+use sp_keyring::AccountKeyring;
+use sp_runtime::{generic::Era, BuildStorage, MultiAddress, MultiSignature};
+use todo_runtime::{
+    AccountId, Balance, Balances, ChargeTodoTransaction, Executive, Nonce, Runtime,
+    RuntimeCall, RuntimeEvent, RuntimeGenesisConfig, SignedExtra, SignedPayload, System,
+    UncheckedExtrinsic,
+};
+
+/// Balance every endowed account starts with.
+pub const INITIAL_BALANCE: Balance = 1_000_000_000_000_000;
+
+/// Accounts endowed at genesis.
+pub fn endowed_accounts() -> Vec<AccountKeyring> {
+    vec![AccountKeyring::Alice, AccountKeyring::Bob, AccountKeyring::Charlie]
+}
+
+/// Build test externalities with balances for the endowed accounts, at block 1.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let storage = RuntimeGenesisConfig {
+        balances: pallet_balances::GenesisConfig {
+            balances: endowed_accounts()
+                .into_iter()
+                .map(|account| (account.to_account_id(), INITIAL_BALANCE))
+                .collect(),
+        },
+        ..Default::default()
+    }
+    .build_storage()
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// The signed extensions for a transaction from an account with the given nonce.
+pub fn signed_extra(nonce: Nonce, tip: Balance) -> SignedExtra {
+    (
+        frame_system::CheckNonZeroSender::<Runtime>::new(),
+        frame_system::CheckSpecVersion::<Runtime>::new(),
+        frame_system::CheckTxVersion::<Runtime>::new(),
+        frame_system::CheckGenesis::<Runtime>::new(),
+        frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+        frame_system::CheckNonce::<Runtime>::from(nonce),
+        frame_system::CheckWeight::<Runtime>::new(),
+        ChargeTodoTransaction::from(tip),
+        pallet_todo::CheckRateLimit::<Runtime>::new(),
+        pallet_todo::CheckTodoCall::<Runtime>::new(),
+    )
+}
+
+/// Sign `call` as `signer`, using the signer's current nonce.
+pub fn sign(signer: AccountKeyring, call: RuntimeCall, tip: Balance) -> UncheckedExtrinsic {
+    sign_with_nonce(signer, call, System::account_nonce(signer.to_account_id()), tip)
+}
+
+/// Sign `call` as `signer` with the given nonce.
+pub fn sign_with_nonce(
+    signer: AccountKeyring,
+    call: RuntimeCall,
+    nonce: Nonce,
+    tip: Balance,
+) -> UncheckedExtrinsic {
+    let extra = signed_extra(nonce, tip);
+    let payload = SignedPayload::new(call.clone(), extra.clone()).unwrap();
+    let signature = payload.using_encoded(|payload| signer.sign(payload));
+
+    UncheckedExtrinsic::new_signed(
+        call,
+        MultiAddress::Id(signer.to_account_id()),
+        MultiSignature::Sr25519(signature),
+        extra,
+    )
+}
+
+/// Sign and apply `call`, asserting that the extrinsic was included and dispatched successfully.
+pub fn apply(signer: AccountKeyring, call: RuntimeCall) {
+    let xt = sign(signer, call, 0);
+    Executive::apply_extrinsic(xt)
+        .expect("extrinsic should be valid")
+        .expect("extrinsic should dispatch successfully");
+}
+
+/// Free balance of an account.
+pub fn free_balance(account: &AccountId) -> Balance {
+    Balances::free_balance(account)
+}
+
+/// Events deposited since the last call to `System::reset_events`.
+pub fn events() -> Vec<RuntimeEvent> {
+    System::events().into_iter().map(|record| record.event).collect()
+}
+
+/// Total fees paid by `who` according to `TransactionFeePaid` events.
+pub fn fees_paid_by(who: &AccountId) -> Balance {
+    events()
+        .into_iter()
+        .filter_map(|event| match event {
+            RuntimeEvent::TransactionPayment(
+                pallet_transaction_payment::Event::TransactionFeePaid { who: payer, actual_fee, .. },
+            ) if &payer == who => Some(actual_fee),
+            _ => None,
+        })
+        .sum()
+}
+
+/// A `pallet_todo::Call::create_todo` with the given title.
+pub fn create_todo_call(title: &[u8]) -> RuntimeCall {
+    RuntimeCall::Todo(pallet_todo::Call::create_todo {
+        title: title.to_vec(),
+        description: b"Created from an integration test".to_vec(),
+        priority: pallet_todo::Priority::Medium,
+    })
+}
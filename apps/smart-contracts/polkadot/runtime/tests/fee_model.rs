@@ -0,0 +1,119 @@
+//! Integration tests for the weight- and size-aware fee model.
+
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::{
+    dispatch::{DispatchClass, GetDispatchInfo},
+    weights::{Weight, WeightToFee as _},
+};
+use pallet_todo::{Priority, WrappedKey};
+use pallet_transaction_payment::Multiplier;
+use sp_keyring::AccountKeyring;
+use sp_runtime::{traits::Convert, FixedPointNumber};
+use todo_runtime::{
+    fee::{self, SlowAdjustingFeeUpdate, FEE_UNIT, TRANSACTION_BYTE_FEE},
+    Balance, BlockWeights, ExtrinsicBaseWeight, Runtime, RuntimeCall, System, TransactionPayment,
+    HOURS,
+};
+
+/// Fee of `call` signed by Alice, without a tip.
+fn fee_of(call: RuntimeCall) -> Balance {
+    let xt = sign(AccountKeyring::Alice, call, 0);
+    let len = xt.encoded_size() as u32;
+    TransactionPayment::compute_fee(len, &xt.get_dispatch_info(), 0)
+}
+
+fn encrypted_todo_call(ciphertext_len: usize) -> RuntimeCall {
+    RuntimeCall::Todo(pallet_todo::Call::create_encrypted_todo {
+        sender: [1; 32],
+        nonce: [2; 24],
+        ciphertext: vec![3; ciphertext_len],
+        recipients: vec![WrappedKey { recipient: [4; 32], wrapped_key: [5; 48] }],
+        priority: Priority::Medium,
+    })
+}
+
+#[test]
+fn base_extrinsic_weight_costs_one_fee_unit() {
+    assert_eq!(fee::WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get()), FEE_UNIT);
+    assert_eq!(
+        fee::WeightToFee::weight_to_fee(&ExtrinsicBaseWeight::get().saturating_mul(10)),
+        10 * FEE_UNIT
+    );
+}
+
+#[test]
+fn every_byte_is_charged() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(TransactionPayment::length_to_fee(600), 600 * TRANSACTION_BYTE_FEE);
+    });
+}
+
+#[test]
+fn small_todo_calls_cost_a_few_fee_units() {
+    new_test_ext().execute_with(|| {
+        let fee = fee_of(create_todo_call(b"Groceries"));
+        assert!((2 * FEE_UNIT..5 * FEE_UNIT).contains(&fee), "unexpected fee {fee}");
+
+        let fee = fee_of(RuntimeCall::Todo(pallet_todo::Call::toggle_todo_completion { id: 0 }));
+        assert!((2 * FEE_UNIT..4 * FEE_UNIT).contains(&fee), "unexpected fee {fee}");
+    });
+}
+
+#[test]
+fn large_todo_payloads_pay_for_their_bytes() {
+    new_test_ext().execute_with(|| {
+        let small = fee_of(encrypted_todo_call(0));
+        let large = fee_of(encrypted_todo_call(600));
+
+        assert!((8 * FEE_UNIT..15 * FEE_UNIT).contains(&large), "unexpected fee {large}");
+        assert!(large - small >= 600 * TRANSACTION_BYTE_FEE);
+    });
+}
+
+#[test]
+fn congestion_raises_the_weight_fee() {
+    new_test_ext().execute_with(|| {
+        let call = create_todo_call(b"Groceries");
+        let weight_fee = fee::WeightToFee::weight_to_fee(&call.get_dispatch_info().weight);
+        let calm = fee_of(call.clone());
+
+        pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(
+            Multiplier::saturating_from_integer(2),
+        );
+
+        // Only the weight part of the fee is scaled by the multiplier
+        assert_eq!(fee_of(call), calm + weight_fee);
+    });
+}
+
+#[test]
+fn full_blocks_double_fees_in_about_five_hours() {
+    new_test_ext().execute_with(|| {
+        let max_normal = BlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+        System::set_block_consumed_resources(max_normal, 0);
+
+        let multiplier = (0..5 * HOURS).fold(Multiplier::one(), |multiplier, _| {
+            SlowAdjustingFeeUpdate::<Runtime>::convert(multiplier)
+        });
+
+        assert!(multiplier > Multiplier::saturating_from_rational(3, 2));
+        assert!(multiplier < Multiplier::saturating_from_rational(5, 2));
+    });
+}
+
+#[test]
+fn fees_never_drop_below_the_calibrated_level() {
+    new_test_ext().execute_with(|| {
+        System::set_block_consumed_resources(Weight::zero(), 0);
+
+        let raised = Multiplier::saturating_from_integer(2);
+        assert!(SlowAdjustingFeeUpdate::<Runtime>::convert(raised) < raised);
+        assert_eq!(
+            SlowAdjustingFeeUpdate::<Runtime>::convert(Multiplier::one()),
+            Multiplier::one()
+        );
+    });
+}
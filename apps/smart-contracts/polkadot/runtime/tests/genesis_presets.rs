@@ -0,0 +1,81 @@
+//! Integration tests for the named genesis presets.
+
+use frame_support::traits::Currency;
+use pallet_todo::{Priority, Status};
+use sp_keyring::AccountKeyring;
+use sp_runtime::BuildStorage;
+use todo_runtime::{
+    genesis_config_presets::{
+        get_preset, preset_names, DEV_RUNTIME_PRESET, E2E_RUNTIME_PRESET,
+        LOCAL_TESTNET_RUNTIME_PRESET,
+    },
+    Balances, Runtime,
+};
+
+/// Build the genesis storage of the preset with the given name.
+fn build_preset(name: &str) -> sp_io::TestExternalities {
+    let config = get_preset(name, &[]).expect("preset exists");
+    config.build_storage().expect("preset builds").into()
+}
+
+#[test]
+fn every_preset_builds() {
+    assert_eq!(preset_names().len(), 3);
+
+    for name in preset_names() {
+        build_preset(name).execute_with(|| {
+            let alice = AccountKeyring::Alice.to_account_id();
+            assert_eq!(pallet_sudo::Key::<Runtime>::get(), Some(alice.clone()));
+            assert!(!pallet_aura::Authorities::<Runtime>::get().is_empty());
+            assert!(!pallet_grandpa::Pallet::<Runtime>::grandpa_authorities().is_empty());
+            assert!(Balances::free_balance(alice) > 0);
+        });
+    }
+}
+
+#[test]
+fn unknown_preset_is_not_found() {
+    assert!(get_preset("mainnet", &[]).is_none());
+}
+
+#[test]
+fn local_testnet_has_two_authorities() {
+    build_preset(LOCAL_TESTNET_RUNTIME_PRESET).execute_with(|| {
+        assert_eq!(pallet_aura::Authorities::<Runtime>::get().len(), 2);
+        assert_eq!(pallet_grandpa::Pallet::<Runtime>::grandpa_authorities().len(), 2);
+    });
+}
+
+#[test]
+fn development_preset_has_a_sample_todo() {
+    build_preset(DEV_RUNTIME_PRESET).execute_with(|| {
+        let todos = pallet_todo::Todos::<Runtime>::get(AccountKeyring::Alice.to_account_id());
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].status, Status::Backlog);
+    });
+}
+
+#[test]
+fn e2e_preset_has_todo_fixtures() {
+    build_preset(E2E_RUNTIME_PRESET).execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let todos = pallet_todo::Todos::<Runtime>::get(&alice);
+        assert_eq!(todos.len(), 3);
+        assert_eq!(todos[0].title.to_vec(), b"Buy groceries".to_vec());
+        assert_eq!(todos[1].priority, Priority::High);
+        assert!(todos[2].is_completed());
+        assert_eq!(pallet_todo::NextId::<Runtime>::get(&alice), 3);
+
+        let stats = pallet_todo::TodoStats::<Runtime>::get(&alice);
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.pending, 2);
+
+        let bob = AccountKeyring::Bob.to_account_id();
+        let charlie = AccountKeyring::Charlie.to_account_id();
+        assert_eq!(pallet_todo::Todos::<Runtime>::get(bob).len(), 1);
+        assert!(pallet_todo::Todos::<Runtime>::get(charlie).is_empty());
+
+        // Every well-known account is funded
+        assert!(Balances::free_balance(AccountKeyring::Ferdie.to_account_id()) > 0);
+    });
+}
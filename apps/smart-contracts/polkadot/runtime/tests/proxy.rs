@@ -0,0 +1,123 @@
+//! Integration tests for the `TodoOnly` proxy type.
+
+mod common;
+
+use common::*;
+use sp_keyring::AccountKeyring;
+use sp_runtime::MultiAddress;
+use todo_runtime::{ProxyType, Runtime, RuntimeCall, RuntimeEvent, System};
+
+/// A `pallet_proxy::Call::add_proxy` registering `delegate` with the given proxy type.
+fn add_proxy_call(delegate: AccountKeyring, proxy_type: ProxyType) -> RuntimeCall {
+    RuntimeCall::Proxy(pallet_proxy::Call::add_proxy {
+        delegate: MultiAddress::Id(delegate.to_account_id()),
+        proxy_type,
+        delay: 0,
+    })
+}
+
+/// A `pallet_proxy::Call::proxy` dispatching `call` on behalf of `real`.
+fn proxy_call(real: AccountKeyring, call: RuntimeCall) -> RuntimeCall {
+    RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+        real: MultiAddress::Id(real.to_account_id()),
+        force_proxy_type: None,
+        call: Box::new(call),
+    })
+}
+
+/// The result of the last call dispatched through a proxy.
+fn last_proxy_result() -> Option<sp_runtime::DispatchResult> {
+    events().into_iter().rev().find_map(|event| match event {
+        RuntimeEvent::Proxy(pallet_proxy::Event::ProxyExecuted { result }) => Some(result),
+        _ => None,
+    })
+}
+
+#[test]
+fn todo_only_proxy_can_manage_todos() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+
+        apply(AccountKeyring::Alice, add_proxy_call(AccountKeyring::Bob, ProxyType::TodoOnly));
+
+        // Bob creates and completes a todo on Alice's list
+        apply(AccountKeyring::Bob, proxy_call(AccountKeyring::Alice, create_todo_call(b"Groceries")));
+        assert_eq!(last_proxy_result(), Some(Ok(())));
+
+        let toggle = RuntimeCall::Todo(pallet_todo::Call::toggle_todo_completion { id: 0 });
+        apply(AccountKeyring::Bob, proxy_call(AccountKeyring::Alice, toggle));
+        assert_eq!(last_proxy_result(), Some(Ok(())));
+
+        let todos = pallet_todo::Todos::<Runtime>::get(&alice);
+        assert_eq!(todos.len(), 1);
+        assert!(todos[0].is_completed());
+        assert!(pallet_todo::Todos::<Runtime>::get(&bob).is_empty());
+    });
+}
+
+#[test]
+fn todo_only_proxy_cannot_transfer() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let charlie = AccountKeyring::Charlie.to_account_id();
+
+        apply(AccountKeyring::Alice, add_proxy_call(AccountKeyring::Bob, ProxyType::TodoOnly));
+        let alice_balance = free_balance(&alice);
+        System::reset_events();
+
+        let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+            dest: MultiAddress::Id(charlie.clone()),
+            value: INITIAL_BALANCE / 2,
+        });
+        apply(AccountKeyring::Bob, proxy_call(AccountKeyring::Alice, transfer));
+
+        // The proxy call itself went through, but the transfer was filtered out
+        assert_eq!(
+            last_proxy_result(),
+            Some(Err(frame_system::Error::<Runtime>::CallFiltered.into()))
+        );
+        assert_eq!(free_balance(&alice), alice_balance);
+        assert_eq!(free_balance(&charlie), INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn todo_only_proxy_cannot_register_proxies() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+
+        apply(AccountKeyring::Alice, add_proxy_call(AccountKeyring::Bob, ProxyType::TodoOnly));
+        System::reset_events();
+
+        // Bob cannot escalate by giving Charlie an unrestricted proxy over Alice's account
+        let escalate = add_proxy_call(AccountKeyring::Charlie, ProxyType::Any);
+        apply(AccountKeyring::Bob, proxy_call(AccountKeyring::Alice, escalate));
+
+        assert_eq!(
+            last_proxy_result(),
+            Some(Err(frame_system::Error::<Runtime>::CallFiltered.into()))
+        );
+        let (proxies, _) = pallet_proxy::Proxies::<Runtime>::get(&alice);
+        assert_eq!(proxies.len(), 1);
+        assert_eq!(proxies[0].proxy_type, ProxyType::TodoOnly);
+    });
+}
+
+#[test]
+fn any_proxy_can_transfer() {
+    new_test_ext().execute_with(|| {
+        let charlie = AccountKeyring::Charlie.to_account_id();
+
+        apply(AccountKeyring::Alice, add_proxy_call(AccountKeyring::Bob, ProxyType::Any));
+
+        let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+            dest: MultiAddress::Id(charlie.clone()),
+            value: 1_000,
+        });
+        apply(AccountKeyring::Bob, proxy_call(AccountKeyring::Alice, transfer));
+
+        assert_eq!(last_proxy_result(), Some(Ok(())));
+        assert_eq!(free_balance(&charlie), INITIAL_BALANCE + 1_000);
+    });
+}
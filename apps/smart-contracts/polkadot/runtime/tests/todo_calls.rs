@@ -0,0 +1,564 @@
+//! End-to-end tests for every `pallet_todo` call.
+//!
+//! Each call is signed with the full `SignedExtra` tuple and applied through
+//! `Executive::apply_extrinsic`. Signed calls carry a tip, so they are charged even within the
+//! daily free quota, and the fee is checked against `TransactionPayment::compute_fee`.
+
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::{
+    assert_ok,
+    dispatch::GetDispatchInfo,
+    traits::{OnIdle, OnInitialize},
+    weights::Weight,
+};
+use pallet_todo::{
+    Priority, RetentionAction, RetentionPolicy, Status, TodoSort, TransferMode, WrappedKey,
+};
+use pallet_todo_runtime_api::runtime_decl_for_todo_api::TodoApiV2;
+use sp_keyring::AccountKeyring;
+use sp_runtime::{transaction_validity::InvalidTransaction, MultiSigner};
+use todo_runtime::{
+    Balance, Executive, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Scheduler, System,
+    TodoReputation, TransactionPayment, UncheckedExtrinsic,
+};
+
+/// Tip attached to every signed call, so the fee waiver never applies.
+const TIP: Balance = 1;
+
+/// Sign and apply `call` with a tip and check the fee it was charged, returning the fee.
+///
+/// Events are reset first, so afterwards `events()` only holds the events of this call.
+fn apply_charged(signer: AccountKeyring, call: RuntimeCall) -> Balance {
+    let who = signer.to_account_id();
+    let balance_before = free_balance(&who);
+
+    let xt = sign(signer, call, TIP);
+    let len = xt.encoded_size() as u32;
+    let info = xt.get_dispatch_info();
+    System::reset_events();
+
+    Executive::apply_extrinsic(xt)
+        .expect("extrinsic should be valid")
+        .expect("extrinsic should dispatch successfully");
+
+    let fee = TransactionPayment::compute_fee(len, &info, TIP);
+    assert!(fee > TIP);
+    assert_eq!(fees_paid_by(&who), fee);
+    assert_eq!(free_balance(&who), balance_before - fee);
+    fee
+}
+
+/// Assert that `event` was deposited by the last applied call.
+fn assert_todo_event(event: pallet_todo::Event<Runtime>) {
+    let event = RuntimeEvent::Todo(event);
+    assert!(events().contains(&event), "missing {event:?} in {:?}", events());
+}
+
+fn todo_call(call: pallet_todo::Call<Runtime>) -> RuntimeCall {
+    RuntimeCall::Todo(call)
+}
+
+/// Dispatch `call` as root through `pallet_sudo`, with Alice as the sudo key.
+fn sudo_call(call: pallet_todo::Call<Runtime>) -> RuntimeCall {
+    pallet_sudo::Key::<Runtime>::put(AccountKeyring::Alice.to_account_id());
+    RuntimeCall::Sudo(pallet_sudo::Call::sudo { call: Box::new(todo_call(call)) })
+}
+
+/// Assert that the last sudo call dispatched successfully.
+fn assert_sudid() {
+    assert_eq!(
+        events().into_iter().find_map(|event| match event {
+            RuntimeEvent::Sudo(pallet_sudo::Event::Sudid { sudo_result }) => Some(sudo_result),
+            _ => None,
+        }),
+        Some(Ok(()))
+    );
+}
+
+fn todos_of(signer: AccountKeyring) -> Vec<pallet_todo::Todo<Runtime>> {
+    pallet_todo::Todos::<Runtime>::get(signer.to_account_id()).into_inner()
+}
+
+#[test]
+fn create_todo() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+
+        apply_charged(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+
+        assert_todo_event(pallet_todo::Event::TodoCreated { who: alice.clone(), id: 0 });
+        let todos = todos_of(AccountKeyring::Alice);
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title.to_vec(), b"Groceries".to_vec());
+        assert_eq!(pallet_todo::NextId::<Runtime>::get(&alice), 1);
+        assert_eq!(pallet_todo::TodoStats::<Runtime>::get(&alice).pending, 1);
+    });
+}
+
+#[test]
+fn update_todo() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        apply(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::update_todo {
+                id: 0,
+                title: Some(b"Groceries and flowers".to_vec()),
+                description: None,
+                priority: Some(Priority::High),
+            }),
+        );
+
+        assert_todo_event(pallet_todo::Event::TodoUpdated { who: alice.clone(), id: 0 });
+        let todos = todos_of(AccountKeyring::Alice);
+        assert_eq!(todos[0].title.to_vec(), b"Groceries and flowers".to_vec());
+        assert_eq!(todos[0].priority, Priority::High);
+        assert_eq!(pallet_todo::TodoStats::<Runtime>::get(&alice).high_priority, 1);
+
+        // The runtime API returns the overwritten fields and who overwrote them
+        let history = Runtime::history(alice.clone(), 0);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].changed_by, Some(alice));
+        assert_eq!(history[0].title.to_vec(), b"Groceries".to_vec());
+        assert_eq!(history[0].priority, Priority::Medium);
+    });
+}
+
+#[test]
+fn set_urgency() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        apply(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+        apply(AccountKeyring::Alice, create_todo_call(b"Taxes"));
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::set_urgency { id: 1, urgency: 255 }),
+        );
+
+        assert_todo_event(pallet_todo::Event::TodoUpdated { who: alice.clone(), id: 1 });
+        let todos = todos_of(AccountKeyring::Alice);
+        assert_eq!((todos[1].priority, todos[1].urgency), (Priority::Urgent, 255));
+
+        // The runtime API lists the urgent todo first and counts it as urgent
+        let sorted = Runtime::todos(alice.clone(), TodoSort::Urgency);
+        assert_eq!(sorted.iter().map(|t| t.id).collect::<Vec<_>>(), vec![1, 0]);
+        assert_eq!(Runtime::stats(alice).urgent_priority, 1);
+    });
+}
+
+#[test]
+fn toggle_todo_completion() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        apply(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::toggle_todo_completion { id: 0 }),
+        );
+
+        assert_todo_event(pallet_todo::Event::TodoCompletionToggled {
+            who: alice.clone(),
+            id: 0,
+            completed: true,
+        });
+        assert!(todos_of(AccountKeyring::Alice)[0].is_completed());
+        assert_eq!(pallet_todo::TodoStats::<Runtime>::get(&alice).completed, 1);
+
+        // The completion handler awarded reputation points
+        assert!(TodoReputation::points(&alice) > 0);
+    });
+}
+
+#[test]
+fn set_status() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        apply(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::set_status { id: 0, status: Status::InProgress }),
+        );
+
+        assert_todo_event(pallet_todo::Event::TodoStatusChanged {
+            who: alice.clone(),
+            id: 0,
+            from: Status::Backlog,
+            to: Status::InProgress,
+        });
+        assert_eq!(todos_of(AccountKeyring::Alice)[0].status, Status::InProgress);
+        assert_eq!(Runtime::stats(alice.clone()).in_progress, 1);
+
+        // Finishing the todo through the workflow also awards reputation points
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::set_status { id: 0, status: Status::Done }),
+        );
+        assert!(todos_of(AccountKeyring::Alice)[0].is_completed());
+        assert!(TodoReputation::points(&alice) > 0);
+    });
+}
+
+#[test]
+fn delete_todo() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        apply(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+
+        apply_charged(AccountKeyring::Alice, todo_call(pallet_todo::Call::delete_todo { id: 0 }));
+
+        assert_todo_event(pallet_todo::Event::TodoDeleted { who: alice.clone(), id: 0 });
+        assert!(todos_of(AccountKeyring::Alice).is_empty());
+        assert_eq!(pallet_todo::TodoStats::<Runtime>::get(&alice).total, 0);
+
+        apply_charged(AccountKeyring::Alice, todo_call(pallet_todo::Call::undelete_todo { id: 0 }));
+
+        assert_todo_event(pallet_todo::Event::TodoRestored { who: alice.clone(), id: 0 });
+        assert_eq!(todos_of(AccountKeyring::Alice).len(), 1);
+        assert_eq!(pallet_todo::TodoStats::<Runtime>::get(&alice).total, 1);
+    });
+}
+
+#[test]
+fn set_retention_policy() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        pallet_timestamp::Now::<Runtime>::put(1_000);
+        apply(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+        apply(AccountKeyring::Alice, todo_call(pallet_todo::Call::toggle_todo_completion { id: 0 }));
+
+        // Archive todos a day after they were completed
+        let policy = RetentionPolicy { max_age: 24 * 60 * 60 * 1000, action: RetentionAction::Archive };
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::set_retention_policy { policy: Some(policy.clone()) }),
+        );
+        assert_todo_event(pallet_todo::Event::RetentionPolicySet {
+            who: alice.clone(),
+            policy: Some(policy.clone()),
+        });
+
+        // The sweep archives the todo once it is old enough
+        pallet_timestamp::Now::<Runtime>::put(1_000 + policy.max_age + 1);
+        pallet_todo::Pallet::<Runtime>::on_idle(System::block_number(), Weight::MAX);
+        assert!(todos_of(AccountKeyring::Alice).is_empty());
+        assert_eq!(pallet_todo::ArchivedTodos::<Runtime>::get(&alice).len(), 1);
+    });
+}
+
+#[test]
+fn clear_all_todos() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        apply(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+        apply(AccountKeyring::Alice, create_todo_call(b"Laundry"));
+
+        apply_charged(AccountKeyring::Alice, todo_call(pallet_todo::Call::clear_all_todos {}));
+
+        assert_todo_event(pallet_todo::Event::AllTodosCleared { who: alice.clone(), removed: 2 });
+        assert!(todos_of(AccountKeyring::Alice).is_empty());
+        assert!(!pallet_todo::NextId::<Runtime>::contains_key(&alice));
+        assert!(!pallet_todo::TodoStats::<Runtime>::contains_key(&alice));
+    });
+}
+
+#[test]
+fn transfer_list() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+        apply(AccountKeyring::Alice, create_todo_call(b"Groceries"));
+        apply(AccountKeyring::Bob, create_todo_call(b"Laundry"));
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::transfer_list { to: bob.clone() }),
+        );
+        assert_todo_event(pallet_todo::Event::ListTransferOffered {
+            from: alice.clone(),
+            to: bob.clone(),
+        });
+
+        apply_charged(
+            AccountKeyring::Bob,
+            todo_call(pallet_todo::Call::accept_list_transfer {
+                from: alice.clone(),
+                mode: TransferMode::Merge,
+            }),
+        );
+        assert_todo_event(pallet_todo::Event::ListTransferred {
+            from: alice.clone(),
+            to: bob.clone(),
+            mode: TransferMode::Merge,
+            count: 1,
+        });
+        assert!(todos_of(AccountKeyring::Alice).is_empty());
+        let todos = todos_of(AccountKeyring::Bob);
+        assert_eq!(todos.len(), 2);
+        assert_eq!((todos[1].id, todos[1].title.to_vec()), (1, b"Groceries".to_vec()));
+    });
+}
+
+#[test]
+fn admin_calls_through_sudo() {
+    new_test_ext().execute_with(|| {
+        let bob = AccountKeyring::Bob.to_account_id();
+        apply(AccountKeyring::Bob, create_todo_call(b"Spam"));
+        apply(AccountKeyring::Bob, create_todo_call(b"More spam"));
+        apply(AccountKeyring::Bob, create_todo_call(b"Even more spam"));
+
+        apply_charged(
+            AccountKeyring::Alice,
+            sudo_call(pallet_todo::Call::force_delete_todo { who: bob.clone(), id: 0 }),
+        );
+        assert_sudid();
+        assert_todo_event(pallet_todo::Event::TodoForceDeleted { who: bob.clone(), id: 0 });
+        assert_eq!(todos_of(AccountKeyring::Bob).len(), 2);
+
+        apply_charged(
+            AccountKeyring::Alice,
+            sudo_call(pallet_todo::Call::force_set_stats { who: bob.clone() }),
+        );
+        assert_sudid();
+        let stats = pallet_todo::TodoStats::<Runtime>::get(&bob);
+        assert_eq!(stats.total, 2);
+        assert_todo_event(pallet_todo::Event::StatsForceSet { who: bob.clone(), stats });
+
+        apply_charged(
+            AccountKeyring::Alice,
+            sudo_call(pallet_todo::Call::force_clear_account { who: bob.clone() }),
+        );
+        assert_sudid();
+        assert_todo_event(pallet_todo::Event::AccountForceCleared { who: bob.clone(), removed: 2 });
+        assert!(todos_of(AccountKeyring::Bob).is_empty());
+
+        // Bob's own fees were never touched by the admin calls
+        assert_eq!(fees_paid_by(&bob), 0);
+    });
+}
+
+#[test]
+fn set_due_date_and_remind_due_todos() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        pallet_timestamp::Now::<Runtime>::put(1_000);
+        apply(AccountKeyring::Alice, create_todo_call(b"Pay rent"));
+
+        // Due in ten minutes, well within the one hour lead time
+        let due = 1_000 + 10 * 60 * 1000;
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::set_due_date { id: 0, due: Some(due) }),
+        );
+        assert_todo_event(pallet_todo::Event::DueDateSet {
+            who: alice.clone(),
+            id: 0,
+            due: Some(due),
+        });
+        assert!(pallet_todo::Pallet::<Runtime>::is_due_for_reminder(&alice, 0));
+
+        // The reminder is an unsigned transaction carrying a payload signed with a `todo` key
+        let payload = pallet_todo::ReminderPayload::<Runtime> {
+            block_number: System::block_number(),
+            todos: vec![(alice.clone(), 0)].try_into().unwrap(),
+            public: MultiSigner::from(AccountKeyring::Bob.public()),
+        };
+        let signature = AccountKeyring::Bob.sign(&payload.encode()).into();
+        let xt = UncheckedExtrinsic::new_unsigned(todo_call(
+            pallet_todo::Call::remind_due_todos { payload, signature },
+        ));
+
+        // Only reminder authorities may sign it
+        assert_eq!(
+            Executive::apply_extrinsic(xt.clone()),
+            Err(InvalidTransaction::BadSigner.into())
+        );
+        assert_ok!(pallet_todo::Pallet::<Runtime>::set_reminder_authorities(
+            RuntimeOrigin::root(),
+            vec![AccountKeyring::Bob.to_account_id()],
+        ));
+        System::reset_events();
+
+        Executive::apply_extrinsic(xt)
+            .expect("extrinsic should be valid")
+            .expect("extrinsic should dispatch successfully");
+
+        assert_todo_event(pallet_todo::Event::TodoDueSoon { who: alice.clone(), id: 0, due });
+        assert_eq!(todos_of(AccountKeyring::Alice)[0].priority, Priority::High);
+        assert!(!pallet_todo::Pallet::<Runtime>::is_due_for_reminder(&alice, 0));
+
+        // Unsigned reminders are free
+        assert_eq!(fees_paid_by(&alice), 0);
+        assert_eq!(fees_paid_by(&AccountKeyring::Bob.to_account_id()), 0);
+    });
+}
+
+#[test]
+fn encrypted_todos() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let recipients = vec![WrappedKey { recipient: [2; 32], wrapped_key: [3; 48] }];
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::create_encrypted_todo {
+                sender: [1; 32],
+                nonce: [4; 24],
+                ciphertext: vec![5; 600],
+                recipients: recipients.clone(),
+                priority: Priority::Low,
+            }),
+        );
+        assert_todo_event(pallet_todo::Event::TodoCreated { who: alice.clone(), id: 0 });
+        assert_todo_event(pallet_todo::Event::EncryptedPayloadSet { who: alice.clone(), id: 0 });
+        assert!(todos_of(AccountKeyring::Alice)[0].title.is_empty());
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::update_encrypted_todo {
+                id: 0,
+                sender: [1; 32],
+                nonce: [6; 24],
+                ciphertext: vec![7; 32],
+                recipients,
+            }),
+        );
+        assert_todo_event(pallet_todo::Event::EncryptedPayloadSet { who: alice.clone(), id: 0 });
+
+        let payload = pallet_todo::EncryptedPayloads::<Runtime>::get(&alice, 0).unwrap();
+        assert_eq!(payload.nonce, [6; 24]);
+        assert_eq!(payload.ciphertext.to_vec(), vec![7; 32]);
+    });
+}
+
+#[test]
+fn schedule_and_cancel_todo_calls() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let create = |title: &[u8]| match create_todo_call(title) {
+            RuntimeCall::Todo(call) => Box::new(call),
+            _ => unreachable!(),
+        };
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::schedule_todo {
+                call: create(b"Scheduled"),
+                when: 3,
+                maybe_periodic: None,
+            }),
+        );
+        assert_todo_event(pallet_todo::Event::TodoScheduled {
+            who: alice.clone(),
+            task_id: 0,
+            when: 3,
+            maybe_periodic: None,
+        });
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::schedule_todo {
+                call: create(b"Cancelled"),
+                when: 3,
+                maybe_periodic: None,
+            }),
+        );
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::cancel_scheduled_todo { task_id: 1 }),
+        );
+        assert_todo_event(pallet_todo::Event::ScheduledTodoCancelled {
+            who: alice.clone(),
+            task_id: 1,
+        });
+        assert!(todos_of(AccountKeyring::Alice).is_empty());
+
+        // Only the remaining call runs at block 3
+        System::set_block_number(3);
+        Scheduler::on_initialize(3);
+
+        let todos = todos_of(AccountKeyring::Alice);
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title.to_vec(), b"Scheduled".to_vec());
+    });
+}
+
+#[test]
+fn community_board() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::post_board_task {
+                title: b"Fix the fence".to_vec(),
+                description: b"Bring your own hammer".to_vec(),
+                priority: Priority::Medium,
+            }),
+        );
+        assert_todo_event(pallet_todo::Event::BoardTaskPosted { poster: alice.clone(), id: 0 });
+
+        apply_charged(
+            AccountKeyring::Bob,
+            todo_call(pallet_todo::Call::claim_board_task { id: 0 }),
+        );
+        assert!(events().iter().any(|event| matches!(
+            event,
+            RuntimeEvent::Todo(pallet_todo::Event::BoardTaskClaimed { id: 0, .. })
+        )));
+
+        apply_charged(
+            AccountKeyring::Bob,
+            todo_call(pallet_todo::Call::submit_board_task { id: 0 }),
+        );
+        assert_todo_event(pallet_todo::Event::BoardTaskSubmitted { claimant: bob.clone(), id: 0 });
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::reject_board_task { id: 0 }),
+        );
+        assert_todo_event(pallet_todo::Event::BoardTaskRejected {
+            poster: alice.clone(),
+            id: 0,
+            claimant: bob.clone(),
+        });
+
+        apply_charged(
+            AccountKeyring::Bob,
+            todo_call(pallet_todo::Call::submit_board_task { id: 0 }),
+        );
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::approve_board_task { id: 0 }),
+        );
+        assert_todo_event(pallet_todo::Event::BoardTaskApproved {
+            poster: alice.clone(),
+            id: 0,
+            claimant: bob.clone(),
+        });
+        assert!(pallet_todo::BoardTasks::<Runtime>::get(0).is_none());
+
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::post_board_task {
+                title: b"Paint the fence".to_vec(),
+                description: Vec::new(),
+                priority: Priority::Low,
+            }),
+        );
+        apply_charged(
+            AccountKeyring::Alice,
+            todo_call(pallet_todo::Call::remove_board_task { id: 1 }),
+        );
+        assert_todo_event(pallet_todo::Event::BoardTaskRemoved { poster: alice.clone(), id: 1 });
+        assert_eq!(pallet_todo::BoardTaskCount::<Runtime>::get(&alice), 0);
+    });
+}
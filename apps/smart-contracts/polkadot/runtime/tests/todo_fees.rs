@@ -0,0 +1,140 @@
+//! Integration tests for the daily fee waiver on todo calls.
+
+mod common;
+
+use codec::Encode;
+use common::*;
+use sp_keyring::AccountKeyring;
+use sp_runtime::transaction_validity::{TransactionSource, ValidTransaction};
+use todo_runtime::{
+    free_todo_calls_left, Executive, Runtime, RuntimeCall, System, UncheckedExtrinsic, DAYS,
+    FREE_TODO_CALLS_PER_DAY, FREE_TODO_CALL_PRIORITY,
+};
+
+fn validate(xt: UncheckedExtrinsic) -> ValidTransaction {
+    Executive::validate_transaction(TransactionSource::External, xt, System::parent_hash())
+        .expect("extrinsic should be valid")
+}
+
+#[test]
+fn todo_calls_within_the_daily_quota_are_free() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+
+        for index in 0..FREE_TODO_CALLS_PER_DAY {
+            apply(AccountKeyring::Alice, create_todo_call(format!("Todo {index}").as_bytes()));
+        }
+
+        // No fee was charged and the todos were created
+        assert_eq!(free_balance(&alice), INITIAL_BALANCE);
+        assert_eq!(fees_paid_by(&alice), 0);
+        assert_eq!(pallet_todo::Todos::<Runtime>::get(&alice).len() as u32, FREE_TODO_CALLS_PER_DAY);
+        assert_eq!(free_todo_calls_left(&alice), 0);
+    });
+}
+
+#[test]
+fn todo_calls_above_the_daily_quota_are_charged() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+
+        for index in 0..FREE_TODO_CALLS_PER_DAY {
+            apply(AccountKeyring::Alice, create_todo_call(format!("Todo {index}").as_bytes()));
+        }
+        System::reset_events();
+
+        apply(AccountKeyring::Alice, create_todo_call(b"One too many"));
+
+        let fee = fees_paid_by(&alice);
+        assert!(fee > 0);
+        assert_eq!(free_balance(&alice), INITIAL_BALANCE - fee);
+    });
+}
+
+#[test]
+fn quota_resets_on_the_next_day() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+
+        for index in 0..FREE_TODO_CALLS_PER_DAY {
+            apply(AccountKeyring::Alice, create_todo_call(format!("Todo {index}").as_bytes()));
+        }
+
+        System::set_block_number(DAYS + 1);
+        System::reset_events();
+        apply(AccountKeyring::Alice, create_todo_call(b"A new day"));
+
+        assert_eq!(fees_paid_by(&alice), 0);
+        assert_eq!(free_balance(&alice), INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn other_calls_and_tipped_todo_calls_are_charged() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let bob = AccountKeyring::Bob.to_account_id();
+
+        // A transfer is never free
+        let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+            dest: bob.clone().into(),
+            value: 1_000,
+        });
+        apply(AccountKeyring::Alice, transfer);
+        let transfer_fee = fees_paid_by(&alice);
+        assert!(transfer_fee > 0);
+
+        // A tipped todo call pays its fee and tip
+        System::reset_events();
+        let xt = sign(AccountKeyring::Alice, create_todo_call(b"Tipped"), 1_000);
+        assert!(Executive::apply_extrinsic(xt).unwrap().is_ok());
+        assert!(fees_paid_by(&alice) >= 1_000);
+
+        // The untipped quota is still untouched
+        assert_eq!(free_todo_calls_left(&alice), FREE_TODO_CALLS_PER_DAY);
+    });
+}
+
+#[test]
+fn only_single_todo_calls_are_free() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+
+        apply(AccountKeyring::Alice, create_todo_call(b"Free"));
+        assert_eq!(fees_paid_by(&alice), 0);
+
+        // Deleting is a todo call, but not one the waiver covers
+        System::reset_events();
+        apply(AccountKeyring::Alice, RuntimeCall::Todo(pallet_todo::Call::delete_todo { id: 0 }));
+
+        assert!(fees_paid_by(&alice) > 0);
+        assert_eq!(free_todo_calls_left(&alice), FREE_TODO_CALLS_PER_DAY - 1);
+    });
+}
+
+#[test]
+fn free_todo_calls_take_one_pool_slot_per_account() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountKeyring::Alice.to_account_id();
+        let tag = (b"FreeTodoCall".as_slice(), &alice, 0u32).encode();
+
+        // A free call enters the pool at the lowest priority, tagged with the sender's nonce
+        let first = validate(sign(AccountKeyring::Alice, create_todo_call(b"First"), 0));
+        assert_eq!(first.priority, FREE_TODO_CALL_PRIORITY);
+        assert!(first.provides.contains(&tag));
+
+        // A second free call waiting behind it provides the same tag, so the pool keeps only one
+        let xt = sign_with_nonce(AccountKeyring::Alice, create_todo_call(b"Second"), 1, 0);
+        assert!(validate(xt).provides.contains(&tag));
+
+        // Paying calls are not limited and outrank free ones
+        let tipped = validate(sign_with_nonce(AccountKeyring::Alice, create_todo_call(b"Tipped"), 1, 1));
+        assert!(!tipped.provides.contains(&tag));
+        assert!(tipped.priority > FREE_TODO_CALL_PRIORITY);
+
+        // Once the first call is included the next one gets a slot of its own
+        apply(AccountKeyring::Alice, create_todo_call(b"First"));
+        let next = validate(sign(AccountKeyring::Alice, create_todo_call(b"Second"), 0));
+        assert!(!next.provides.contains(&tag));
+    });
+}
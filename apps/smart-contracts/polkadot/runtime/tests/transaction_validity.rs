@@ -0,0 +1,58 @@
+//! Integration tests for transaction-pool validation of todo calls.
+
+mod common;
+
+use common::*;
+use sp_keyring::AccountKeyring;
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
+};
+use sp_transaction_pool::runtime_api::runtime_decl_for_tagged_transaction_queue::TaggedTransactionQueueV3;
+use todo_runtime::{Runtime, RuntimeCall, System, UncheckedExtrinsic};
+
+fn validate(xt: UncheckedExtrinsic) -> TransactionValidity {
+    Runtime::validate_transaction(TransactionSource::External, xt, System::parent_hash())
+}
+
+fn rejected_with(error: pallet_todo::ValidityError) -> TransactionValidity {
+    Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(error as u8)))
+}
+
+#[test]
+fn oversized_todos_are_rejected_by_the_pool() {
+    new_test_ext().execute_with(|| {
+        let call = RuntimeCall::Todo(pallet_todo::Call::create_todo {
+            title: vec![b'a'; 101],
+            description: Vec::new(),
+            priority: pallet_todo::Priority::Low,
+        });
+
+        assert_eq!(
+            validate(sign(AccountKeyring::Alice, call, 0)),
+            rejected_with(pallet_todo::ValidityError::TitleTooLong)
+        );
+    });
+}
+
+#[test]
+fn calls_for_missing_todos_are_rejected_by_the_pool() {
+    new_test_ext().execute_with(|| {
+        let call = RuntimeCall::Todo(pallet_todo::Call::toggle_todo_completion { id: 42 });
+
+        assert_eq!(
+            validate(sign(AccountKeyring::Alice, call, 0)),
+            rejected_with(pallet_todo::ValidityError::TodoNotFound)
+        );
+    });
+}
+
+#[test]
+fn valid_todo_calls_enter_the_pool() {
+    new_test_ext().execute_with(|| {
+        assert!(validate(sign(AccountKeyring::Alice, create_todo_call(b"Valid"), 0)).is_ok());
+
+        apply(AccountKeyring::Alice, create_todo_call(b"Valid"));
+        let toggle = RuntimeCall::Todo(pallet_todo::Call::toggle_todo_completion { id: 0 });
+        assert!(validate(sign(AccountKeyring::Alice, toggle, 0)).is_ok());
+    });
+}
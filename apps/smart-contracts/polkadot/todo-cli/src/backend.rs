@@ -0,0 +1,127 @@
+//! Chain-agnostic view of a todo list.
+//!
+//! The commands only talk to a [`Backend`]; `substrate` and `solana` implement it for
+//! `pallet_todo` and the Anchor `todo_program`.
+
+use serde::Serialize;
+
+/// Priority level of a todo.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    /// Only the Substrate pallet has urgent todos
+    Urgent,
+}
+
+/// Workflow status of a todo.
+///
+/// Todos of the Solana program are only ever in the backlog or done.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Backlog,
+    InProgress,
+    Blocked,
+    Done,
+    Cancelled,
+}
+
+impl Status {
+    /// Whether the todo still has to be done; blocked todos count, cancelled ones do not.
+    pub fn is_pending(self) -> bool {
+        matches!(self, Status::Backlog | Status::InProgress | Status::Blocked)
+    }
+
+    /// Whether the pallet's workflow lets a todo in this status move to done.
+    pub fn can_complete(self) -> bool {
+        matches!(self, Status::Backlog | Status::InProgress)
+    }
+}
+
+/// A todo item, as stored by either chain.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Todo {
+    /// ID of the todo, unique per account
+    pub id: u64,
+    /// Title of the todo
+    pub title: String,
+    /// Description of the todo
+    pub description: String,
+    /// Workflow status of the todo
+    pub status: Status,
+    /// Whether the todo is done
+    pub completed: bool,
+    /// Priority level of the todo
+    pub priority: Priority,
+    /// When the todo was created, in seconds since the Unix epoch
+    pub created_at: i64,
+    /// When the todo was completed, in seconds since the Unix epoch
+    pub completed_at: Option<i64>,
+}
+
+/// Changes to apply to a todo; `None` keeps the current value.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TodoChanges {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub priority: Option<Priority>,
+}
+
+impl TodoChanges {
+    /// Whether the changes leave the todo as it is.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.description.is_none() && self.priority.is_none()
+    }
+}
+
+/// Todo statistics of an account.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Stats {
+    pub total: u32,
+    pub completed: u32,
+    pub pending: u32,
+    pub high_priority: u32,
+}
+
+impl Stats {
+    /// Compute the statistics of a todo list, counting pending high priority todos like the
+    /// Solana program does.
+    pub fn of(todos: &[Todo]) -> Self {
+        let total = todos.len() as u32;
+        let completed = todos.iter().filter(|t| t.completed).count() as u32;
+        let high_priority =
+            todos.iter().filter(|t| t.priority == Priority::High && !t.completed).count() as u32;
+
+        Stats { total, completed, pending: total - completed, high_priority }
+    }
+}
+
+/// The todo list of the signing account on some chain.
+///
+/// Every method that changes the list waits until the change is included in a block.
+#[allow(async_fn_in_trait)]
+pub trait Backend {
+    /// All todos of the account.
+    async fn list(&self) -> Result<Vec<Todo>, String>;
+
+    /// Create a todo and return its ID.
+    async fn add(&self, title: &str, description: &str, priority: Priority)
+        -> Result<u64, String>;
+
+    /// Apply `changes` to a todo.
+    async fn edit(&self, id: u64, changes: &TodoChanges) -> Result<(), String>;
+
+    /// Mark a todo as done; only called for todos whose status can move to done.
+    async fn complete(&self, id: u64) -> Result<(), String>;
+
+    /// Delete a todo.
+    async fn remove(&self, id: u64) -> Result<(), String>;
+
+    /// Todo statistics of the account.
+    async fn stats(&self) -> Result<Stats, String> {
+        Ok(Stats::of(&self.list().await?))
+    }
+}
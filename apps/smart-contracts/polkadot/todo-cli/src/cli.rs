@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use crate::backend::Priority;
+
+/// Manage your todos on the Substrate todo chain or with the Solana todo program.
+#[derive(Debug, clap::Parser)]
+#[command(name = "todo", version, about)]
+pub struct Cli {
+    /// Chain that stores the todos
+    #[arg(long, value_enum, default_value_t = Chain::Substrate, env = "TODO_CHAIN", global = true)]
+    pub chain: Chain,
+
+    /// Node URL; defaults to a local development node of the chain
+    #[arg(long, env = "TODO_URL", global = true)]
+    pub url: Option<String>,
+
+    /// Print JSON instead of tables
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(flatten)]
+    pub keys: KeyArgs,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// Chains the todos can be stored on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum Chain {
+    /// `pallet_todo` on a Substrate node
+    Substrate,
+    /// The Anchor `todo_program` on a Solana cluster
+    Solana,
+}
+
+/// Where the signing key comes from.
+#[derive(Debug, clap::Args)]
+pub struct KeyArgs {
+    /// Substrate keystore directory, as written by `todo-node key insert --key-type acco`
+    #[arg(long, env = "TODO_KEYSTORE", global = true)]
+    pub keystore: Option<PathBuf>,
+
+    /// Hex public key of the Substrate keystore key to use, if the keystore holds several
+    #[arg(long, env = "TODO_KEY", global = true)]
+    pub key: Option<String>,
+
+    /// Substrate secret URI, such as `//Alice`, to use instead of the keystore
+    #[arg(long, env = "TODO_SURI", global = true, conflicts_with = "keystore")]
+    pub suri: Option<String>,
+
+    /// Solana keypair file; defaults to `~/.config/solana/id.json`
+    #[arg(long, env = "TODO_KEYPAIR", global = true)]
+    pub keypair: Option<PathBuf>,
+
+    /// Address of the Solana todo program, if it is not deployed at its declared ID
+    #[arg(long, env = "TODO_PROGRAM_ID", global = true)]
+    pub program_id: Option<String>,
+}
+
+/// Todo commands.
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Create a todo
+    Add {
+        /// Title of the todo
+        title: String,
+
+        /// Description of the todo
+        #[arg(long, short, default_value = "")]
+        description: String,
+
+        /// Priority of the todo
+        #[arg(long, short, value_enum, default_value_t = Priority::Medium)]
+        priority: Priority,
+    },
+
+    /// List your todos
+    List {
+        /// Only list todos that are neither done nor cancelled
+        #[arg(long)]
+        pending: bool,
+    },
+
+    /// Mark a todo as completed
+    Done {
+        /// ID of the todo
+        id: u64,
+    },
+
+    /// Change the title, description or priority of a todo
+    Edit {
+        /// ID of the todo
+        id: u64,
+
+        /// New title
+        #[arg(long, short)]
+        title: Option<String>,
+
+        /// New description
+        #[arg(long, short)]
+        description: Option<String>,
+
+        /// New priority
+        #[arg(long, short, value_enum)]
+        priority: Option<Priority>,
+    },
+
+    /// Delete a todo
+    Rm {
+        /// ID of the todo
+        id: u64,
+    },
+
+    /// Show your todo statistics
+    Stats,
+}
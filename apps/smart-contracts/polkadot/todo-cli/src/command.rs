@@ -0,0 +1,94 @@
+use std::io::Write;
+
+use serde_json::json;
+
+use crate::{
+    backend::{Backend, Status, TodoChanges},
+    cli::Command,
+    output,
+};
+
+/// Run `command` against `backend`, writing the result to `out` as a table or as JSON.
+pub async fn run<B: Backend>(
+    backend: &B,
+    command: Command,
+    json: bool,
+    out: &mut impl Write,
+) -> Result<(), String> {
+    match command {
+        Command::Add { title, description, priority } => {
+            let id = backend.add(&title, &description, priority).await?;
+            if json {
+                output::json(out, &json!({ "id": id }))
+            } else {
+                output::line(out, &format!("Created todo {id}"))
+            }
+        },
+        Command::List { pending } => {
+            let mut todos = backend.list().await?;
+            if pending {
+                todos.retain(|todo| todo.status.is_pending());
+            }
+            if json {
+                output::json(out, &todos)
+            } else {
+                output::todos(out, &todos)
+            }
+        },
+        Command::Done { id } => {
+            let todo = backend
+                .list()
+                .await?
+                .into_iter()
+                .find(|todo| todo.id == id)
+                .ok_or_else(|| format!("todo {id} not found"))?;
+
+            // Completed todos are left alone, and the workflow keeps blocked and cancelled todos
+            // from moving to done
+            let already_done = todo.status == Status::Done;
+            if !already_done {
+                if !todo.status.can_complete() {
+                    let status = output::status(todo.status);
+                    return Err(format!("todo {id} is {status} and cannot be marked done"))
+                }
+                backend.complete(id).await?;
+            }
+            if json {
+                output::json(out, &json!({ "id": id, "completed": true }))
+            } else if already_done {
+                output::line(out, &format!("Todo {id} was already done"))
+            } else {
+                output::line(out, &format!("Completed todo {id}"))
+            }
+        },
+        Command::Edit { id, title, description, priority } => {
+            let changes = TodoChanges { title, description, priority };
+            if changes.is_empty() {
+                return Err("nothing to change, pass --title, --description or --priority".into())
+            }
+
+            backend.edit(id, &changes).await?;
+            if json {
+                output::json(out, &json!({ "id": id }))
+            } else {
+                output::line(out, &format!("Updated todo {id}"))
+            }
+        },
+        Command::Rm { id } => {
+            backend.remove(id).await?;
+            if json {
+                output::json(out, &json!({ "id": id }))
+            } else {
+                output::line(out, &format!("Deleted todo {id}"))
+            }
+        },
+        Command::Stats => {
+            let stats = backend.stats().await?;
+            if json {
+                output::json(out, &stats)
+            } else {
+                output::stats(out, &stats)
+            }
+        },
+    }
+}
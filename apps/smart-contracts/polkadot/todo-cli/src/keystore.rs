@@ -0,0 +1,63 @@
+//! Signing keys from local keystores.
+//!
+//! Substrate keys come from a keystore directory in the node's layout: one file per key, named
+//! by the hex key type followed by the hex public key, holding the secret URI as a JSON string.
+//! Solana keys come from a keypair file as written by `solana-keygen`.
+
+use std::{fs, path::Path, str::FromStr};
+
+use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair};
+use subxt_signer::{sr25519, SecretUri};
+
+/// Key type of account keys in a Substrate keystore.
+const ACCOUNT_KEY_TYPE: &[u8; 4] = b"acco";
+
+/// The sr25519 keypair of a secret URI, such as `//Alice`.
+pub fn sr25519_from_suri(suri: &str) -> Result<sr25519::Keypair, String> {
+    let uri = SecretUri::from_str(suri).map_err(|e| format!("invalid secret URI: {e}"))?;
+    sr25519::Keypair::from_uri(&uri).map_err(|e| format!("invalid secret URI: {e}"))
+}
+
+/// The account key in `keystore` with the given hex public key, or the only account key if no
+/// public key is given.
+pub fn sr25519_from_keystore(
+    keystore: &Path,
+    public: Option<&str>,
+) -> Result<sr25519::Keypair, String> {
+    let prefix = hex::encode(ACCOUNT_KEY_TYPE);
+    let public = public.map(|public| public.trim_start_matches("0x").to_lowercase());
+
+    let entries = fs::read_dir(keystore)
+        .map_err(|e| format!("failed to read keystore {}: {e}", keystore.display()))?;
+    let mut keys = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            match (name.strip_prefix(&prefix), &public) {
+                (Some(key), Some(public)) => key == public,
+                (Some(_), None) => true,
+                (None, _) => false,
+            }
+        })
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+
+    let path = match (keys.pop(), keys.is_empty()) {
+        (Some(path), true) => path,
+        (Some(_), false) => {
+            return Err("the keystore holds several account keys, pick one with --key".into())
+        },
+        (None, _) => return Err(format!("no account key found in {}", keystore.display())),
+    };
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read key {}: {e}", path.display()))?;
+    let suri: String = serde_json::from_str(&contents)
+        .map_err(|e| format!("malformed key file {}: {e}", path.display()))?;
+    sr25519_from_suri(&suri)
+}
+
+/// The Solana keypair stored in `path`.
+pub fn solana_keypair(path: &Path) -> Result<Keypair, String> {
+    read_keypair_file(path).map_err(|e| format!("failed to read keypair {}: {e}", path.display()))
+}
@@ -0,0 +1,73 @@
+//! Manages todos on the Substrate todo chain or with the Solana todo program.
+//!
+//! ```bash
+//! todo --suri //Alice add "Write docs" --priority high
+//! todo --keystore /tmp/todo/chains/dev/keystore list --pending
+//! todo --chain solana done 1
+//! todo --chain solana stats --json
+//! ```
+
+use std::{path::PathBuf, str::FromStr};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use clap::Parser;
+
+use crate::{
+    cli::{Chain, Cli, KeyArgs},
+    solana::SolanaBackend,
+    substrate::SubstrateBackend,
+};
+
+mod backend;
+mod cli;
+mod command;
+mod keystore;
+mod output;
+mod solana;
+mod substrate;
+
+#[cfg(test)]
+mod tests;
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+    let cli = Cli::parse();
+    let mut out = std::io::stdout();
+
+    match cli.chain {
+        Chain::Substrate => {
+            let signer = substrate_signer(&cli.keys)?;
+            let url = cli.url.as_deref().unwrap_or(substrate::DEFAULT_URL);
+            let backend = SubstrateBackend::connect(url, signer).await?;
+            command::run(&backend, cli.command, cli.json, &mut out).await
+        },
+        Chain::Solana => {
+            let keypair = keystore::solana_keypair(&solana_keypair_path(&cli.keys)?)?;
+            let program_id = match &cli.keys.program_id {
+                Some(id) => Pubkey::from_str(id).map_err(|e| format!("invalid program ID: {e}"))?,
+                None => todo_program::ID,
+            };
+            let url = cli.url.as_deref().unwrap_or(solana::DEFAULT_URL);
+            let backend = SolanaBackend::connect(url, program_id, keypair)?;
+            command::run(&backend, cli.command, cli.json, &mut out).await
+        },
+    }
+}
+
+fn substrate_signer(keys: &KeyArgs) -> Result<subxt_signer::sr25519::Keypair, String> {
+    match (&keys.suri, &keys.keystore) {
+        (Some(suri), _) => keystore::sr25519_from_suri(suri),
+        (None, Some(dir)) => keystore::sr25519_from_keystore(dir, keys.key.as_deref()),
+        (None, None) => Err("no signing key, pass --keystore or --suri".into()),
+    }
+}
+
+/// The Solana keypair file, defaulting to the one the Solana CLI uses.
+fn solana_keypair_path(keys: &KeyArgs) -> Result<PathBuf, String> {
+    match &keys.keypair {
+        Some(path) => Ok(path.clone()),
+        None => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".config/solana/id.json"))
+            .ok_or_else(|| "no keypair file, pass --keypair".into()),
+    }
+}
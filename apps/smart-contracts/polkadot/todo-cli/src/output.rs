@@ -0,0 +1,95 @@
+//! Table and JSON output.
+
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::backend::{Priority, Stats, Status, Todo};
+
+/// Write `value` as pretty-printed JSON.
+pub fn json(out: &mut impl Write, value: &impl Serialize) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    line(out, &json)
+}
+
+/// Write a line of text.
+pub fn line(out: &mut impl Write, text: &str) -> Result<(), String> {
+    writeln!(out, "{text}").map_err(|e| format!("failed to write output: {e}"))
+}
+
+/// Write a table of todos.
+pub fn todos(out: &mut impl Write, todos: &[Todo]) -> Result<(), String> {
+    if todos.is_empty() {
+        return line(out, "No todos")
+    }
+
+    let rows = todos
+        .iter()
+        .map(|todo| {
+            vec![
+                todo.id.to_string(),
+                status(todo.status).to_string(),
+                priority(todo.priority).to_string(),
+                todo.title.clone(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table(out, &["ID", "STATUS", "PRIORITY", "TITLE"], &rows)
+}
+
+/// Write a table of todo statistics.
+pub fn stats(out: &mut impl Write, stats: &Stats) -> Result<(), String> {
+    let rows = [
+        ("Total", stats.total),
+        ("Completed", stats.completed),
+        ("Pending", stats.pending),
+        ("High priority", stats.high_priority),
+    ]
+    .map(|(name, count)| vec![name.to_string(), count.to_string()]);
+    table(out, &["STAT", "COUNT"], &rows)
+}
+
+/// Name of a status, as shown in tables and messages.
+pub fn status(status: Status) -> &'static str {
+    match status {
+        Status::Backlog => "backlog",
+        Status::InProgress => "in progress",
+        Status::Blocked => "blocked",
+        Status::Done => "done",
+        Status::Cancelled => "cancelled",
+    }
+}
+
+fn priority(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "low",
+        Priority::Medium => "medium",
+        Priority::High => "high",
+        Priority::Urgent => "urgent",
+    }
+}
+
+/// Write `rows` under `header`, with every column padded to its widest cell.
+fn table(out: &mut impl Write, header: &[&str], rows: &[Vec<String>]) -> Result<(), String> {
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(column, name)| {
+            rows.iter().map(|row| row[column].chars().count()).fold(name.len(), usize::max)
+        })
+        .collect::<Vec<_>>();
+    let format_row = |cells: Vec<&str>| {
+        let padded = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>();
+        padded.join("  ").trim_end().to_string()
+    };
+
+    line(out, &format_row(header.to_vec()))?;
+    for row in rows {
+        line(out, &format_row(row.iter().map(String::as_str).collect()))?;
+    }
+    Ok(())
+}
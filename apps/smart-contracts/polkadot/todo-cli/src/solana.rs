@@ -0,0 +1,157 @@
+//! The Anchor `todo_program` on a Solana cluster.
+//!
+//! Each owner keeps all todos in one `TodoList` account, derived from the owner's key. The list
+//! is initialized in the same transaction as the owner's first todo.
+
+use std::{str::FromStr, sync::Arc};
+
+use anchor_client::{
+    anchor_lang::{system_program, InstructionData, ToAccountMetas},
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+    Client, ClientError, Cluster, Program,
+};
+use todo_program::{accounts, instruction, TodoList};
+
+use crate::backend::{Backend, Priority, Status, Todo, TodoChanges};
+
+/// URL of a local `solana-test-validator`.
+pub const DEFAULT_URL: &str = "http://127.0.0.1:8899";
+
+/// The todo list of an owner on a Solana cluster.
+pub struct SolanaBackend {
+    program: Program<Arc<Keypair>>,
+    owner: Pubkey,
+    todo_list: Pubkey,
+}
+
+impl SolanaBackend {
+    /// Connect to the cluster at `url`, where the program is deployed at `program_id`, with
+    /// `owner` signing and paying for transactions.
+    pub fn connect(url: &str, program_id: Pubkey, owner: Keypair) -> Result<Self, String> {
+        let cluster = Cluster::from_str(url).map_err(|e| format!("invalid cluster {url}: {e}"))?;
+        let owner = Arc::new(owner);
+        let client =
+            Client::new_with_options(cluster, owner.clone(), CommitmentConfig::confirmed());
+        let program = client.program(program_id).map_err(|e| e.to_string())?;
+        let (todo_list, _) = Pubkey::find_program_address(
+            &[b"todo_list", owner.pubkey().as_ref()],
+            &program_id,
+        );
+
+        Ok(SolanaBackend { program, owner: owner.pubkey(), todo_list })
+    }
+
+    /// The owner's todo list, or `None` if it was never initialized.
+    async fn fetch_todo_list(&self) -> Result<Option<TodoList>, String> {
+        match self.program.account::<TodoList>(self.todo_list).await {
+            Ok(todo_list) => Ok(Some(todo_list)),
+            Err(ClientError::AccountNotFound) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    fn initialize_todo_list(&self) -> Instruction {
+        Instruction {
+            program_id: self.program.id(),
+            accounts: accounts::InitializeTodoList {
+                todo_list: self.todo_list,
+                owner: self.owner,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::InitializeTodoList {}.data(),
+        }
+    }
+
+    /// Send an instruction that takes the accounts every todo instruction takes.
+    async fn send(&self, args: impl InstructionData) -> Result<(), String> {
+        self.program
+            .request()
+            .accounts(accounts::UpdateTodo { todo_list: self.todo_list, owner: self.owner })
+            .args(args)
+            .send()
+            .await
+            .map(drop)
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl Backend for SolanaBackend {
+    async fn list(&self) -> Result<Vec<Todo>, String> {
+        let todos = self.fetch_todo_list().await?.map(|list| list.todos).unwrap_or_default();
+        Ok(todos.into_iter().map(todo).collect())
+    }
+
+    async fn add(&self, title: &str, description: &str, priority: Priority) -> Result<u64, String> {
+        let priority = program_priority(priority)?;
+        let mut request = self.program.request();
+        let id = match self.fetch_todo_list().await? {
+            Some(todo_list) => todo_list.next_id,
+            None => {
+                request = request.instruction(self.initialize_todo_list());
+                1
+            },
+        };
+
+        request
+            .accounts(accounts::CreateTodo { todo_list: self.todo_list, owner: self.owner })
+            .args(instruction::CreateTodo {
+                title: title.into(),
+                description: description.into(),
+                priority,
+            })
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(id)
+    }
+
+    async fn edit(&self, id: u64, changes: &TodoChanges) -> Result<(), String> {
+        self.send(instruction::UpdateTodo {
+            todo_id: id,
+            title: changes.title.clone(),
+            description: changes.description.clone(),
+            priority: changes.priority.map(program_priority).transpose()?,
+        })
+        .await
+    }
+
+    async fn complete(&self, id: u64) -> Result<(), String> {
+        self.send(instruction::ToggleTodoCompletion { todo_id: id }).await
+    }
+
+    async fn remove(&self, id: u64) -> Result<(), String> {
+        self.send(instruction::DeleteTodo { todo_id: id }).await
+    }
+}
+
+fn program_priority(priority: Priority) -> Result<todo_program::Priority, String> {
+    match priority {
+        Priority::Low => Ok(todo_program::Priority::Low),
+        Priority::Medium => Ok(todo_program::Priority::Medium),
+        Priority::High => Ok(todo_program::Priority::High),
+        Priority::Urgent => Err("the Solana program has no urgent priority".into()),
+    }
+}
+
+fn todo(todo: todo_program::Todo) -> Todo {
+    Todo {
+        id: todo.id,
+        title: todo.title,
+        description: todo.description,
+        status: if todo.completed { Status::Done } else { Status::Backlog },
+        completed: todo.completed,
+        priority: match todo.priority {
+            todo_program::Priority::Low => Priority::Low,
+            todo_program::Priority::Medium => Priority::Medium,
+            todo_program::Priority::High => Priority::High,
+        },
+        created_at: todo.created_at,
+        completed_at: todo.completed_at,
+    }
+}
@@ -0,0 +1,118 @@
+//! `pallet_todo` on a Substrate node, through `todo-subxt-client`.
+
+use subxt_signer::sr25519::Keypair;
+use todo_subxt_client::{calls, events::TodoEvent, storage, AccountId, TodoClient};
+
+use crate::backend::{Backend, Priority, Stats, Status, Todo, TodoChanges};
+
+/// URL of a local development node.
+pub const DEFAULT_URL: &str = "ws://127.0.0.1:9944";
+
+/// The todo list of an account on a Substrate node.
+pub struct SubstrateBackend {
+    client: TodoClient,
+    signer: Keypair,
+    account: AccountId,
+}
+
+impl SubstrateBackend {
+    /// Connect to the node at `url`, signing with `signer`.
+    pub async fn connect(url: &str, signer: Keypair) -> Result<Self, String> {
+        let client = TodoClient::from_url(url)
+            .await
+            .map_err(|e| format!("failed to connect to {url}: {e}"))?;
+        let account = signer.public_key().into();
+
+        Ok(SubstrateBackend { client, signer, account })
+    }
+
+    async fn submit(&self, call: &impl subxt::tx::TxPayload) -> Result<Vec<TodoEvent>, String> {
+        todo_subxt_client::submit(&self.client, call, &self.signer).await.map_err(|e| e.to_string())
+    }
+}
+
+impl Backend for SubstrateBackend {
+    async fn list(&self) -> Result<Vec<Todo>, String> {
+        let todos = storage::todos(&self.client, &self.account).await.map_err(|e| e.to_string())?;
+        Ok(todos.into_iter().map(todo).collect())
+    }
+
+    async fn add(&self, title: &str, description: &str, priority: Priority) -> Result<u64, String> {
+        let call = calls::create_todo(title, description, chain_priority(priority));
+        let events = self.submit(&call).await?;
+
+        events
+            .into_iter()
+            .find_map(|event| match event {
+                TodoEvent::TodoCreated { id, .. } => Some(id),
+                _ => None,
+            })
+            .ok_or_else(|| "the chain did not report the new todo".into())
+    }
+
+    async fn edit(&self, id: u64, changes: &TodoChanges) -> Result<(), String> {
+        let call = calls::update_todo(
+            id,
+            changes.title.clone().map(String::into_bytes),
+            changes.description.clone().map(String::into_bytes),
+            changes.priority.map(chain_priority),
+        );
+        self.submit(&call).await.map(drop)
+    }
+
+    async fn complete(&self, id: u64) -> Result<(), String> {
+        self.submit(&calls::set_status(id, todo_subxt_client::Status::Done)).await.map(drop)
+    }
+
+    async fn remove(&self, id: u64) -> Result<(), String> {
+        self.submit(&calls::delete_todo(id)).await.map(drop)
+    }
+
+    async fn stats(&self) -> Result<Stats, String> {
+        let stats =
+            storage::todo_stats(&self.client, &self.account).await.map_err(|e| e.to_string())?;
+        Ok(Stats {
+            total: stats.total,
+            completed: stats.completed,
+            pending: stats.pending,
+            high_priority: stats.high_priority,
+        })
+    }
+}
+
+fn chain_priority(priority: Priority) -> todo_subxt_client::Priority {
+    match priority {
+        Priority::Low => todo_subxt_client::Priority::Low,
+        Priority::Medium => todo_subxt_client::Priority::Medium,
+        Priority::High => todo_subxt_client::Priority::High,
+        Priority::Urgent => todo_subxt_client::Priority::Urgent,
+    }
+}
+
+/// Convert a todo of the chain, whose timestamps are in milliseconds.
+fn todo(todo: todo_subxt_client::Todo) -> Todo {
+    Todo {
+        id: todo.id,
+        title: String::from_utf8_lossy(&todo.title.0).into_owned(),
+        description: String::from_utf8_lossy(&todo.description.0).into_owned(),
+        status: match todo.status {
+            todo_subxt_client::Status::Backlog => Status::Backlog,
+            todo_subxt_client::Status::InProgress => Status::InProgress,
+            todo_subxt_client::Status::Blocked => Status::Blocked,
+            todo_subxt_client::Status::Done => Status::Done,
+            todo_subxt_client::Status::Cancelled => Status::Cancelled,
+        },
+        completed: todo.status == todo_subxt_client::Status::Done,
+        priority: match todo.priority {
+            todo_subxt_client::Priority::Low => Priority::Low,
+            todo_subxt_client::Priority::Medium => Priority::Medium,
+            todo_subxt_client::Priority::High => Priority::High,
+            todo_subxt_client::Priority::Urgent => Priority::Urgent,
+        },
+        created_at: (todo.created_at / 1000) as i64,
+        completed_at: (todo.status == todo_subxt_client::Status::Done)
+            .then_some(todo.entered_at.done)
+            .flatten()
+            .map(|at| (at / 1000) as i64),
+    }
+}
@@ -0,0 +1,320 @@
+use std::{cell::RefCell, fs};
+
+use clap::Parser;
+
+use crate::{
+    backend::{Backend, Priority, Stats, Status, Todo, TodoChanges},
+    cli::{Chain, Cli, Command},
+    command, keystore,
+};
+
+/// A todo list kept in memory, behaving like both chains do.
+#[derive(Default)]
+struct MemoryBackend {
+    todos: RefCell<Vec<Todo>>,
+    next_id: RefCell<u64>,
+}
+
+impl MemoryBackend {
+    fn with(todos: &[(&str, Priority, Status)]) -> Self {
+        let backend = MemoryBackend::default();
+        for (id, (title, priority, status)) in (1..).zip(todos) {
+            let completed = *status == Status::Done;
+            backend.todos.borrow_mut().push(Todo {
+                id,
+                title: title.to_string(),
+                description: String::new(),
+                status: *status,
+                completed,
+                priority: *priority,
+                created_at: 1_700_000_000,
+                completed_at: completed.then_some(1_700_000_100),
+            });
+        }
+        *backend.next_id.borrow_mut() = todos.len() as u64;
+        backend
+    }
+
+    fn todo(&self, id: u64) -> Option<Todo> {
+        self.todos.borrow().iter().find(|todo| todo.id == id).cloned()
+    }
+
+    fn update(&self, id: u64, f: impl FnOnce(&mut Todo)) -> Result<(), String> {
+        let mut todos = self.todos.borrow_mut();
+        let todo = todos.iter_mut().find(|todo| todo.id == id).ok_or("TodoNotFound")?;
+        f(todo);
+        Ok(())
+    }
+}
+
+impl Backend for MemoryBackend {
+    async fn list(&self) -> Result<Vec<Todo>, String> {
+        Ok(self.todos.borrow().clone())
+    }
+
+    async fn add(&self, title: &str, description: &str, priority: Priority) -> Result<u64, String> {
+        let mut next_id = self.next_id.borrow_mut();
+        *next_id += 1;
+        self.todos.borrow_mut().push(Todo {
+            id: *next_id,
+            title: title.into(),
+            description: description.into(),
+            status: Status::Backlog,
+            completed: false,
+            priority,
+            created_at: 1_700_000_000,
+            completed_at: None,
+        });
+        Ok(*next_id)
+    }
+
+    async fn edit(&self, id: u64, changes: &TodoChanges) -> Result<(), String> {
+        self.update(id, |todo| {
+            if let Some(title) = &changes.title {
+                todo.title = title.clone();
+            }
+            if let Some(description) = &changes.description {
+                todo.description = description.clone();
+            }
+            if let Some(priority) = changes.priority {
+                todo.priority = priority;
+            }
+        })
+    }
+
+    async fn complete(&self, id: u64) -> Result<(), String> {
+        let mut todos = self.todos.borrow_mut();
+        let todo = todos.iter_mut().find(|todo| todo.id == id).ok_or("TodoNotFound")?;
+        if !todo.status.can_complete() {
+            return Err("InvalidStatusTransition".into())
+        }
+        todo.status = Status::Done;
+        todo.completed = true;
+        todo.completed_at = Some(1_700_000_100);
+        Ok(())
+    }
+
+    async fn remove(&self, id: u64) -> Result<(), String> {
+        let mut todos = self.todos.borrow_mut();
+        let index = todos.iter().position(|todo| todo.id == id).ok_or("TodoNotFound")?;
+        todos.remove(index);
+        Ok(())
+    }
+}
+
+/// Parse `args` as the arguments of `todo` and run the command, returning its output.
+async fn run(backend: &MemoryBackend, args: &[&str]) -> Result<String, String> {
+    let cli = Cli::try_parse_from([&["todo"], args].concat()).map_err(|e| e.to_string())?;
+    let mut out = Vec::new();
+    command::run(backend, cli.command, cli.json, &mut out).await?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+fn json(output: &str) -> serde_json::Value {
+    serde_json::from_str(output).unwrap()
+}
+
+#[test]
+fn cli_defaults_to_the_substrate_chain() {
+    let cli = Cli::try_parse_from(["todo", "list"]).unwrap();
+    assert_eq!(cli.chain, Chain::Substrate);
+    assert!(matches!(cli.command, Command::List { pending: false }));
+
+    let cli = Cli::try_parse_from(["todo", "add", "Buy milk", "--chain", "solana"]).unwrap();
+    assert_eq!(cli.chain, Chain::Solana);
+    assert!(matches!(
+        cli.command,
+        Command::Add { ref title, ref description, priority: Priority::Medium }
+            if title == "Buy milk" && description.is_empty()
+    ));
+}
+
+#[test]
+fn suri_and_keystore_conflict() {
+    assert!(Cli::try_parse_from(["todo", "--suri", "//Alice", "--keystore", "/tmp", "list"])
+        .is_err());
+}
+
+#[tokio::test]
+async fn add_prints_the_new_id() {
+    let backend = MemoryBackend::default();
+
+    let output = run(&backend, &["add", "Buy milk", "-d", "Oat milk", "-p", "high"]).await;
+    assert_eq!(output.unwrap(), "Created todo 1\n");
+    let output = run(&backend, &["--json", "add", "Walk the dog"]).await.unwrap();
+    assert_eq!(json(&output), serde_json::json!({ "id": 2 }));
+
+    let todo = backend.todo(1).unwrap();
+    assert_eq!((todo.title.as_str(), todo.description.as_str()), ("Buy milk", "Oat milk"));
+    assert_eq!(todo.priority, Priority::High);
+    assert_eq!(backend.todo(2).unwrap().priority, Priority::Medium);
+}
+
+#[tokio::test]
+async fn list_prints_a_table() {
+    let backend = MemoryBackend::with(&[
+        ("Buy milk", Priority::High, Status::InProgress),
+        ("Write the quarterly report", Priority::Low, Status::Done),
+        ("Fix the roof", Priority::Medium, Status::Blocked),
+        ("Learn the banjo", Priority::Low, Status::Cancelled),
+    ]);
+
+    assert_eq!(
+        run(&backend, &["list"]).await.unwrap(),
+        "ID  STATUS       PRIORITY  TITLE\n\
+         1   in progress  high      Buy milk\n\
+         2   done         low       Write the quarterly report\n\
+         3   blocked      medium    Fix the roof\n\
+         4   cancelled    low       Learn the banjo\n"
+    );
+    assert_eq!(
+        run(&backend, &["list", "--pending"]).await.unwrap(),
+        "ID  STATUS       PRIORITY  TITLE\n\
+         1   in progress  high      Buy milk\n\
+         3   blocked      medium    Fix the roof\n"
+    );
+    assert_eq!(run(&MemoryBackend::default(), &["list"]).await.unwrap(), "No todos\n");
+}
+
+#[tokio::test]
+async fn list_prints_json() {
+    let backend = MemoryBackend::with(&[("Buy milk", Priority::High, Status::Done)]);
+
+    let output = run(&backend, &["list", "--json"]).await.unwrap();
+    assert_eq!(
+        json(&output),
+        serde_json::json!([{
+            "id": 1,
+            "title": "Buy milk",
+            "description": "",
+            "status": "done",
+            "completed": true,
+            "priority": "high",
+            "created_at": 1_700_000_000,
+            "completed_at": 1_700_000_100,
+        }])
+    );
+}
+
+#[tokio::test]
+async fn done_completes_a_todo_once() {
+    let backend = MemoryBackend::with(&[("Buy milk", Priority::Medium, Status::InProgress)]);
+
+    assert_eq!(run(&backend, &["done", "1"]).await.unwrap(), "Completed todo 1\n");
+    assert_eq!(backend.todo(1).unwrap().status, Status::Done);
+
+    // Done todos stay done
+    assert_eq!(run(&backend, &["done", "1"]).await.unwrap(), "Todo 1 was already done\n");
+    assert_eq!(backend.todo(1).unwrap().status, Status::Done);
+
+    let output = run(&backend, &["done", "1", "--json"]).await.unwrap();
+    assert_eq!(json(&output), serde_json::json!({ "id": 1, "completed": true }));
+
+    assert_eq!(run(&backend, &["done", "7"]).await, Err("todo 7 not found".into()));
+}
+
+#[tokio::test]
+async fn done_reports_todos_that_cannot_be_completed() {
+    let backend = MemoryBackend::with(&[
+        ("Fix the roof", Priority::Medium, Status::Blocked),
+        ("Learn the banjo", Priority::Low, Status::Cancelled),
+    ]);
+
+    assert_eq!(
+        run(&backend, &["done", "1"]).await,
+        Err("todo 1 is blocked and cannot be marked done".into())
+    );
+    assert_eq!(
+        run(&backend, &["done", "2", "--json"]).await,
+        Err("todo 2 is cancelled and cannot be marked done".into())
+    );
+    assert_eq!(backend.todo(1).unwrap().status, Status::Blocked);
+    assert_eq!(backend.todo(2).unwrap().status, Status::Cancelled);
+}
+
+#[tokio::test]
+async fn edit_changes_only_the_given_fields() {
+    let backend = MemoryBackend::with(&[("Buy milk", Priority::Medium, Status::Backlog)]);
+
+    let output = run(&backend, &["edit", "1", "--title", "Buy oat milk", "-p", "low"]).await;
+    assert_eq!(output.unwrap(), "Updated todo 1\n");
+    let todo = backend.todo(1).unwrap();
+    assert_eq!((todo.title.as_str(), todo.priority), ("Buy oat milk", Priority::Low));
+    assert_eq!(todo.description, "");
+
+    assert_eq!(
+        run(&backend, &["edit", "1"]).await,
+        Err("nothing to change, pass --title, --description or --priority".into())
+    );
+}
+
+#[tokio::test]
+async fn rm_deletes_a_todo() {
+    let backend = MemoryBackend::with(&[
+        ("Buy milk", Priority::Medium, Status::Backlog),
+        ("Walk the dog", Priority::Medium, Status::Backlog),
+    ]);
+
+    assert_eq!(run(&backend, &["rm", "1"]).await.unwrap(), "Deleted todo 1\n");
+    assert_eq!(backend.todo(1), None);
+    assert!(backend.todo(2).is_some());
+
+    // Errors of the chain are passed through
+    assert_eq!(run(&backend, &["rm", "1"]).await, Err("TodoNotFound".into()));
+}
+
+#[tokio::test]
+async fn stats_count_the_todos() {
+    let backend = MemoryBackend::with(&[
+        ("Buy milk", Priority::High, Status::Backlog),
+        ("Walk the dog", Priority::High, Status::Done),
+        ("Water the plants", Priority::Low, Status::Backlog),
+    ]);
+
+    assert_eq!(
+        run(&backend, &["stats"]).await.unwrap(),
+        "STAT           COUNT\n\
+         Total          3\n\
+         Completed      1\n\
+         Pending        2\n\
+         High priority  1\n"
+    );
+    let output = run(&backend, &["stats", "--json"]).await.unwrap();
+    assert_eq!(
+        json(&output),
+        serde_json::json!({ "total": 3, "completed": 1, "pending": 2, "high_priority": 1 })
+    );
+    assert_eq!(
+        backend.stats().await.unwrap(),
+        Stats { total: 3, completed: 1, pending: 2, high_priority: 1 }
+    );
+}
+
+#[test]
+fn keystore_keys_are_found_by_public_key() {
+    let dir = std::env::temp_dir().join(format!("todo-cli-keystore-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let alice = keystore::sr25519_from_suri("//Alice").unwrap();
+    let alice_public = hex::encode(alice.public_key().0);
+    let key_file = |public: &str| dir.join(format!("{}{public}", hex::encode(b"acco")));
+    fs::write(key_file(&alice_public), "\"//Alice\"").unwrap();
+    // Keys of other types are ignored
+    fs::write(dir.join(format!("{}{alice_public}", hex::encode(b"aura"))), "\"//Bob\"").unwrap();
+
+    let key = keystore::sr25519_from_keystore(&dir, None).unwrap();
+    assert_eq!(key.public_key().0, alice.public_key().0);
+
+    let bob = keystore::sr25519_from_suri("//Bob").unwrap();
+    let bob_public = hex::encode(bob.public_key().0);
+    fs::write(key_file(&bob_public), "\"//Bob\"").unwrap();
+
+    assert_eq!(
+        keystore::sr25519_from_keystore(&dir, None).err(),
+        Some("the keystore holds several account keys, pick one with --key".into())
+    );
+    let key = keystore::sr25519_from_keystore(&dir, Some(&format!("0x{bob_public}"))).unwrap();
+    assert_eq!(key.public_key().0, bob.public_key().0);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
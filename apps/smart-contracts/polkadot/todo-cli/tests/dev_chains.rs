@@ -0,0 +1,89 @@
+//! Runs the `todo` binary against local development chains.
+//!
+//! These tests need running chains, so they are ignored by default:
+//!
+//! ```bash
+//! ./target/release/todo-node --dev --tmp &
+//! cargo test -p todo-cli --test dev_chains substrate -- --ignored
+//!
+//! (cd ../solana && anchor localnet) &
+//! cargo test -p todo-cli --test dev_chains solana -- --ignored
+//! ```
+//!
+//! `TODO_URL` points the tests at other nodes; the Solana test signs with `TODO_KEYPAIR` or
+//! the Solana CLI's default keypair.
+
+use std::process::Command;
+
+use serde_json::Value;
+
+/// Run `todo` with `args` on `chain`, returning its JSON output.
+fn todo(chain: &str, args: &[&str]) -> Value {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_todo"));
+    command.args(["--chain", chain, "--json"]).args(args);
+    if chain == "substrate" {
+        command.args(["--suri", "//Alice"]);
+    }
+
+    let output = command.output().expect("failed to run todo");
+    assert!(
+        output.status.success(),
+        "todo {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("todo printed malformed JSON")
+}
+
+fn find(todos: &Value, id: &Value) -> Option<Value> {
+    todos.as_array().unwrap().iter().find(|todo| &todo["id"] == id).cloned()
+}
+
+/// Walk a todo through every command.
+fn manage_a_todo(chain: &str) {
+    let before = todo(chain, &["stats"]);
+
+    let id = todo(chain, &["add", "Buy milk", "-d", "Oat milk", "-p", "high"])["id"].clone();
+    let created = find(&todo(chain, &["list", "--pending"]), &id).expect("new todo is listed");
+    assert_eq!(created["title"], "Buy milk");
+    assert_eq!(created["description"], "Oat milk");
+    assert_eq!(created["priority"], "high");
+    assert_eq!(created["status"], "backlog");
+    assert_eq!(created["completed"], false);
+
+    let id_arg = id.to_string();
+    todo(chain, &["edit", &id_arg, "--title", "Buy oat milk", "-p", "low"]);
+    let edited = find(&todo(chain, &["list"]), &id).unwrap();
+    assert_eq!(edited["title"], "Buy oat milk");
+    assert_eq!(edited["description"], "Oat milk");
+    assert_eq!(edited["priority"], "low");
+
+    todo(chain, &["done", &id_arg]);
+    // Completing twice keeps the todo completed
+    todo(chain, &["done", &id_arg]);
+    let done = find(&todo(chain, &["list"]), &id).unwrap();
+    assert_eq!(done["status"], "done");
+    assert_eq!(done["completed"], true);
+    assert!(done["completed_at"].is_i64());
+    assert_eq!(find(&todo(chain, &["list", "--pending"]), &id), None);
+
+    let after = todo(chain, &["stats"]);
+    assert_eq!(after["total"], before["total"].as_u64().unwrap() + 1);
+    assert_eq!(after["completed"], before["completed"].as_u64().unwrap() + 1);
+
+    todo(chain, &["rm", &id_arg]);
+    assert_eq!(find(&todo(chain, &["list"]), &id), None);
+    assert_eq!(todo(chain, &["stats"])["total"], before["total"]);
+}
+
+#[test]
+#[ignore = "needs a running `todo-node --dev`"]
+fn substrate_dev_node() {
+    manage_a_todo("substrate");
+}
+
+#[test]
+#[ignore = "needs a running `solana-test-validator` with the todo program deployed"]
+fn solana_test_validator() {
+    manage_a_todo("solana");
+}
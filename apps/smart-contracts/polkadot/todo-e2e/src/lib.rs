@@ -0,0 +1,203 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! End-to-end encryption of todo payloads for `pallet-todo`.
+//!
+//! The pallet stores encrypted todos as an `EncryptedPayload`: a ciphertext, a nonce, the
+//! sender's public key and the content key wrapped for every recipient. This crate produces
+//! and opens such payloads; the chain never sees the plaintext.
+//!
+//! # Scheme
+//!
+//! - Every account derives an x25519 keypair from its sr25519 secret key with
+//!   [`EncryptionKeypair::from_sr25519_secret`], so no extra key has to be backed up.
+//! - A fresh 32-byte content key encrypts the plaintext with XChaCha20-Poly1305 under a
+//!   random 24-byte nonce.
+//! - For each recipient, the x25519 shared secret between sender and recipient is hashed
+//!   into a key-encryption key, which encrypts the content key under the same nonce.
+//!
+//! Senders who want to read their own todos again must list their own public key as a
+//! recipient.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use rand_core::{CryptoRng, RngCore};
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+
+/// Length of an x25519 public key.
+pub const PUBLIC_KEY_LEN: usize = 32;
+
+/// Length of an XChaCha20-Poly1305 nonce.
+pub const NONCE_LEN: usize = 24;
+
+/// Length of a wrapped content key: a 32-byte key and a 16-byte authentication tag.
+pub const WRAPPED_KEY_LEN: usize = 48;
+
+const KEYPAIR_CONTEXT: &[u8] = b"todo-e2e/x25519-from-sr25519";
+const KEK_CONTEXT: &[u8] = b"todo-e2e/key-encryption-key";
+
+type Blake2b256 = Blake2b<U32>;
+
+/// Errors returned while encrypting or decrypting a todo.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// Encryption failed
+    Encryption,
+    /// The keypair is not among the recipients of the payload
+    NotARecipient,
+    /// The payload or one of its keys failed authentication
+    Decryption,
+    /// A public key is a low-order point, so no secret can be agreed with it
+    WeakPublicKey,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Error::Encryption => write!(f, "encryption failed"),
+            Error::NotARecipient => write!(f, "not a recipient of this todo"),
+            Error::Decryption => write!(f, "the todo payload failed authentication"),
+            Error::WeakPublicKey => write!(f, "weak x25519 public key"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// An x25519 keypair used to encrypt and decrypt todos.
+pub struct EncryptionKeypair {
+    secret: StaticSecret,
+    public: PublicKey,
+}
+
+impl EncryptionKeypair {
+    /// Derive the keypair of an account from its sr25519 secret key.
+    ///
+    /// `secret` is the 64-byte schnorrkel secret key (`SecretKey::to_bytes`), as found in
+    /// `sp_core::sr25519::Pair`. The same secret always yields the same keypair.
+    pub fn from_sr25519_secret(secret: &[u8]) -> Self {
+        let mut hasher = Blake2b256::new();
+        hasher.update(KEYPAIR_CONTEXT);
+        hasher.update(secret);
+        let seed = Zeroizing::new(<[u8; 32]>::from(hasher.finalize()));
+
+        let secret = StaticSecret::from(*seed);
+        let public = PublicKey::from(&secret);
+        Self { secret, public }
+    }
+
+    /// The public key other accounts encrypt todos for.
+    pub fn public_key(&self) -> [u8; PUBLIC_KEY_LEN] {
+        self.public.to_bytes()
+    }
+
+    /// The key-encryption key shared between this keypair and `other`.
+    fn key_encryption_key(
+        &self,
+        sender: &[u8; PUBLIC_KEY_LEN],
+        recipient: &[u8; PUBLIC_KEY_LEN],
+        other: &[u8; PUBLIC_KEY_LEN],
+    ) -> Result<Zeroizing<[u8; 32]>, Error> {
+        let shared = self.secret.diffie_hellman(&PublicKey::from(*other));
+        if !shared.was_contributory() {
+            return Err(Error::WeakPublicKey)
+        }
+
+        let mut hasher = Blake2b256::new();
+        hasher.update(KEK_CONTEXT);
+        hasher.update(shared.as_bytes());
+        hasher.update(sender);
+        hasher.update(recipient);
+        Ok(Zeroizing::new(hasher.finalize().into()))
+    }
+}
+
+/// The content key of a todo, wrapped for one recipient.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WrappedKey {
+    /// The recipient's x25519 public key
+    pub recipient: [u8; PUBLIC_KEY_LEN],
+    /// The content key, encrypted for the recipient
+    pub wrapped_key: [u8; WRAPPED_KEY_LEN],
+}
+
+/// An encrypted todo, matching the arguments of `pallet_todo::Call::create_encrypted_todo`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptedTodo {
+    /// The sender's x25519 public key
+    pub sender: [u8; PUBLIC_KEY_LEN],
+    /// XChaCha20-Poly1305 nonce
+    pub nonce: [u8; NONCE_LEN],
+    /// The encrypted plaintext
+    pub ciphertext: Vec<u8>,
+    /// The content key, wrapped for each recipient
+    pub recipients: Vec<WrappedKey>,
+}
+
+/// Encrypt `plaintext` from `sender` for the given recipient public keys.
+pub fn encrypt<R: RngCore + CryptoRng>(
+    sender: &EncryptionKeypair,
+    recipients: &[[u8; PUBLIC_KEY_LEN]],
+    plaintext: &[u8],
+    rng: &mut R,
+) -> Result<EncryptedTodo, Error> {
+    let mut content_key = Zeroizing::new([0u8; 32]);
+    rng.fill_bytes(content_key.as_mut());
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut nonce);
+
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(content_key.as_ref()))
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|_| Error::Encryption)?;
+
+    let sender_key = sender.public_key();
+    let recipients = recipients
+        .iter()
+        .map(|recipient| {
+            let kek = sender.key_encryption_key(&sender_key, recipient, recipient)?;
+            let wrapped = XChaCha20Poly1305::new(Key::from_slice(kek.as_ref()))
+                .encrypt(XNonce::from_slice(&nonce), content_key.as_ref())
+                .map_err(|_| Error::Encryption)?;
+
+            let wrapped_key = wrapped.try_into().map_err(|_| Error::Encryption)?;
+            Ok(WrappedKey { recipient: *recipient, wrapped_key })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(EncryptedTodo { sender: sender_key, nonce, ciphertext, recipients })
+}
+
+/// Decrypt a todo with the keypair of one of its recipients.
+pub fn decrypt(recipient: &EncryptionKeypair, todo: &EncryptedTodo) -> Result<Vec<u8>, Error> {
+    let own_key = recipient.public_key();
+    let wrapped = todo
+        .recipients
+        .iter()
+        .find(|wrapped| wrapped.recipient == own_key)
+        .ok_or(Error::NotARecipient)?;
+
+    let kek = recipient.key_encryption_key(&todo.sender, &own_key, &todo.sender)?;
+    let content_key = Zeroizing::new(
+        XChaCha20Poly1305::new(Key::from_slice(kek.as_ref()))
+            .decrypt(XNonce::from_slice(&todo.nonce), wrapped.wrapped_key.as_ref())
+            .map_err(|_| Error::Decryption)?,
+    );
+    if content_key.len() != 32 {
+        return Err(Error::Decryption)
+    }
+
+    XChaCha20Poly1305::new(Key::from_slice(&content_key))
+        .decrypt(XNonce::from_slice(&todo.nonce), todo.ciphertext.as_ref())
+        .map_err(|_| Error::Decryption)
+}
+
+#[cfg(test)]
+mod tests;
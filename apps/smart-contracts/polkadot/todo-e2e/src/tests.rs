@@ -0,0 +1,50 @@
+use crate::*;
+use rand_core::OsRng;
+
+fn keypair(seed: u8) -> EncryptionKeypair {
+    EncryptionKeypair::from_sr25519_secret(&[seed; 64])
+}
+
+#[test]
+fn keypairs_are_derived_deterministically() {
+    assert_eq!(keypair(1).public_key(), keypair(1).public_key());
+    assert_ne!(keypair(1).public_key(), keypair(2).public_key());
+}
+
+#[test]
+fn every_recipient_can_decrypt() {
+    let alice = keypair(1);
+    let bob = keypair(2);
+    let plaintext = b"Buy groceries: milk, eggs, bread";
+
+    let todo = encrypt(&alice, &[alice.public_key(), bob.public_key()], plaintext, &mut OsRng)
+        .unwrap();
+
+    assert_ne!(todo.ciphertext.as_slice(), plaintext.as_slice());
+    assert_eq!(decrypt(&alice, &todo).unwrap(), plaintext);
+    assert_eq!(decrypt(&bob, &todo).unwrap(), plaintext);
+}
+
+#[test]
+fn others_cannot_decrypt() {
+    let alice = keypair(1);
+    let bob = keypair(2);
+    let eve = keypair(3);
+
+    let mut todo = encrypt(&alice, &[bob.public_key()], b"Secret", &mut OsRng).unwrap();
+    assert_eq!(decrypt(&eve, &todo), Err(Error::NotARecipient));
+
+    // Claiming a recipient's slot does not help without the recipient's secret
+    todo.recipients[0].recipient = eve.public_key();
+    assert_eq!(decrypt(&eve, &todo), Err(Error::Decryption));
+}
+
+#[test]
+fn tampered_payloads_are_rejected() {
+    let alice = keypair(1);
+    let bob = keypair(2);
+
+    let mut todo = encrypt(&alice, &[bob.public_key()], b"Secret", &mut OsRng).unwrap();
+    todo.ciphertext[0] ^= 1;
+    assert_eq!(decrypt(&bob, &todo), Err(Error::Decryption));
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+
+/// Errors returned while indexing.
+#[derive(Debug)]
+pub enum Error {
+    /// The database failed
+    Database(sqlx::Error),
+    /// The Substrate node failed
+    Substrate(Box<subxt::Error>),
+    /// The Solana RPC node failed
+    Solana(Box<solana_client::client_error::ClientError>),
+    /// A recorded block or a stored event could not be decoded
+    Decode(String),
+    /// A block needed to resolve a reorg is not available
+    UnknownBlock(String),
+    /// Reading or writing a recording failed
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Database(e) => write!(f, "database error: {e}"),
+            Error::Substrate(e) => write!(f, "substrate node error: {e}"),
+            Error::Solana(e) => write!(f, "solana node error: {e}"),
+            Error::Decode(e) => write!(f, "malformed data: {e}"),
+            Error::UnknownBlock(hash) => write!(f, "block {hash} is not available"),
+            Error::Io(e) => write!(f, "i/o error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<sqlx::Error> for Error {
+    fn from(e: sqlx::Error) -> Self {
+        Error::Database(e)
+    }
+}
+
+impl From<subxt::Error> for Error {
+    fn from(e: subxt::Error) -> Self {
+        Error::Substrate(Box::new(e))
+    }
+}
+
+impl From<solana_client::client_error::ClientError> for Error {
+    fn from(e: solana_client::client_error::ClientError) -> Self {
+        Error::Solana(Box::new(e))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
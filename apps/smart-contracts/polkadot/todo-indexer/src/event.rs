@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// Chains whose todos can be indexed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum Chain {
+    /// `pallet_todo` on a Substrate node
+    Substrate,
+    /// The Anchor `todo_program` on a Solana cluster
+    Solana,
+}
+
+impl Chain {
+    /// Name of the chain in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Chain::Substrate => "substrate",
+            Chain::Solana => "solana",
+        }
+    }
+}
+
+/// A change to the todos of an owner, as reported by either chain.
+///
+/// Owners are SS58 addresses on Substrate and base58 public keys on Solana.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TodoEvent {
+    /// A todo was created
+    Created { owner: String, id: u64 },
+    /// The title, description or priority of a todo changed
+    Updated { owner: String, id: u64 },
+    /// A todo was completed or reopened
+    CompletionToggled { owner: String, id: u64, completed: bool },
+    /// The due date of a todo, in milliseconds since the Unix epoch, was set or cleared
+    DueDateSet { owner: String, id: u64, due: Option<u64> },
+    /// A todo was deleted, but can still be restored
+    Deleted { owner: String, id: u64 },
+    /// A deleted todo was restored, with the due date and completion it had
+    Restored { owner: String, id: u64 },
+    /// A todo was removed for good
+    Purged { owner: String, id: u64 },
+    /// A todo of `from` moved to `owner` under a new ID.
+    ///
+    /// Chains only report the IDs. The store fills in the due date and completion block of
+    /// the old todo when it indexes the event, so the new owner's todos can be rebuilt without
+    /// the old owner's.
+    Transferred {
+        owner: String,
+        id: u64,
+        from: String,
+        old_id: u64,
+        #[serde(default)]
+        due: Option<u64>,
+        #[serde(default)]
+        completed_block: Option<u64>,
+    },
+    /// All todos of an owner were removed
+    AccountCleared { owner: String },
+    /// The live todos of an owner moved to another owner, after their `Transferred` events.
+    /// Deleted todos stay with the owner.
+    ListTransferred { owner: String },
+}
+
+impl TodoEvent {
+    /// Owner of the todos the event changes.
+    pub fn owner(&self) -> &str {
+        match self {
+            TodoEvent::Created { owner, .. } |
+            TodoEvent::Updated { owner, .. } |
+            TodoEvent::CompletionToggled { owner, .. } |
+            TodoEvent::DueDateSet { owner, .. } |
+            TodoEvent::Deleted { owner, .. } |
+            TodoEvent::Restored { owner, .. } |
+            TodoEvent::Purged { owner, .. } |
+            TodoEvent::Transferred { owner, .. } |
+            TodoEvent::AccountCleared { owner } |
+            TodoEvent::ListTransferred { owner } => owner,
+        }
+    }
+}
+
+/// The todo events of a block, in the order the chain emitted them.
+///
+/// Numbers are block numbers on Substrate and slots on Solana, where the parent of a block
+/// need not be in the previous slot.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Block {
+    pub number: u64,
+    pub hash: String,
+    pub parent_number: u64,
+    pub parent_hash: String,
+    pub events: Vec<TodoEvent>,
+}
+
+/// Number and hash of an indexed block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockRef {
+    pub number: u64,
+    pub hash: String,
+}
@@ -0,0 +1,119 @@
+//! Event-sourced index of todos, kept in SQLite or Postgres.
+//!
+//! The indexer follows the finalized blocks of a chain and stores every todo event it finds,
+//! then projects the events into one row per live todo:
+//!
+//! - [`substrate`] reads `pallet_todo` events through `todo-subxt-client`.
+//! - [`solana`] reads the logs the Anchor `todo_program` writes for each instruction.
+//!
+//! Both turn a block into a chain-agnostic [`Block`] of [`TodoEvent`]s, which [`Indexer`]
+//! applies to the [`Store`].
+//!
+//! # Reorgs
+//!
+//! The store keeps the hash of every indexed block. A block whose parent is not the indexed
+//! block at the parent's height means the chain reorganized: the indexer fetches ancestors
+//! until it reaches an indexed one, reverts every indexed block above it and applies the new
+//! branch. Reverting drops the events of the reverted blocks and rebuilds the todos of the
+//! affected owners from the events that remain.
+//!
+//! # Recording
+//!
+//! Followers can write the raw blocks they index to a JSON lines file, one
+//! [`substrate::RecordedBlock`] or [`solana::RecordedBlock`] per line. [`replay`] indexes such
+//! a file again without a chain, which is how the tests exercise the indexer.
+
+use std::{collections::HashMap, future::Future};
+
+mod error;
+mod event;
+pub mod record;
+pub mod solana;
+mod store;
+pub mod substrate;
+
+pub use error::Error;
+pub use event::{Block, BlockRef, Chain, TodoEvent};
+pub use store::{IndexedTodo, Store};
+
+/// Applies the blocks of one chain to a store.
+pub struct Indexer {
+    store: Store,
+    chain: Chain,
+}
+
+impl Indexer {
+    pub fn new(store: Store, chain: Chain) -> Self {
+        Indexer { store, chain }
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    pub fn chain(&self) -> Chain {
+        self.chain
+    }
+
+    /// The indexed block with the highest number.
+    pub async fn head(&self) -> Result<Option<BlockRef>, Error> {
+        self.store.head(self.chain).await
+    }
+
+    /// Index `block`, after the ancestors that replace indexed blocks of another branch.
+    ///
+    /// `fetch` returns the block with the given hash; it is only called on reorgs. Blocks that
+    /// are already indexed are skipped.
+    pub async fn ingest<F, Fut>(&self, block: Block, mut fetch: F) -> Result<(), Error>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<Block, Error>>,
+    {
+        if self.is_indexed(&block).await? {
+            return Ok(())
+        }
+
+        // Walk back until the oldest pending block extends an indexed block, or a block that
+        // was never indexed
+        let mut branch = vec![block];
+        loop {
+            let oldest = branch.last().expect("the branch starts with a block; qed");
+            if oldest.number == 0 {
+                break
+            }
+            match self.store.block_hash(self.chain, oldest.parent_number).await? {
+                Some(hash) if hash != oldest.parent_hash => {
+                    let parent = fetch(oldest.parent_hash.clone()).await?;
+                    branch.push(parent);
+                },
+                _ => break,
+            }
+        }
+
+        for block in branch.into_iter().rev() {
+            self.store.apply(self.chain, &block).await?;
+        }
+        Ok(())
+    }
+
+    async fn is_indexed(&self, block: &Block) -> Result<bool, Error> {
+        let hash = self.store.block_hash(self.chain, block.number).await?;
+        Ok(hash.as_deref() == Some(block.hash.as_str()))
+    }
+}
+
+/// Index recorded `blocks` in order, looking up the ancestors needed on reorgs among them.
+pub async fn replay(indexer: &Indexer, blocks: Vec<Block>) -> Result<(), Error> {
+    let by_hash =
+        blocks.iter().map(|block| (block.hash.clone(), block.clone())).collect::<HashMap<_, _>>();
+
+    for block in blocks {
+        indexer
+            .ingest(block, |hash| {
+                let parent = by_hash.get(&hash).cloned().ok_or(Error::UnknownBlock(hash));
+                async move { parent }
+            })
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Indexes the todos of the todo chain or of the Solana todo program.
+//!
+//! ```bash
+//! todo-indexer follow --chain substrate --record blocks.jsonl
+//! todo-indexer --database-url postgres://localhost/todos follow --chain solana
+//! todo-indexer replay --chain substrate blocks.jsonl
+//! ```
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufReader, Write},
+    path::PathBuf,
+};
+
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use todo_indexer::{record, solana, substrate, Block, Chain, Indexer, Store};
+use todo_subxt_client::TodoClient;
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Database to index into, SQLite or Postgres
+    #[arg(long, env = "DATABASE_URL", default_value = "sqlite://todo-indexer.db?mode=rwc")]
+    database_url: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Index the finalized blocks of a chain as they come in
+    Follow {
+        /// Chain to index
+        #[arg(long, value_enum)]
+        chain: Chain,
+
+        /// Node URL; defaults to a local development node of the chain
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Address of the Solana todo program, if it is not deployed at its declared ID
+        #[arg(long)]
+        program_id: Option<String>,
+
+        /// Append every indexed block to this file, for `replay`
+        #[arg(long)]
+        record: Option<PathBuf>,
+    },
+
+    /// Index the blocks of a recording
+    Replay {
+        /// Chain the blocks were recorded from
+        #[arg(long, value_enum)]
+        chain: Chain,
+
+        /// Address of the Solana todo program the blocks were recorded for
+        #[arg(long)]
+        program_id: Option<String>,
+
+        /// Recording written by `follow --record`
+        recording: PathBuf,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+    let cli = Cli::parse();
+    let store = Store::connect(&cli.database_url).await.map_err(|e| e.to_string())?;
+
+    match cli.command {
+        Command::Follow { chain, url, program_id, record } => {
+            let mut recording = match record {
+                Some(path) => Some(
+                    OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(&path)
+                        .map_err(|e| format!("failed to open {}: {e}", path.display()))?,
+                ),
+                None => None,
+            };
+            let recording = recording.as_mut().map(|file| file as &mut dyn Write);
+            let indexer = Indexer::new(store, chain);
+
+            match chain {
+                Chain::Substrate => {
+                    let url = url.as_deref().unwrap_or(substrate::DEFAULT_URL);
+                    let client = TodoClient::from_url(url)
+                        .await
+                        .map_err(|e| format!("failed to connect to {url}: {e}"))?;
+                    substrate::follow(&indexer, &client, recording).await
+                },
+                Chain::Solana => {
+                    let rpc = RpcClient::new(url.unwrap_or_else(|| solana::DEFAULT_URL.into()));
+                    let program_id = program_id.unwrap_or_else(|| todo_program::ID.to_string());
+                    solana::follow(&indexer, &rpc, &program_id, recording).await
+                },
+            }
+            .map_err(|e| e.to_string())
+        },
+        Command::Replay { chain, program_id, recording } => {
+            let file = File::open(&recording)
+                .map_err(|e| format!("failed to open {}: {e}", recording.display()))?;
+            let reader = BufReader::new(file);
+            let blocks: Vec<Block> = match chain {
+                Chain::Substrate => record::read::<substrate::RecordedBlock>(reader)
+                    .and_then(|blocks| blocks.iter().map(|block| block.decode()).collect()),
+                Chain::Solana => {
+                    let program_id = program_id.unwrap_or_else(|| todo_program::ID.to_string());
+                    record::read::<solana::RecordedBlock>(reader).map(|blocks| {
+                        blocks.iter().map(|block| block.decode(&program_id)).collect()
+                    })
+                },
+            }
+            .map_err(|e| e.to_string())?;
+
+            let count = blocks.len();
+            todo_indexer::replay(&Indexer::new(store, chain), blocks)
+                .await
+                .map_err(|e| e.to_string())?;
+            println!("Replayed {count} blocks");
+            Ok(())
+        },
+    }
+}
@@ -0,0 +1,30 @@
+//! Recordings of raw blocks, one JSON document per line.
+
+use std::io::{BufRead, Write};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::Error;
+
+/// Append `block` to a recording.
+pub fn write(out: &mut impl Write, block: &impl Serialize) -> Result<(), Error> {
+    let line = serde_json::to_string(block).map_err(|e| Error::Decode(e.to_string()))?;
+    writeln!(out, "{line}")?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Read every block of a recording, skipping blank lines.
+pub fn read<T: DeserializeOwned>(recording: impl BufRead) -> Result<Vec<T>, Error> {
+    let mut blocks = Vec::new();
+    for (number, line) in recording.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue
+        }
+        let block = serde_json::from_str(&line)
+            .map_err(|e| Error::Decode(format!("line {}: {e}", number + 1)))?;
+        blocks.push(block);
+    }
+    Ok(blocks)
+}
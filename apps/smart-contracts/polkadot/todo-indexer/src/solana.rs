@@ -0,0 +1,205 @@
+//! Todo events of the Anchor `todo_program`, read from its logs.
+//!
+//! The program reports each change with `msg!`, such as `Todo 3 marked as completed`, so the
+//! indexer reads the log lines the program wrote while it was the innermost invoked program.
+//! The owner of the changed todos is the `owner` account of the instruction.
+
+use std::{io::Write, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcBlockConfig};
+use solana_commitment_config::CommitmentConfig;
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, EncodedTransaction, EncodedTransactionWithStatusMeta,
+    TransactionDetails, UiConfirmedBlock, UiMessage, UiTransactionEncoding,
+};
+
+use crate::{record, Block, Error, Indexer, TodoEvent};
+
+/// URL of a local `solana-test-validator`.
+pub const DEFAULT_URL: &str = "http://127.0.0.1:8899";
+
+/// Position of the `owner` account in every instruction of the program.
+const OWNER_ACCOUNT: usize = 1;
+
+/// How long to wait for new finalized slots.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A block as recorded from a cluster, with only the transactions that invoked the program.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedBlock {
+    pub slot: u64,
+    pub blockhash: String,
+    pub parent_slot: u64,
+    pub previous_blockhash: String,
+    pub transactions: Vec<RecordedTransaction>,
+}
+
+/// A transaction that invoked the program.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedTransaction {
+    pub signature: String,
+    /// The `owner` account of the first instruction of the program
+    pub owner: String,
+    /// Whether the transaction failed, leaving the todos as they were
+    #[serde(default)]
+    pub failed: bool,
+    pub logs: Vec<String>,
+}
+
+impl RecordedBlock {
+    /// Keep the transactions of `block` that invoked `program_id`.
+    pub fn new(slot: u64, block: UiConfirmedBlock, program_id: &str) -> Self {
+        let transactions = block
+            .transactions
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|transaction| recorded_transaction(transaction, program_id))
+            .collect();
+
+        RecordedBlock {
+            slot,
+            blockhash: block.blockhash,
+            parent_slot: block.parent_slot,
+            previous_blockhash: block.previous_blockhash,
+            transactions,
+        }
+    }
+
+    /// The todo events the program logged in this block.
+    pub fn decode(&self, program_id: &str) -> Block {
+        let events = self
+            .transactions
+            .iter()
+            .filter(|transaction| !transaction.failed)
+            .flat_map(|transaction| parse_logs(program_id, &transaction.owner, &transaction.logs))
+            .collect();
+
+        Block {
+            number: self.slot,
+            hash: self.blockhash.clone(),
+            parent_number: self.parent_slot,
+            parent_hash: self.previous_blockhash.clone(),
+            events,
+        }
+    }
+}
+
+fn recorded_transaction(
+    transaction: EncodedTransactionWithStatusMeta,
+    program_id: &str,
+) -> Option<RecordedTransaction> {
+    let EncodedTransaction::Json(ui_transaction) = transaction.transaction else { return None };
+    let UiMessage::Raw(message) = ui_transaction.message else { return None };
+
+    let owner = message.instructions.iter().find_map(|instruction| {
+        let program = message.account_keys.get(instruction.program_id_index as usize)?;
+        let owner = instruction.accounts.get(OWNER_ACCOUNT)?;
+        (program == program_id).then(|| message.account_keys.get(*owner as usize)).flatten()
+    })?;
+    let meta = transaction.meta?;
+    let logs = match meta.log_messages {
+        OptionSerializer::Some(logs) => logs,
+        _ => Vec::new(),
+    };
+
+    Some(RecordedTransaction {
+        signature: ui_transaction.signatures.first().cloned().unwrap_or_default(),
+        owner: owner.clone(),
+        failed: meta.err.is_some(),
+        logs,
+    })
+}
+
+/// The todo events in the logs of a transaction of `owner`.
+pub fn parse_logs(program_id: &str, owner: &str, logs: &[String]) -> Vec<TodoEvent> {
+    let mut invoked = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        if let Some(message) = log.strip_prefix("Program log: ") {
+            if invoked.last() == Some(&program_id) {
+                events.extend(parse_message(owner, message));
+            }
+            continue
+        }
+
+        let mut words = log.split_whitespace();
+        match (words.next(), words.next(), words.next()) {
+            (Some("Program"), Some(program), Some("invoke")) => invoked.push(program),
+            (Some("Program"), Some(_), Some("success" | "failed:")) => {
+                invoked.pop();
+            },
+            _ => {},
+        }
+    }
+    events
+}
+
+fn parse_message(owner: &str, message: &str) -> Option<TodoEvent> {
+    let owner = owner.to_string();
+    if let Some(id) = message.strip_prefix("Todo created with ID: ") {
+        return Some(TodoEvent::Created { owner, id: id.trim().parse().ok()? })
+    }
+
+    let rest = message.strip_prefix("Todo ")?;
+    let (id, action) = rest.split_once(' ')?;
+    let id = id.parse().ok()?;
+    let event = match action {
+        "updated" => TodoEvent::Updated { owner, id },
+        "marked as completed" => TodoEvent::CompletionToggled { owner, id, completed: true },
+        "marked as incomplete" => TodoEvent::CompletionToggled { owner, id, completed: false },
+        // The program closes the todo's account, so it cannot come back
+        "deleted" => TodoEvent::Purged { owner, id },
+        _ => return None,
+    };
+    Some(event)
+}
+
+/// Fetch the finalized block in `slot`.
+pub async fn fetch(rpc: &RpcClient, slot: u64, program_id: &str) -> Result<RecordedBlock, Error> {
+    let config = RpcBlockConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        transaction_details: Some(TransactionDetails::Full),
+        rewards: Some(false),
+        commitment: Some(CommitmentConfig::finalized()),
+        max_supported_transaction_version: Some(0),
+    };
+    let block = rpc.get_block_with_config(slot, config).await?;
+    Ok(RecordedBlock::new(slot, block, program_id))
+}
+
+/// Index the finalized blocks of the cluster, starting after the indexed head or at the
+/// current finalized slot, and append them to `recording` if given.
+pub async fn follow(
+    indexer: &Indexer,
+    rpc: &RpcClient,
+    program_id: &str,
+    mut recording: Option<&mut dyn Write>,
+) -> Result<(), Error> {
+    let finalized = CommitmentConfig::finalized();
+    let mut next = match indexer.head().await? {
+        Some(head) => head.number + 1,
+        None => rpc.get_slot_with_commitment(finalized).await?,
+    };
+
+    loop {
+        let tip = rpc.get_slot_with_commitment(finalized).await?;
+        if tip < next {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            continue
+        }
+
+        // Skipped slots have no block
+        for slot in rpc.get_blocks_with_commitment(next, Some(tip), finalized).await? {
+            let recorded = fetch(rpc, slot, program_id).await?;
+            if let Some(out) = recording.as_mut() {
+                record::write(out, &recorded)?;
+            }
+            // Finalized blocks are never replaced, so there is no fork to fetch
+            let block = recorded.decode(program_id);
+            indexer.ingest(block, |hash| async { Err(Error::UnknownBlock(hash)) }).await?;
+        }
+        next = tip + 1;
+    }
+}
@@ -0,0 +1,346 @@
+//! The relational schema and the projection of todo events into it.
+//!
+//! - `blocks` holds the number and hash of every indexed block.
+//! - `events` is the log of every indexed todo event, stored as JSON. It is the source of
+//!   truth: the other tables can be rebuilt from it.
+//! - `todos` holds one row per todo, with the blocks in which it changed. A todo is
+//!   completed while it has a `completed_block`. A transferred todo keeps the due date and
+//!   `completed_block` it had under its old owner. A deleted todo keeps its row, with a
+//!   `deleted_block`, until it is purged, so a restore brings it back as it was.
+//!
+//! Rows of both chains live in the same tables, told apart by their `chain` column. The SQL
+//! sticks to what SQLite and Postgres have in common, so one store runs on either.
+
+use sqlx::{
+    any::{install_default_drivers, AnyPoolOptions},
+    AnyConnection, AnyPool, Row,
+};
+
+use crate::{Block, BlockRef, Chain, Error, TodoEvent};
+
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS blocks (
+        chain TEXT NOT NULL,
+        number BIGINT NOT NULL,
+        hash TEXT NOT NULL,
+        parent_hash TEXT NOT NULL,
+        PRIMARY KEY (chain, number)
+    )",
+    "CREATE TABLE IF NOT EXISTS events (
+        chain TEXT NOT NULL,
+        block_number BIGINT NOT NULL,
+        event_index INTEGER NOT NULL,
+        owner TEXT NOT NULL,
+        event TEXT NOT NULL,
+        PRIMARY KEY (chain, block_number, event_index)
+    )",
+    "CREATE INDEX IF NOT EXISTS events_by_owner ON events (chain, owner)",
+    "CREATE TABLE IF NOT EXISTS todos (
+        chain TEXT NOT NULL,
+        owner TEXT NOT NULL,
+        id BIGINT NOT NULL,
+        due BIGINT,
+        created_block BIGINT NOT NULL,
+        updated_block BIGINT NOT NULL,
+        completed_block BIGINT,
+        deleted_block BIGINT,
+        PRIMARY KEY (chain, owner, id)
+    )",
+];
+
+/// A todo as projected from its events.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexedTodo {
+    pub id: u64,
+    pub completed: bool,
+    /// Due date in milliseconds since the Unix epoch
+    pub due: Option<u64>,
+    /// Block in which the todo was created
+    pub created_block: u64,
+    /// Block in which the todo last changed
+    pub updated_block: u64,
+    /// Block in which the todo was completed, if it is completed
+    pub completed_block: Option<u64>,
+}
+
+/// The indexed blocks, events and todos of every chain.
+#[derive(Clone)]
+pub struct Store {
+    pool: AnyPool,
+}
+
+impl Store {
+    /// Connect to the database at `url`, such as `sqlite://todos.db?mode=rwc` or
+    /// `postgres://localhost/todos`, and create the schema if it is missing.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        install_default_drivers();
+        // The indexer writes one block at a time, and a single connection also keeps every
+        // query on the same database for `sqlite::memory:`
+        let pool = AnyPoolOptions::new().max_connections(1).connect(url).await?;
+
+        for statement in SCHEMA {
+            sqlx::query(statement).execute(&pool).await?;
+        }
+        Ok(Store { pool })
+    }
+
+    /// The indexed block of `chain` with the highest number.
+    pub async fn head(&self, chain: Chain) -> Result<Option<BlockRef>, Error> {
+        let row = sqlx::query(
+            "SELECT number, hash FROM blocks WHERE chain = $1 ORDER BY number DESC LIMIT 1",
+        )
+        .bind(chain.as_str())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(BlockRef { number: row.try_get::<i64, _>(0)? as u64, hash: row.try_get(1)? })
+        })
+        .transpose()
+    }
+
+    /// Hash of the indexed block of `chain` at `number`.
+    pub async fn block_hash(&self, chain: Chain, number: u64) -> Result<Option<String>, Error> {
+        let hash = sqlx::query_scalar("SELECT hash FROM blocks WHERE chain = $1 AND number = $2")
+            .bind(chain.as_str())
+            .bind(number as i64)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(hash)
+    }
+
+    /// The live todos of `owner` on `chain`, by ID.
+    pub async fn todos(&self, chain: Chain, owner: &str) -> Result<Vec<IndexedTodo>, Error> {
+        let rows = sqlx::query(
+            "SELECT id, due, created_block, updated_block, completed_block
+            FROM todos WHERE chain = $1 AND owner = $2 AND deleted_block IS NULL ORDER BY id",
+        )
+        .bind(chain.as_str())
+        .bind(owner)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let completed_block = row.try_get::<Option<i64>, _>(4)?.map(|at| at as u64);
+                Ok(IndexedTodo {
+                    id: row.try_get::<i64, _>(0)? as u64,
+                    completed: completed_block.is_some(),
+                    due: row.try_get::<Option<i64>, _>(1)?.map(|due| due as u64),
+                    created_block: row.try_get::<i64, _>(2)? as u64,
+                    updated_block: row.try_get::<i64, _>(3)? as u64,
+                    completed_block,
+                })
+            })
+            .collect()
+    }
+
+    /// Number of indexed events of `chain`.
+    pub async fn event_count(&self, chain: Chain) -> Result<u64, Error> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events WHERE chain = $1")
+            .bind(chain.as_str())
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count as u64)
+    }
+
+    /// Index `block` in one transaction, replacing any indexed blocks above its parent.
+    pub async fn apply(&self, chain: Chain, block: &Block) -> Result<(), Error> {
+        let mut tx = self.pool.begin().await?;
+        // On Solana, the replaced branch may hold blocks in slots below this block's
+        revert(&mut tx, chain, block.parent_number.saturating_add(1).min(block.number)).await?;
+
+        sqlx::query("INSERT INTO blocks (chain, number, hash, parent_hash) VALUES ($1, $2, $3, $4)")
+            .bind(chain.as_str())
+            .bind(block.number as i64)
+            .bind(&block.hash)
+            .bind(&block.parent_hash)
+            .execute(&mut *tx)
+            .await?;
+
+        for (index, event) in block.events.iter().enumerate() {
+            let event = resolve(&mut tx, chain, event).await?;
+            let json = serde_json::to_string(&event).map_err(|e| Error::Decode(e.to_string()))?;
+            sqlx::query(
+                "INSERT INTO events (chain, block_number, event_index, owner, event)
+                VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(chain.as_str())
+            .bind(block.number as i64)
+            .bind(index as i32)
+            .bind(event.owner())
+            .bind(json)
+            .execute(&mut *tx)
+            .await?;
+
+            project(&mut tx, chain, block.number, &event).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// Remove the blocks of `chain` from `number` on and rebuild the todos their events touched.
+async fn revert(conn: &mut AnyConnection, chain: Chain, number: u64) -> Result<(), Error> {
+    let owners: Vec<String> = sqlx::query_scalar(
+        "SELECT DISTINCT owner FROM events WHERE chain = $1 AND block_number >= $2",
+    )
+    .bind(chain.as_str())
+    .bind(number as i64)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for statement in [
+        "DELETE FROM events WHERE chain = $1 AND block_number >= $2",
+        "DELETE FROM blocks WHERE chain = $1 AND number >= $2",
+    ] {
+        sqlx::query(statement).bind(chain.as_str()).bind(number as i64).execute(&mut *conn).await?;
+    }
+
+    for owner in owners {
+        sqlx::query("DELETE FROM todos WHERE chain = $1 AND owner = $2")
+            .bind(chain.as_str())
+            .bind(&owner)
+            .execute(&mut *conn)
+            .await?;
+
+        let events: Vec<(i64, String)> = sqlx::query_as(
+            "SELECT block_number, event FROM events WHERE chain = $1 AND owner = $2
+            ORDER BY block_number, event_index",
+        )
+        .bind(chain.as_str())
+        .bind(&owner)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for (block_number, event) in events {
+            let event = serde_json::from_str(&event)
+                .map_err(|e| Error::Decode(format!("stored event {event}: {e}")))?;
+            project(conn, chain, block_number as u64, &event).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Fill in what `event` copies from other todos, so projecting it again later does not need
+/// them.
+async fn resolve(
+    conn: &mut AnyConnection,
+    chain: Chain,
+    event: &TodoEvent,
+) -> Result<TodoEvent, Error> {
+    let TodoEvent::Transferred { owner, id, from, old_id, .. } = event else {
+        return Ok(event.clone())
+    };
+
+    let old: Option<(Option<i64>, Option<i64>)> = sqlx::query_as(
+        "SELECT due, completed_block FROM todos WHERE chain = $1 AND owner = $2 AND id = $3",
+    )
+    .bind(chain.as_str())
+    .bind(from)
+    .bind(*old_id as i64)
+    .fetch_optional(&mut *conn)
+    .await?;
+    let (due, completed_block) = old.unwrap_or_default();
+
+    Ok(TodoEvent::Transferred {
+        owner: owner.clone(),
+        id: *id,
+        from: from.clone(),
+        old_id: *old_id,
+        due: due.map(|due| due as u64),
+        completed_block: completed_block.map(|at| at as u64),
+    })
+}
+
+/// Apply `event`, emitted in block `number`, to the todos table.
+async fn project(
+    conn: &mut AnyConnection,
+    chain: Chain,
+    number: u64,
+    event: &TodoEvent,
+) -> Result<(), Error> {
+    let chain = chain.as_str();
+    let number = number as i64;
+
+    let query = match event {
+        TodoEvent::Created { owner, id } => sqlx::query(
+            "INSERT INTO todos (chain, owner, id, created_block, updated_block)
+            VALUES ($1, $2, $3, $4, $4)",
+        )
+        .bind(chain)
+        .bind(owner)
+        .bind(*id as i64)
+        .bind(number),
+        TodoEvent::Updated { owner, id } => sqlx::query(
+            "UPDATE todos SET updated_block = $4 WHERE chain = $1 AND owner = $2 AND id = $3",
+        )
+        .bind(chain)
+        .bind(owner)
+        .bind(*id as i64)
+        .bind(number),
+        TodoEvent::CompletionToggled { owner, id, completed } => sqlx::query(
+            "UPDATE todos SET completed_block = $4, updated_block = $5
+            WHERE chain = $1 AND owner = $2 AND id = $3",
+        )
+        .bind(chain)
+        .bind(owner)
+        .bind(*id as i64)
+        .bind(completed.then_some(number))
+        .bind(number),
+        TodoEvent::DueDateSet { owner, id, due } => sqlx::query(
+            "UPDATE todos SET due = $4, updated_block = $5
+            WHERE chain = $1 AND owner = $2 AND id = $3",
+        )
+        .bind(chain)
+        .bind(owner)
+        .bind(*id as i64)
+        .bind(due.map(|due| due as i64))
+        .bind(number),
+        TodoEvent::Deleted { owner, id } => sqlx::query(
+            "UPDATE todos SET deleted_block = $4 WHERE chain = $1 AND owner = $2 AND id = $3",
+        )
+        .bind(chain)
+        .bind(owner)
+        .bind(*id as i64)
+        .bind(number),
+        TodoEvent::Restored { owner, id } => sqlx::query(
+            "UPDATE todos SET deleted_block = NULL, updated_block = $4
+            WHERE chain = $1 AND owner = $2 AND id = $3",
+        )
+        .bind(chain)
+        .bind(owner)
+        .bind(*id as i64)
+        .bind(number),
+        TodoEvent::Purged { owner, id } => {
+            sqlx::query("DELETE FROM todos WHERE chain = $1 AND owner = $2 AND id = $3")
+                .bind(chain)
+                .bind(owner)
+                .bind(*id as i64)
+        },
+        TodoEvent::Transferred { owner, id, due, completed_block, .. } => sqlx::query(
+            "INSERT INTO todos (chain, owner, id, due, created_block, updated_block, completed_block)
+            VALUES ($1, $2, $3, $4, $5, $5, $6)",
+        )
+        .bind(chain)
+        .bind(owner)
+        .bind(*id as i64)
+        .bind(due.map(|due| due as i64))
+        .bind(number)
+        .bind(completed_block.map(|at| at as i64)),
+        TodoEvent::AccountCleared { owner } => {
+            sqlx::query("DELETE FROM todos WHERE chain = $1 AND owner = $2")
+                .bind(chain)
+                .bind(owner)
+        },
+        TodoEvent::ListTransferred { owner } => sqlx::query(
+            "DELETE FROM todos WHERE chain = $1 AND owner = $2 AND deleted_block IS NULL",
+        )
+        .bind(chain)
+        .bind(owner),
+    };
+
+    query.execute(conn).await?;
+    Ok(())
+}
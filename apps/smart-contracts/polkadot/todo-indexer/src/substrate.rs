@@ -0,0 +1,162 @@
+//! Todo events of `pallet_todo`, read through `todo-subxt-client`.
+
+use std::io::Write;
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use subxt::{blocks::Block as SubxtBlock, utils::H256};
+use todo_subxt_client::{events::TodoEvent as PalletEvent, TodoClient, TodoConfig};
+
+use crate::{record, Block, Error, Indexer, TodoEvent};
+
+/// URL of a local development node.
+pub const DEFAULT_URL: &str = "ws://127.0.0.1:9944";
+
+/// A block as recorded from a node.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedBlock {
+    pub number: u64,
+    /// Hex block hash, with a `0x` prefix
+    pub hash: String,
+    pub parent_hash: String,
+    /// Every event of the block, as hex SCALE encoded runtime events
+    pub events: Vec<String>,
+}
+
+impl RecordedBlock {
+    /// Record the events of `block`.
+    pub async fn new(block: &SubxtBlock<TodoConfig, TodoClient>) -> Result<Self, Error> {
+        let events = block
+            .events()
+            .await?
+            .iter()
+            .map(|event| {
+                let event = event?;
+                // A runtime event is the pallet index, the variant index and the fields
+                let mut bytes = vec![event.pallet_index(), event.variant_index()];
+                bytes.extend_from_slice(event.field_bytes());
+                Ok(format!("0x{}", hex::encode(bytes)))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(RecordedBlock {
+            number: block.number().into(),
+            hash: format!("{:?}", block.hash()),
+            parent_hash: format!("{:?}", block.header().parent_hash),
+            events,
+        })
+    }
+
+    /// The todo events of this block.
+    pub fn decode(&self) -> Result<Block, Error> {
+        let mut events = Vec::new();
+        for (index, event) in self.events.iter().enumerate() {
+            let malformed =
+                |e: String| Error::Decode(format!("event {index} of #{}: {e}", self.number));
+            let bytes = hex::decode(event.trim_start_matches("0x"))
+                .map_err(|e| malformed(e.to_string()))?;
+            let event = todo_subxt_client::events::decode_todo_event(&bytes)
+                .map_err(|e| malformed(e.to_string()))?;
+            events.extend(event.and_then(todo_event));
+        }
+
+        Ok(Block {
+            number: self.number,
+            hash: self.hash.clone(),
+            parent_number: self.number.saturating_sub(1),
+            parent_hash: self.parent_hash.clone(),
+            events,
+        })
+    }
+}
+
+/// The change an event of the pallet makes to the todo lists, if any.
+pub fn todo_event(event: PalletEvent) -> Option<TodoEvent> {
+    let event = match event {
+        PalletEvent::TodoCreated { who, id } => TodoEvent::Created { owner: who.to_string(), id },
+        PalletEvent::TodoRestored { who, id } => TodoEvent::Restored { owner: who.to_string(), id },
+        PalletEvent::TodoTransferred { from, old_id, to, new_id } => TodoEvent::Transferred {
+            owner: to.to_string(),
+            id: new_id,
+            from: from.to_string(),
+            old_id,
+            due: None,
+            completed_block: None,
+        },
+        PalletEvent::TodoUpdated { who, id } |
+        PalletEvent::TodoDueSoon { who, id, .. } |
+        PalletEvent::TodoStatusChanged { who, id, .. } |
+        PalletEvent::EncryptedPayloadSet { who, id } =>
+            TodoEvent::Updated { owner: who.to_string(), id },
+        PalletEvent::TodoCompletionToggled { who, id, completed } =>
+            TodoEvent::CompletionToggled { owner: who.to_string(), id, completed },
+        PalletEvent::DueDateSet { who, id, due } =>
+            TodoEvent::DueDateSet { owner: who.to_string(), id, due },
+        PalletEvent::TodoDeleted { who, id } => TodoEvent::Deleted { owner: who.to_string(), id },
+        // Archived todos cannot be restored, so they leave the index like purged ones
+        PalletEvent::TodoForceDeleted { who, id } |
+        PalletEvent::TodoPurged { who, id } |
+        PalletEvent::TodoArchived { who, id } =>
+            TodoEvent::Purged { owner: who.to_string(), id },
+        PalletEvent::AccountForceCleared { who, .. } | PalletEvent::AllTodosCleared { who, .. } =>
+            TodoEvent::AccountCleared { owner: who.to_string() },
+        // Follows the `TodoTransferred` of every todo, so only the sender's rows are left
+        PalletEvent::ListTransferred { from, .. } =>
+            TodoEvent::ListTransferred { owner: from.to_string() },
+        _ => return None,
+    };
+    Some(event)
+}
+
+/// Fetch the block with the hex `hash`.
+pub async fn fetch(client: &TodoClient, hash: &str) -> Result<RecordedBlock, Error> {
+    let bytes = hex::decode(hash.trim_start_matches("0x"))
+        .ok()
+        .filter(|bytes| bytes.len() == 32)
+        .ok_or_else(|| Error::Decode(format!("invalid block hash {hash}")))?;
+    let block = client.blocks().at(H256::from_slice(&bytes)).await?;
+    RecordedBlock::new(&block).await
+}
+
+/// Index the finalized blocks of the node, catching up from the indexed head first, and append
+/// them to `recording` if given.
+pub async fn follow(
+    indexer: &Indexer,
+    client: &TodoClient,
+    mut recording: Option<&mut dyn Write>,
+) -> Result<(), Error> {
+    let mut finalized = client.blocks().subscribe_finalized().await?;
+
+    while let Some(block) = finalized.next().await {
+        let block = block?;
+        let number = u64::from(block.number());
+
+        let next = indexer.head().await?.map_or(number, |head| head.number + 1);
+        for missed in next..number {
+            let hash = client
+                .rpc()
+                .block_hash(Some(missed.into()))
+                .await?
+                .ok_or_else(|| Error::UnknownBlock(format!("#{missed}")))?;
+            let missed = RecordedBlock::new(&client.blocks().at(hash).await?).await?;
+            ingest(indexer, client, missed, &mut recording).await?;
+        }
+
+        ingest(indexer, client, RecordedBlock::new(&block).await?, &mut recording).await?;
+    }
+    Ok(())
+}
+
+async fn ingest(
+    indexer: &Indexer,
+    client: &TodoClient,
+    recorded: RecordedBlock,
+    recording: &mut Option<&mut dyn Write>,
+) -> Result<(), Error> {
+    if let Some(out) = recording.as_mut() {
+        record::write(out, &recorded)?;
+    }
+    indexer
+        .ingest(recorded.decode()?, |hash| async move { fetch(client, &hash).await?.decode() })
+        .await
+}
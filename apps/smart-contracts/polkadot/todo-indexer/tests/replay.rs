@@ -0,0 +1,278 @@
+//! Replays recorded blocks into an in-memory SQLite store.
+//!
+//! `fixtures/substrate.jsonl` holds blocks 1 to 5 of a dev chain that reorganized at block 3:
+//! blocks 3a and 4a were replaced by 3b, 4b and 5b. `fixtures/solana.jsonl` holds slots 10 to
+//! 15 of a cluster where the block in slot 14 was replaced by one in slot 15.
+
+use std::{collections::HashMap, io::BufReader};
+
+use todo_indexer::{
+    record, replay, solana, substrate, Block, BlockRef, Chain, IndexedTodo, Indexer, Store,
+    TodoEvent,
+};
+
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+const SOLANA_A: &str = "GsbwXfJraMomNxBcjYLcG3mxkBUiyWXAB32fGbSMQRdW";
+const SOLANA_B: &str = "HJp6bJcoLcD6bmLaBmwVdfm5hVRCnMfUqMzsgWMvQpAB";
+
+fn substrate_blocks() -> Vec<Block> {
+    let recording = BufReader::new(&include_bytes!("fixtures/substrate.jsonl")[..]);
+    record::read::<substrate::RecordedBlock>(recording)
+        .unwrap()
+        .iter()
+        .map(|block| block.decode().unwrap())
+        .collect()
+}
+
+fn solana_blocks() -> Vec<Block> {
+    let program_id = todo_program::ID.to_string();
+    let recording = BufReader::new(&include_bytes!("fixtures/solana.jsonl")[..]);
+    record::read::<solana::RecordedBlock>(recording)
+        .unwrap()
+        .iter()
+        .map(|block| block.decode(&program_id))
+        .collect()
+}
+
+async fn indexer(chain: Chain) -> Indexer {
+    Indexer::new(Store::connect("sqlite::memory:").await.unwrap(), chain)
+}
+
+fn todo(id: u64, created_block: u64, updated_block: u64) -> IndexedTodo {
+    IndexedTodo {
+        id,
+        completed: false,
+        due: None,
+        created_block,
+        updated_block,
+        completed_block: None,
+    }
+}
+
+/// A block of hand-written events.
+fn block(number: u64, hash: &str, parent_hash: &str, events: Vec<TodoEvent>) -> Block {
+    Block {
+        number,
+        hash: hash.into(),
+        parent_number: number - 1,
+        parent_hash: parent_hash.into(),
+        events,
+    }
+}
+
+#[test]
+fn recorded_substrate_blocks_decode_to_todo_events() {
+    let blocks = substrate_blocks();
+    assert_eq!(blocks.len(), 7);
+
+    // The system event of block 1 is skipped
+    assert_eq!(
+        blocks[0].events,
+        vec![
+            TodoEvent::Created { owner: ALICE.into(), id: 1 },
+            TodoEvent::Created { owner: ALICE.into(), id: 2 },
+        ]
+    );
+    assert_eq!(
+        blocks[1].events[1],
+        TodoEvent::DueDateSet { owner: ALICE.into(), id: 2, due: Some(1_700_000_000_000) }
+    );
+    assert_eq!((blocks[4].number, blocks[4].parent_number), (3, 2));
+    assert_eq!(blocks[4].parent_hash, blocks[1].hash);
+}
+
+#[tokio::test]
+async fn replays_substrate_blocks_across_a_reorg() {
+    let indexer = indexer(Chain::Substrate).await;
+    let blocks = substrate_blocks();
+    replay(&indexer, blocks.clone()).await.unwrap();
+
+    let store = indexer.store();
+    let head = BlockRef { number: 5, hash: blocks[6].hash.clone() };
+    assert_eq!(indexer.head().await.unwrap(), Some(head));
+    assert_eq!(store.block_hash(Chain::Substrate, 3).await.unwrap(), Some(blocks[4].hash.clone()));
+
+    // Todo 1 was completed in block 2 and reopened in 3b; todo 2 was deleted in 3a only
+    assert_eq!(
+        store.todos(Chain::Substrate, ALICE).await.unwrap(),
+        vec![todo(1, 1, 3), todo(2, 1, 4), todo(3, 3, 3)]
+    );
+    // Bob's todos were cleared in 5b
+    assert_eq!(store.todos(Chain::Substrate, BOB).await.unwrap(), vec![]);
+    // Only the events of the canonical chain are kept
+    assert_eq!(store.event_count(Chain::Substrate).await.unwrap(), 10);
+}
+
+#[tokio::test]
+async fn ingest_fetches_the_new_branch_on_a_reorg() {
+    let indexer = indexer(Chain::Substrate).await;
+    let blocks = substrate_blocks();
+    replay(&indexer, blocks[..4].to_vec()).await.unwrap();
+
+    let store = indexer.store();
+    assert_eq!(
+        store.todos(Chain::Substrate, ALICE).await.unwrap(),
+        vec![
+            IndexedTodo { completed: true, completed_block: Some(2), ..todo(1, 1, 2) },
+            todo(3, 4, 4),
+        ]
+    );
+    assert_eq!(store.todos(Chain::Substrate, BOB).await.unwrap(), vec![todo(1, 2, 3)]);
+
+    // Block 4b arrives without its parent 3b, which has to be fetched
+    let by_hash =
+        blocks.iter().map(|block| (block.hash.clone(), block.clone())).collect::<HashMap<_, _>>();
+    let mut fetched = Vec::new();
+    indexer
+        .ingest(blocks[5].clone(), |hash| {
+            fetched.push(hash.clone());
+            let block = by_hash[&hash].clone();
+            async move { Ok(block) }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(fetched, vec![blocks[4].hash.clone()]);
+    let head = BlockRef { number: 4, hash: blocks[5].hash.clone() };
+    assert_eq!(indexer.head().await.unwrap(), Some(head));
+    assert_eq!(
+        store.todos(Chain::Substrate, ALICE).await.unwrap(),
+        vec![todo(1, 1, 3), todo(2, 1, 4), todo(3, 3, 3)]
+    );
+    // Bob's update in 3a is gone with it
+    assert_eq!(
+        store.todos(Chain::Substrate, BOB).await.unwrap(),
+        vec![todo(1, 2, 2), todo(2, 4, 4)]
+    );
+}
+
+#[tokio::test]
+async fn replaying_twice_changes_nothing() {
+    let indexer = indexer(Chain::Substrate).await;
+    replay(&indexer, substrate_blocks()).await.unwrap();
+    let todos = indexer.store().todos(Chain::Substrate, ALICE).await.unwrap();
+
+    replay(&indexer, substrate_blocks()[4..].to_vec()).await.unwrap();
+    assert_eq!(indexer.store().todos(Chain::Substrate, ALICE).await.unwrap(), todos);
+    assert_eq!(indexer.store().event_count(Chain::Substrate).await.unwrap(), 10);
+}
+
+#[tokio::test]
+async fn transferred_todos_keep_their_due_date_and_completion() {
+    let indexer = indexer(Chain::Substrate).await;
+    let transferred = |id, old_id| TodoEvent::Transferred {
+        owner: BOB.into(),
+        id,
+        from: ALICE.into(),
+        old_id,
+        due: None,
+        completed_block: None,
+    };
+    let due = Some(1_700_000_000_000);
+    let blocks = vec![
+        block(1, "1", "0", vec![
+            TodoEvent::Created { owner: ALICE.into(), id: 0 },
+            TodoEvent::Created { owner: ALICE.into(), id: 1 },
+        ]),
+        block(2, "2", "1", vec![
+            TodoEvent::CompletionToggled { owner: ALICE.into(), id: 0, completed: true },
+            TodoEvent::DueDateSet { owner: ALICE.into(), id: 1, due },
+        ]),
+        // Bob accepts Alice's list, merging it into his own
+        block(3, "3", "2", vec![
+            TodoEvent::Created { owner: BOB.into(), id: 0 },
+            transferred(1, 0),
+            transferred(2, 1),
+            TodoEvent::AccountCleared { owner: ALICE.into() },
+        ]),
+        block(4, "4a", "3", vec![TodoEvent::Updated { owner: BOB.into(), id: 1 }]),
+        // Replaces 4a, so Bob's todos are rebuilt without Alice's rows
+        block(4, "4b", "3", vec![TodoEvent::Deleted { owner: BOB.into(), id: 0 }]),
+    ];
+    replay(&indexer, blocks).await.unwrap();
+
+    let store = indexer.store();
+    assert_eq!(store.todos(Chain::Substrate, ALICE).await.unwrap(), vec![]);
+    assert_eq!(
+        store.todos(Chain::Substrate, BOB).await.unwrap(),
+        vec![
+            IndexedTodo { completed: true, completed_block: Some(2), ..todo(1, 3, 3) },
+            IndexedTodo { due, ..todo(2, 3, 3) },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn restored_todos_keep_their_due_date_and_completion() {
+    let indexer = indexer(Chain::Substrate).await;
+    let due = Some(1_700_000_000_000);
+    let blocks = vec![
+        block(1, "1", "0", vec![
+            TodoEvent::Created { owner: ALICE.into(), id: 0 },
+            TodoEvent::Created { owner: ALICE.into(), id: 1 },
+            TodoEvent::DueDateSet { owner: ALICE.into(), id: 0, due },
+        ]),
+        block(2, "2", "1", vec![
+            TodoEvent::CompletionToggled { owner: ALICE.into(), id: 0, completed: true },
+            TodoEvent::Deleted { owner: ALICE.into(), id: 0 },
+            TodoEvent::Deleted { owner: ALICE.into(), id: 1 },
+        ]),
+        // Alice's list moves to Bob; her deleted todos stay with her
+        block(3, "3", "2", vec![TodoEvent::ListTransferred { owner: ALICE.into() }]),
+        block(4, "4", "3", vec![
+            TodoEvent::Restored { owner: ALICE.into(), id: 0 },
+            TodoEvent::Purged { owner: ALICE.into(), id: 1 },
+        ]),
+    ];
+    replay(&indexer, blocks[..3].to_vec()).await.unwrap();
+
+    // Deleted todos are not listed
+    let store = indexer.store();
+    assert_eq!(store.todos(Chain::Substrate, ALICE).await.unwrap(), vec![]);
+
+    replay(&indexer, blocks[3..].to_vec()).await.unwrap();
+    assert_eq!(
+        store.todos(Chain::Substrate, ALICE).await.unwrap(),
+        vec![IndexedTodo { completed: true, due, completed_block: Some(2), ..todo(0, 1, 4) }]
+    );
+}
+
+#[test]
+fn solana_logs_of_other_programs_and_failed_transactions_are_ignored() {
+    let blocks = solana_blocks();
+
+    // The spoofed deletion and the failed deletion leave only the toggle
+    assert_eq!(
+        blocks[1].events,
+        vec![
+            TodoEvent::CompletionToggled { owner: SOLANA_A.into(), id: 1, completed: true },
+            TodoEvent::Created { owner: SOLANA_B.into(), id: 1 },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn replays_solana_blocks_across_a_fork() {
+    let indexer = indexer(Chain::Solana).await;
+    let blocks = solana_blocks();
+    replay(&indexer, blocks.clone()).await.unwrap();
+
+    let store = indexer.store();
+    let head = BlockRef { number: 15, hash: blocks[4].hash.clone() };
+    assert_eq!(indexer.head().await.unwrap(), Some(head));
+    // The block in slot 14 was on the abandoned fork
+    assert_eq!(store.block_hash(Chain::Solana, 14).await.unwrap(), None);
+
+    assert_eq!(
+        store.todos(Chain::Solana, SOLANA_A).await.unwrap(),
+        vec![todo(1, 10, 15), todo(2, 10, 13)]
+    );
+    assert_eq!(
+        store.todos(Chain::Solana, SOLANA_B).await.unwrap(),
+        vec![IndexedTodo { completed: true, completed_block: Some(13), ..todo(1, 12, 13) }]
+    );
+    // Both chains share the tables without seeing each other's todos
+    assert_eq!(store.todos(Chain::Substrate, SOLANA_A).await.unwrap(), vec![]);
+}
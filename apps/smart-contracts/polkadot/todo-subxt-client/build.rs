@@ -0,0 +1,33 @@
+//! Generates the typed client from the metadata of the `todo-runtime` crate.
+//!
+//! The metadata is taken straight from the runtime this workspace builds, so the client never
+//! drifts from the pallet and no node has to be running to regenerate it.
+
+use std::{env, fs, path::PathBuf};
+
+use codec::Encode;
+use subxt_codegen::{CratePath, DerivesRegistry, GenerateRuntimeApi, TypeSubstitutes};
+
+fn main() {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("set by cargo"));
+    let metadata = todo_runtime::Runtime::metadata().encode();
+
+    let crate_path = CratePath::default();
+    let mut derives = DerivesRegistry::with_default_derives(&crate_path);
+    derives.extend_for_all(
+        [syn::parse_quote!(Clone), syn::parse_quote!(PartialEq), syn::parse_quote!(Eq)],
+        [],
+    );
+
+    let api = GenerateRuntimeApi::new(syn::parse_quote!(pub mod api {}), crate_path.clone())
+        .derives_registry(derives)
+        .type_substitutes(TypeSubstitutes::with_default_substitutes(&crate_path))
+        .generate_docs(true)
+        .generate_from_bytes(&metadata)
+        .expect("the todo runtime's metadata is supported by subxt");
+
+    fs::write(out_dir.join("todo_metadata.scale"), &metadata).expect("metadata is written");
+    fs::write(out_dir.join("todo_api.rs"), api.to_string()).expect("client is written");
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,84 @@
+//! Builders for the todo extrinsics.
+//!
+//! Each builder returns a statically checked payload: submitting it to a node whose runtime no
+//! longer matches the generated [`api`](crate::api) fails before anything is signed.
+
+use subxt::tx::Payload;
+
+use crate::{
+    api, api::todo::calls::types, AccountId, Priority, RetentionPolicy, Status, TransferMode,
+};
+
+/// Create a new todo.
+pub fn create_todo(
+    title: impl Into<Vec<u8>>,
+    description: impl Into<Vec<u8>>,
+    priority: Priority,
+) -> Payload<types::CreateTodo> {
+    api::tx().todo().create_todo(title.into(), description.into(), priority)
+}
+
+/// Update an existing todo; fields left as `None` keep their value.
+pub fn update_todo(
+    id: u64,
+    title: Option<Vec<u8>>,
+    description: Option<Vec<u8>>,
+    priority: Option<Priority>,
+) -> Payload<types::UpdateTodo> {
+    api::tx().todo().update_todo(id, title, description, priority)
+}
+
+/// Set the urgency of a todo from 0 to 255, which also sets its priority.
+pub fn set_urgency(id: u64, urgency: u8) -> Payload<types::SetUrgency> {
+    api::tx().todo().set_urgency(id, urgency)
+}
+
+/// Move a todo to another status; the pallet rejects transitions its workflow does not allow.
+pub fn set_status(id: u64, status: Status) -> Payload<types::SetStatus> {
+    api::tx().todo().set_status(id, status)
+}
+
+/// Toggle the completion status of a todo.
+pub fn toggle_todo_completion(id: u64) -> Payload<types::ToggleTodoCompletion> {
+    api::tx().todo().toggle_todo_completion(id)
+}
+
+/// Delete a todo; it can be restored until the pallet's grace period is over.
+pub fn delete_todo(id: u64) -> Payload<types::DeleteTodo> {
+    api::tx().todo().delete_todo(id)
+}
+
+/// Restore a deleted todo.
+pub fn undelete_todo(id: u64) -> Payload<types::UndeleteTodo> {
+    api::tx().todo().undelete_todo(id)
+}
+
+/// Opt in to a retention policy for completed todos, or out of it with `None`.
+pub fn set_retention_policy(
+    policy: Option<RetentionPolicy<u64>>,
+) -> Payload<types::SetRetentionPolicy> {
+    api::tx().todo().set_retention_policy(policy)
+}
+
+/// Remove all todos of the signer and start IDs from 0 again.
+pub fn clear_all_todos() -> Payload<types::ClearAllTodos> {
+    api::tx().todo().clear_all_todos()
+}
+
+/// Offer the signer's todo list to `to`, who has to accept it.
+pub fn transfer_list(to: AccountId) -> Payload<types::TransferList> {
+    api::tx().todo().transfer_list(to)
+}
+
+/// Accept the todo list `from` offered, merging it into or replacing the signer's list.
+pub fn accept_list_transfer(
+    from: AccountId,
+    mode: TransferMode,
+) -> Payload<types::AcceptListTransfer> {
+    api::tx().todo().accept_list_transfer(from, mode)
+}
+
+/// Withdraw the offer of the signer's todo list.
+pub fn cancel_list_transfer() -> Payload<types::CancelListTransfer> {
+    api::tx().todo().cancel_list_transfer()
+}
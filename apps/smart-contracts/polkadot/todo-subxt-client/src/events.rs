@@ -0,0 +1,34 @@
+//! Typed decoding of the todo events.
+//!
+//! Every event of `pallet_todo` is re-exported as a struct implementing
+//! [`StaticEvent`](subxt::events::StaticEvent), so it can be looked up with `find_first`, and
+//! [`TodoEvent`] holds any of them.
+
+use subxt::{blocks::ExtrinsicEvents, ext::codec::Decode};
+
+use crate::{api, TodoConfig};
+
+pub use api::todo::events::*;
+
+/// Any event of `pallet_todo`.
+pub type TodoEvent = api::todo::Event;
+
+/// The todo events emitted by a transaction, in order.
+pub fn todo_events(events: &ExtrinsicEvents<TodoConfig>) -> Result<Vec<TodoEvent>, subxt::Error> {
+    events
+        .iter()
+        .filter_map(|event| match event.and_then(|event| event.as_root_event::<api::Event>()) {
+            Ok(api::Event::Todo(event)) => Some(Ok(event)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect()
+}
+
+/// Decode a SCALE encoded runtime event, returning it only if it is a todo event.
+pub fn decode_todo_event(mut bytes: &[u8]) -> Result<Option<TodoEvent>, subxt::ext::codec::Error> {
+    match api::Event::decode(&mut bytes)? {
+        api::Event::Todo(event) => Ok(Some(event)),
+        _ => Ok(None),
+    }
+}
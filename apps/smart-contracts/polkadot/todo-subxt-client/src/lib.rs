@@ -0,0 +1,96 @@
+//! Typed Rust client for the todo chain.
+//!
+//! The [`api`] module is generated by `build.rs` from the metadata of the `todo-runtime` crate,
+//! so it always matches the runtime in this workspace. On top of it this crate offers:
+//!
+//! - [`calls`]: builders for `create_todo`, `update_todo`, `set_urgency`, `set_status`,
+//!   `toggle_todo_completion`, `delete_todo`, `undelete_todo`, `set_retention_policy`,
+//!   `clear_all_todos` and the list transfer calls,
+//! - [`events`]: typed decoding of the todo events, such as [`events::TodoCreated`],
+//! - [`storage`]: readers for `Todos`, `NextId` and `TodoStats`.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), subxt::Error> {
+//! use subxt_signer::sr25519::dev;
+//! use todo_subxt_client::{calls, storage, Priority, TodoClient};
+//!
+//! let client = TodoClient::from_url("ws://127.0.0.1:9944").await?;
+//! let alice = dev::alice();
+//!
+//! let call = calls::create_todo("Buy groceries", "Milk and eggs", Priority::Medium);
+//! let events = todo_subxt_client::submit(&client, &call, &alice).await?;
+//! println!("{events:?}");
+//!
+//! let todos = storage::todos(&client, &alice.public_key().into()).await?;
+//! println!("{} todos", todos.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use subxt::{
+    ext::codec::Decode,
+    tx::{Signer, TxPayload},
+    OnlineClient, PolkadotConfig,
+};
+
+pub mod calls;
+pub mod events;
+pub mod storage;
+
+#[cfg(test)]
+mod tests;
+
+#[allow(clippy::all)]
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/todo_api.rs"));
+}
+
+pub use generated::api;
+pub use api::runtime_types::pallet_todo::pallet::{
+    Priority, RetentionAction, RetentionPolicy, Status, StatusTimestamps, Todo, TodoStatistics,
+    TransferMode,
+};
+pub use subxt::utils::AccountId32 as AccountId;
+
+/// SCALE encoded metadata of the runtime the client was generated from.
+pub const METADATA: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/todo_metadata.scale"));
+
+/// Chain configuration of the todo runtime.
+///
+/// The runtime uses Polkadot's address, signature and hashing types, and its signed extensions
+/// encode exactly like Polkadot's: the todo extensions add no bytes and `ChargeTodoTransaction`
+/// encodes a plain tip.
+pub type TodoConfig = PolkadotConfig;
+
+/// Client connected to a todo node.
+pub type TodoClient = OnlineClient<TodoConfig>;
+
+/// Decoded [`METADATA`].
+pub fn metadata() -> subxt::Metadata {
+    subxt::Metadata::decode(&mut &METADATA[..]).expect("metadata generated by build.rs is valid")
+}
+
+/// Sign `call`, submit it and wait until it is included in a block.
+///
+/// Returns the todo events emitted by the transaction. A failed dispatch is returned as an
+/// error.
+pub async fn submit<Call, S>(
+    client: &TodoClient,
+    call: &Call,
+    signer: &S,
+) -> Result<Vec<events::TodoEvent>, subxt::Error>
+where
+    Call: TxPayload,
+    S: Signer<TodoConfig>,
+{
+    let events = client
+        .tx()
+        .sign_and_submit_then_watch_default(call, signer)
+        .await?
+        .wait_for_in_block()
+        .await?
+        .wait_for_success()
+        .await?;
+
+    events::todo_events(&events)
+}
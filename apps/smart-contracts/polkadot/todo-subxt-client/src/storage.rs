@@ -0,0 +1,26 @@
+//! Readers for the todo storage, at the latest block.
+
+use crate::{api, AccountId, Todo, TodoClient, TodoStatistics};
+
+/// The todos of an account.
+pub async fn todos(client: &TodoClient, who: &AccountId) -> Result<Vec<Todo>, subxt::Error> {
+    let address = api::storage().todo().todos(who);
+    let todos = client.storage().at_latest().await?.fetch_or_default(&address).await?;
+
+    Ok(todos.0)
+}
+
+/// The ID the next todo of an account will get.
+pub async fn next_id(client: &TodoClient, who: &AccountId) -> Result<u64, subxt::Error> {
+    let address = api::storage().todo().next_id(who);
+    client.storage().at_latest().await?.fetch_or_default(&address).await
+}
+
+/// The todo statistics of an account.
+pub async fn todo_stats(
+    client: &TodoClient,
+    who: &AccountId,
+) -> Result<TodoStatistics, subxt::Error> {
+    let address = api::storage().todo().todo_stats(who);
+    client.storage().at_latest().await?.fetch_or_default(&address).await
+}
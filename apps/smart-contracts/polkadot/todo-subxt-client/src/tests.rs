@@ -0,0 +1,209 @@
+use crate::{
+    api, calls, events, metadata, AccountId, Priority, RetentionAction, RetentionPolicy, Status,
+    TodoConfig, TransferMode, METADATA,
+};
+use codec::{Decode, Encode};
+use sp_keyring::AccountKeyring;
+use subxt::{rpc::types::RuntimeVersion, OfflineClient};
+use todo_runtime::{Runtime, RuntimeCall, RuntimeEvent};
+
+/// A client that knows the runtime's metadata but never talks to a node.
+fn offline_client() -> OfflineClient<TodoConfig> {
+    let version = RuntimeVersion {
+        spec_version: todo_runtime::VERSION.spec_version,
+        transaction_version: todo_runtime::VERSION.transaction_version,
+        other: Default::default(),
+    };
+    OfflineClient::new(Default::default(), version, metadata())
+}
+
+fn account(keyring: AccountKeyring) -> AccountId {
+    AccountId(keyring.to_raw_public())
+}
+
+/// Encode `call` with the client and decode it as the runtime would.
+fn runtime_call<Call: subxt::tx::TxPayload>(call: &Call) -> RuntimeCall {
+    let call_data = offline_client().tx().call_data(call).expect("call matches the metadata");
+    RuntimeCall::decode(&mut &call_data[..]).expect("runtime decodes the call")
+}
+
+#[test]
+fn client_is_generated_from_the_runtime_metadata() {
+    assert_eq!(METADATA, &Runtime::metadata().encode()[..]);
+    assert!(api::is_codegen_valid_for(&metadata()));
+}
+
+#[test]
+fn call_builders_encode_runtime_calls() {
+    assert_eq!(
+        runtime_call(&calls::create_todo("Groceries", "Milk and eggs", Priority::High)),
+        RuntimeCall::Todo(pallet_todo::Call::create_todo {
+            title: b"Groceries".to_vec(),
+            description: b"Milk and eggs".to_vec(),
+            priority: pallet_todo::Priority::High,
+        })
+    );
+    assert_eq!(
+        runtime_call(&calls::update_todo(3, Some(b"Flowers".to_vec()), None, Some(Priority::Low))),
+        RuntimeCall::Todo(pallet_todo::Call::update_todo {
+            id: 3,
+            title: Some(b"Flowers".to_vec()),
+            description: None,
+            priority: Some(pallet_todo::Priority::Low),
+        })
+    );
+    assert_eq!(
+        runtime_call(&calls::set_urgency(3, 200)),
+        RuntimeCall::Todo(pallet_todo::Call::set_urgency { id: 3, urgency: 200 })
+    );
+    assert_eq!(
+        runtime_call(&calls::set_status(3, Status::Blocked)),
+        RuntimeCall::Todo(pallet_todo::Call::set_status {
+            id: 3,
+            status: pallet_todo::Status::Blocked,
+        })
+    );
+    assert_eq!(
+        runtime_call(&calls::toggle_todo_completion(3)),
+        RuntimeCall::Todo(pallet_todo::Call::toggle_todo_completion { id: 3 })
+    );
+    assert_eq!(
+        runtime_call(&calls::delete_todo(3)),
+        RuntimeCall::Todo(pallet_todo::Call::delete_todo { id: 3 })
+    );
+    assert_eq!(
+        runtime_call(&calls::undelete_todo(3)),
+        RuntimeCall::Todo(pallet_todo::Call::undelete_todo { id: 3 })
+    );
+    assert_eq!(
+        runtime_call(&calls::set_retention_policy(Some(RetentionPolicy {
+            max_age: 86_400_000,
+            action: RetentionAction::Archive,
+        }))),
+        RuntimeCall::Todo(pallet_todo::Call::set_retention_policy {
+            policy: Some(pallet_todo::RetentionPolicy {
+                max_age: 86_400_000,
+                action: pallet_todo::RetentionAction::Archive,
+            }),
+        })
+    );
+    assert_eq!(
+        runtime_call(&calls::clear_all_todos()),
+        RuntimeCall::Todo(pallet_todo::Call::clear_all_todos {})
+    );
+    assert_eq!(
+        runtime_call(&calls::transfer_list(account(AccountKeyring::Bob))),
+        RuntimeCall::Todo(pallet_todo::Call::transfer_list { to: AccountKeyring::Bob.to_account_id() })
+    );
+    assert_eq!(
+        runtime_call(&calls::accept_list_transfer(account(AccountKeyring::Alice), TransferMode::Replace)),
+        RuntimeCall::Todo(pallet_todo::Call::accept_list_transfer {
+            from: AccountKeyring::Alice.to_account_id(),
+            mode: pallet_todo::TransferMode::Replace,
+        })
+    );
+    assert_eq!(
+        runtime_call(&calls::cancel_list_transfer()),
+        RuntimeCall::Todo(pallet_todo::Call::cancel_list_transfer {})
+    );
+}
+
+#[test]
+fn todo_events_decode_from_runtime_events() {
+    let alice = AccountKeyring::Alice.to_account_id();
+    let encode = |event: pallet_todo::Event<Runtime>| RuntimeEvent::Todo(event).encode();
+
+    let created = encode(pallet_todo::Event::TodoCreated { who: alice.clone(), id: 7 });
+    assert_eq!(
+        events::decode_todo_event(&created).unwrap(),
+        Some(api::todo::Event::TodoCreated { who: account(AccountKeyring::Alice), id: 7 })
+    );
+
+    let toggled = encode(pallet_todo::Event::TodoCompletionToggled {
+        who: alice.clone(),
+        id: 7,
+        completed: true,
+    });
+    assert_eq!(
+        events::decode_todo_event(&toggled).unwrap(),
+        Some(api::todo::Event::TodoCompletionToggled {
+            who: account(AccountKeyring::Alice),
+            id: 7,
+            completed: true,
+        })
+    );
+
+    let deleted = encode(pallet_todo::Event::TodoDeleted { who: alice, id: 7 });
+    assert_eq!(
+        events::decode_todo_event(&deleted).unwrap(),
+        Some(api::todo::Event::TodoDeleted { who: account(AccountKeyring::Alice), id: 7 })
+    );
+
+    // Events of other pallets are skipped
+    let remarked = RuntimeEvent::System(frame_system::Event::Remarked {
+        sender: AccountKeyring::Bob.to_account_id(),
+        hash: Default::default(),
+    })
+    .encode();
+    assert_eq!(events::decode_todo_event(&remarked).unwrap(), None);
+}
+
+#[test]
+fn storage_readers_address_the_pallet_storage() {
+    let client = offline_client();
+    let alice = AccountKeyring::Alice.to_account_id();
+    let who = account(AccountKeyring::Alice);
+
+    assert_eq!(
+        client.storage().address_bytes(&api::storage().todo().todos(&who)).unwrap(),
+        pallet_todo::Todos::<Runtime>::hashed_key_for(&alice)
+    );
+    assert_eq!(
+        client.storage().address_bytes(&api::storage().todo().next_id(&who)).unwrap(),
+        pallet_todo::NextId::<Runtime>::hashed_key_for(&alice)
+    );
+    assert_eq!(
+        client.storage().address_bytes(&api::storage().todo().todo_stats(&who)).unwrap(),
+        pallet_todo::TodoStats::<Runtime>::hashed_key_for(&alice)
+    );
+}
+
+#[test]
+fn storage_values_decode_from_runtime_values() {
+    let todo = pallet_todo::Todo::<Runtime> {
+        id: 1,
+        title: b"Groceries".to_vec().try_into().unwrap(),
+        description: b"Milk and eggs".to_vec().try_into().unwrap(),
+        status: pallet_todo::Status::Done,
+        priority: pallet_todo::Priority::Medium,
+        urgency: 96,
+        created_at: 1_000,
+        updated_at: 2_000,
+        entered_at: pallet_todo::StatusTimestamps {
+            backlog: Some(1_000),
+            done: Some(2_000),
+            ..Default::default()
+        },
+    };
+
+    // A `BoundedVec` encodes like a `Vec`
+    let decoded = <Vec<crate::Todo>>::decode(&mut &vec![todo].encode()[..]).unwrap();
+    assert_eq!(decoded.len(), 1);
+    assert_eq!(decoded[0].title.0, b"Groceries".to_vec());
+    assert_eq!((decoded[0].priority.clone(), decoded[0].urgency), (Priority::Medium, 96));
+    assert_eq!(decoded[0].status, Status::Done);
+    assert_eq!(decoded[0].entered_at.done, Some(2_000));
+
+    let stats = pallet_todo::TodoStatistics {
+        total: 3,
+        completed: 1,
+        pending: 2,
+        blocked: 1,
+        high_priority: 1,
+        urgent_priority: 1,
+        ..Default::default()
+    };
+    let decoded = crate::TodoStatistics::decode(&mut &stats.encode()[..]).unwrap();
+    assert_eq!((decoded.total, decoded.completed, decoded.pending), (3, 1, 2));
+    assert_eq!((decoded.blocked, decoded.high_priority, decoded.urgent_priority), (1, 1, 1));
+}
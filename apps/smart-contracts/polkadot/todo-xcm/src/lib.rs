@@ -0,0 +1,81 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! XCM configuration for chains that host `pallet-todo`.
+//!
+//! A chain that plugs these types into its `xcm_executor::Config` lets users on trusted chains
+//! create and complete todos with `Transact`, without holding a key on the todo chain:
+//!
+//! - [`TodoBarrier`] admits messages that pay for their execution and come from a trusted
+//!   location or from inside one, e.g. an account on a trusted sibling chain after
+//!   `DescendOrigin`.
+//! - [`LocationToAccountId`] derives a local account for every remote location by hashing its
+//!   description, so `../Parachain(2)/AccountId32(alice)` owns a todo list of its own that no
+//!   local key controls.
+//! - [`TodoOriginConverter`] dispatches `Transact` with `OriginKind::SovereignAccount` as a
+//!   signed origin of that derived account.
+//! - [`OnlyTodoCalls`] is a `SafeCallFilter` that lets nothing but `pallet_todo` calls through.
+//!
+//! The derived account pays for execution, so it has to be funded before its first message.
+
+use frame_support::traits::{ConstU32, Contains, Get, IsSubType};
+use sp_std::{marker::PhantomData, vec::Vec};
+use xcm::latest::prelude::*;
+use xcm_builder::{
+    AccountId32Aliases, AllowTopLevelPaidExecutionFrom, DescribeAllTerminal, DescribeFamily,
+    HashedDescription, SovereignSignedViaLocation, TakeWeightCredit, WithComputedOrigin,
+};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+/// Converts locations into accounts.
+///
+/// Local `AccountId32` locations map to the account itself; every other location maps to an
+/// account derived from a hash of its description.
+pub type LocationToAccountId<AccountId, Network> = (
+    AccountId32Aliases<Network, AccountId>,
+    HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
+);
+
+/// Dispatches `Transact` with `OriginKind::SovereignAccount` as the signed origin of the account
+/// `LocationToAccountId` derives for the message's origin.
+pub type TodoOriginConverter<LocationToAccountId, RuntimeOrigin> =
+    SovereignSignedViaLocation<LocationToAccountId, RuntimeOrigin>;
+
+/// Matches every location in `Locations` and every location inside one of them.
+pub struct InsideTrustedLocations<Locations>(PhantomData<Locations>);
+
+impl<Locations: Get<Vec<MultiLocation>>> Contains<MultiLocation> for InsideTrustedLocations<Locations> {
+    fn contains(location: &MultiLocation) -> bool {
+        Locations::get().iter().any(|trusted| location.starts_with(trusted))
+    }
+}
+
+/// Admits messages that pay for their execution and whose origin, after any leading
+/// `DescendOrigin`, is inside one of the `TrustedLocations`.
+///
+/// `UniversalLocation` is the universal location of the executing chain.
+pub type TodoBarrier<TrustedLocations, UniversalLocation> = (
+    TakeWeightCredit,
+    WithComputedOrigin<
+        AllowTopLevelPaidExecutionFrom<InsideTrustedLocations<TrustedLocations>>,
+        UniversalLocation,
+        ConstU32<8>,
+    >,
+);
+
+/// `SafeCallFilter` that only lets `pallet_todo` calls be dispatched from XCM.
+pub struct OnlyTodoCalls<T>(PhantomData<T>);
+
+impl<T> Contains<<T as frame_system::Config>::RuntimeCall> for OnlyTodoCalls<T>
+where
+    T: pallet_todo::Config,
+    <T as frame_system::Config>::RuntimeCall: IsSubType<pallet_todo::Call<T>>,
+{
+    fn contains(call: &<T as frame_system::Config>::RuntimeCall) -> bool {
+        call.is_sub_type().is_some()
+    }
+}
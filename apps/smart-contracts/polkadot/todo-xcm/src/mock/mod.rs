@@ -0,0 +1,104 @@
+//! A relay chain and two parachains hosting `pallet_todo`, connected through `xcm-simulator`.
+//!
+//! Parachain 1 and parachain 2 trust each other; neither trusts the relay chain.
+
+pub mod parachain;
+pub mod relay_chain;
+
+use sp_runtime::{AccountId32, BuildStorage};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::ConvertLocation;
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+pub const INITIAL_BALANCE: u128 = 1_000_000_000_000_000;
+
+decl_test_parachain! {
+    pub struct ParaA {
+        Runtime = parachain::Runtime,
+        XcmpMessageHandler = parachain::MsgQueue,
+        DmpMessageHandler = parachain::MsgQueue,
+        new_ext = para_ext(1),
+    }
+}
+
+decl_test_parachain! {
+    pub struct ParaB {
+        Runtime = parachain::Runtime,
+        XcmpMessageHandler = parachain::MsgQueue,
+        DmpMessageHandler = parachain::MsgQueue,
+        new_ext = para_ext(2),
+    }
+}
+
+decl_test_relay_chain! {
+    pub struct Relay {
+        Runtime = relay_chain::Runtime,
+        RuntimeCall = relay_chain::RuntimeCall,
+        RuntimeEvent = relay_chain::RuntimeEvent,
+        XcmConfig = relay_chain::XcmConfig,
+        MessageQueue = relay_chain::MessageQueue,
+        System = relay_chain::System,
+        new_ext = relay_ext(),
+    }
+}
+
+decl_test_network! {
+    pub struct MockNet {
+        relay_chain = Relay,
+        parachains = vec![
+            (1, ParaA),
+            (2, ParaB),
+        ],
+    }
+}
+
+/// The account that `who` on parachain `sibling` controls on another parachain.
+pub fn derived_account(sibling: u32, who: &AccountId32) -> AccountId32 {
+    let location = MultiLocation::new(
+        1,
+        X2(
+            Parachain(sibling),
+            Junction::AccountId32 {
+                network: Some(parachain::RelayNetwork::get()),
+                id: who.clone().into(),
+            },
+        ),
+    );
+    parachain::LocationToAccountId::convert_location(&location)
+        .expect("hashed descriptions cover every location; qed")
+}
+
+pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
+    use parachain::{MsgQueue, Runtime, System};
+
+    let sibling = if para_id == 1 { 2 } else { 1 };
+    let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![(ALICE, INITIAL_BALANCE), (derived_account(sibling, &ALICE), INITIAL_BALANCE)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        MsgQueue::set_para_id(para_id.into());
+    });
+    ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+    use relay_chain::{Runtime, System};
+
+    let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, INITIAL_BALANCE)] }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -0,0 +1,374 @@
+//! A parachain that hosts `pallet_todo` and admits todo calls from its trusted sibling.
+
+use crate::{OnlyTodoCalls, TodoBarrier, TodoOriginConverter};
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{ConstU128, ConstU16, ConstU32, ConstU64, EqualPrivilegeOnly, Everything, Nothing},
+    weights::Weight,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    testing::TestXt,
+    traits::{IdentityLookup, Verify},
+    AccountId32, MultiSignature,
+};
+use xcm::latest::prelude::*;
+use xcm_builder::{
+    CurrencyAdapter as XcmCurrencyAdapter, FixedRateOfFungible, FixedWeightBounds, IsConcrete,
+    SignedToAccountId32,
+};
+use xcm_executor::XcmExecutor;
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+impl frame_system::Config for Runtime {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Hash = H256;
+    type Hashing = sp_runtime::traits::BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Block = Block;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ConstU16<42>;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Runtime {
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type RuntimeHoldReason = ();
+    type MaxHolds = ();
+}
+
+impl pallet_preimage::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<AccountId>;
+    type BaseDeposit = ConstU128<0>;
+    type ByteDeposit = ConstU128<0>;
+}
+
+parameter_types! {
+    pub MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+}
+
+impl pallet_scheduler::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type PalletsOrigin = OriginCaller;
+    type RuntimeCall = RuntimeCall;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<AccountId>;
+    type MaxScheduledPerBlock = ConstU32<10>;
+    type WeightInfo = ();
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type Preimages = Preimage;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+    type Public = <MultiSignature as Verify>::Signer;
+    type Signature = MultiSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Runtime
+where
+    RuntimeCall: From<LocalCall>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = TestXt<RuntimeCall, ()>;
+}
+
+pub struct MockTime;
+
+impl frame_support::traits::Time for MockTime {
+    type Moment = u64;
+
+    fn now() -> Self::Moment {
+        System::block_number() * 1000
+    }
+}
+
+impl pallet_todo::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Moment = u64;
+    type TimeProvider = MockTime;
+    type MaxTitleLength = ConstU32<100>;
+    type MaxDescriptionLength = ConstU32<500>;
+    type MaxTodosPerAccount = ConstU32<50>;
+    type FreeCallsPerPeriod = ConstU32<10>;
+    type FreeCallPeriod = ConstU64<100>;
+    type CompletionHandler = ();
+    type AdminOrigin = EnsureRoot<AccountId>;
+    type MaxOpsPerWindow = ConstU32<100>;
+    type RateLimitWindow = ConstU64<10>;
+    type AuthorityId = pallet_todo::crypto::TodoAuthId;
+    type ReminderLeadTime = ConstU64<5_000>;
+    type MaxRemindersPerBlock = ConstU32<10>;
+    type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+    type MaxReminderAuthorities = ConstU32<4>;
+    type MaxCiphertextLength = ConstU32<1024>;
+    type MaxRecipients = ConstU32<4>;
+    type RuntimeCall = RuntimeCall;
+    type PalletsOrigin = OriginCaller;
+    type Scheduler = Scheduler;
+    type MaxScheduledPerAccount = ConstU32<3>;
+    type MinSchedulePeriod = ConstU64<3>;
+    type MaxScheduleRepetitions = ConstU32<5>;
+    type ClaimDuration = ConstU64<10>;
+    type MaxBoardTasksPerAccount = ConstU32<2>;
+    type MaxRevisions = ConstU32<3>;
+    type DeletionGracePeriod = ConstU64<10>;
+}
+
+parameter_types! {
+    pub const TokenLocation: MultiLocation = Here.into_location();
+    pub const RelayNetwork: NetworkId = NetworkId::Kusama;
+    pub UniversalLocation: InteriorMultiLocation = Parachain(MsgQueue::parachain_id().into()).into();
+    pub const UnitWeightCost: Weight = Weight::from_parts(1_000, 1_000);
+    pub TokensPerSecondPerByte: (AssetId, u128, u128) =
+        (Concrete(TokenLocation::get()), 1_000_000_000_000, 1024 * 1024);
+    pub const MaxInstructions: u32 = 100;
+    pub const MaxAssetsIntoHolding: u32 = 64;
+    /// Parachain 1 trusts parachain 2 and the other way round
+    pub TrustedTodoLocations: Vec<MultiLocation> = {
+        let sibling = if MsgQueue::parachain_id() == 1.into() { 2 } else { 1 };
+        vec![MultiLocation::new(1, X1(Parachain(sibling)))]
+    };
+}
+
+pub type LocationToAccountId = crate::LocationToAccountId<AccountId, RelayNetwork>;
+
+pub type LocalAssetTransactor =
+    XcmCurrencyAdapter<Balances, IsConcrete<TokenLocation>, LocationToAccountId, AccountId, ()>;
+
+pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
+
+pub type Barrier = TodoBarrier<TrustedTodoLocations, UniversalLocation>;
+
+pub struct XcmConfig;
+
+impl xcm_executor::Config for XcmConfig {
+    type RuntimeCall = RuntimeCall;
+    type XcmSender = XcmRouter;
+    type AssetTransactor = LocalAssetTransactor;
+    type OriginConverter = TodoOriginConverter<LocationToAccountId, RuntimeOrigin>;
+    type IsReserve = ();
+    type IsTeleporter = ();
+    type UniversalLocation = UniversalLocation;
+    type Barrier = Barrier;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type Trader = FixedRateOfFungible<TokensPerSecondPerByte, ()>;
+    type ResponseHandler = ();
+    type AssetTrap = ();
+    type AssetLocker = ();
+    type AssetExchanger = ();
+    type AssetClaims = ();
+    type SubscriptionService = ();
+    type PalletInstancesInfo = ();
+    type FeeManager = ();
+    type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
+    type MessageExporter = ();
+    type UniversalAliases = Nothing;
+    type CallDispatcher = RuntimeCall;
+    type SafeCallFilter = OnlyTodoCalls<Runtime>;
+}
+
+#[frame_support::pallet]
+pub mod mock_msg_queue {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_support::pallet_prelude::*;
+    use polkadot_core_primitives::BlockNumber as RelayBlockNumber;
+    use polkadot_parachain::primitives::{
+        DmpMessageHandler, Id as ParaId, XcmpMessageFormat, XcmpMessageHandler,
+    };
+    use sp_runtime::traits::Hash;
+    use xcm::VersionedXcm;
+    use xcm_executor::traits::ExecuteXcm;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type XcmExecutor: ExecuteXcm<Self::RuntimeCall>;
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {}
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::storage]
+    #[pallet::getter(fn parachain_id)]
+    pub(super) type ParachainId<T: Config> = StorageValue<_, ParaId, ValueQuery>;
+
+    impl<T: Config> Get<ParaId> for Pallet<T> {
+        fn get() -> ParaId {
+            Self::parachain_id()
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// An XCMP message was executed successfully
+        Success(Option<T::Hash>),
+        /// An XCMP message failed
+        Fail(Option<T::Hash>, XcmError),
+        /// An XCMP message used an unsupported XCM version
+        BadVersion(Option<T::Hash>),
+        /// A downward message is not valid XCM
+        InvalidFormat([u8; 32]),
+        /// A downward message used an unsupported XCM version
+        UnsupportedVersion([u8; 32]),
+        /// A downward message was executed with the given outcome
+        ExecutedDownward([u8; 32], Outcome),
+    }
+
+    impl<T: Config> Pallet<T> {
+        pub fn set_para_id(para_id: ParaId) {
+            ParachainId::<T>::put(para_id);
+        }
+
+        fn handle_xcmp_message(
+            sender: ParaId,
+            xcm: VersionedXcm<T::RuntimeCall>,
+            max_weight: Weight,
+        ) -> Result<Weight, XcmError> {
+            let hash = Encode::using_encoded(&xcm, T::Hashing::hash);
+            let message_hash = Encode::using_encoded(&xcm, sp_io::hashing::blake2_256);
+            let (result, event) = match Xcm::<T::RuntimeCall>::try_from(xcm) {
+                Ok(xcm) => {
+                    let location = (Parent, Parachain(sender.into()));
+                    match T::XcmExecutor::execute_xcm(location, xcm, message_hash, max_weight) {
+                        Outcome::Error(e) => (Err(e.clone()), Event::Fail(Some(hash), e)),
+                        Outcome::Complete(w) => (Ok(w), Event::Success(Some(hash))),
+                        Outcome::Incomplete(w, e) => (Ok(w), Event::Fail(Some(hash), e)),
+                    }
+                },
+                Err(()) => (Err(XcmError::UnhandledXcmVersion), Event::BadVersion(Some(hash))),
+            };
+            Self::deposit_event(event);
+            result
+        }
+    }
+
+    impl<T: Config> XcmpMessageHandler for Pallet<T> {
+        fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
+            iter: I,
+            max_weight: Weight,
+        ) -> Weight {
+            for (sender, _sent_at, data) in iter {
+                let mut data_ref = data;
+                let _ = XcmpMessageFormat::decode(&mut data_ref)
+                    .expect("Simulator encodes with versioned xcm format; qed");
+
+                let mut remaining_fragments = data_ref;
+                while !remaining_fragments.is_empty() {
+                    if let Ok(xcm) = VersionedXcm::<T::RuntimeCall>::decode(&mut remaining_fragments) {
+                        let _ = Self::handle_xcmp_message(sender, xcm, max_weight);
+                    } else {
+                        debug_assert!(false, "Invalid incoming XCMP message data");
+                    }
+                }
+            }
+            max_weight
+        }
+    }
+
+    impl<T: Config> DmpMessageHandler for Pallet<T> {
+        fn handle_dmp_messages(
+            iter: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>,
+            limit: Weight,
+        ) -> Weight {
+            for (_sent_at, data) in iter {
+                let id = sp_io::hashing::blake2_256(&data[..]);
+                match VersionedXcm::<T::RuntimeCall>::decode(&mut &data[..]) {
+                    Err(_) => Self::deposit_event(Event::InvalidFormat(id)),
+                    Ok(versioned) => match Xcm::try_from(versioned) {
+                        Err(()) => Self::deposit_event(Event::UnsupportedVersion(id)),
+                        Ok(xcm) => {
+                            let outcome = T::XcmExecutor::execute_xcm(Parent, xcm, id, limit);
+                            Self::deposit_event(Event::ExecutedDownward(id, outcome));
+                        },
+                    },
+                }
+            }
+            limit
+        }
+    }
+}
+
+impl mock_msg_queue::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+    type XcmRouter = XcmRouter;
+    type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+    type XcmExecuteFilter = Nothing;
+    type XcmExecutor = XcmExecutor<XcmConfig>;
+    type XcmTeleportFilter = Nothing;
+    type XcmReserveTransferFilter = Nothing;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+    type UniversalLocation = UniversalLocation;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+    type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+    type Currency = Balances;
+    type CurrencyMatcher = ();
+    type TrustedLockers = ();
+    type SovereignAccountOf = LocationToAccountId;
+    type MaxLockers = ConstU32<8>;
+    type MaxRemoteLockConsumers = ConstU32<0>;
+    type RemoteLockConsumerIdentifier = ();
+    type WeightInfo = pallet_xcm::TestWeightInfo;
+    type AdminOrigin = EnsureRoot<AccountId>;
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+    pub enum Runtime {
+        System: frame_system,
+        Balances: pallet_balances,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
+        MsgQueue: mock_msg_queue,
+        PolkadotXcm: pallet_xcm,
+        Todo: pallet_todo,
+    }
+);
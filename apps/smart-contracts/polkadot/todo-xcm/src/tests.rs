@@ -0,0 +1,165 @@
+use crate::mock::*;
+use codec::Encode;
+use frame_support::{assert_ok, weights::Weight};
+use pallet_todo::Priority;
+use xcm::latest::prelude::*;
+use xcm_simulator::TestExt;
+
+const FEE: u128 = 1_000_000_000_000;
+
+// Helper function to build a `create_todo` call for parachain 1
+fn create_todo_call(title: &[u8]) -> parachain::RuntimeCall {
+    parachain::RuntimeCall::Todo(pallet_todo::Call::create_todo {
+        title: title.to_vec(),
+        description: b"Sent from a sibling".to_vec(),
+        priority: Priority::Medium,
+    })
+}
+
+// Helper function to build a message that pays for and transacts `call` as its sender
+fn paid_transact(call: parachain::RuntimeCall) -> Xcm<()> {
+    Xcm(vec![
+        WithdrawAsset((Here, FEE).into()),
+        BuyExecution { fees: (Here, FEE).into(), weight_limit: Unlimited },
+        Transact {
+            origin_kind: OriginKind::SovereignAccount,
+            require_weight_at_most: Weight::from_parts(1_000_000_000, 1024 * 1024),
+            call: call.encode().into(),
+        },
+        RefundSurplus,
+        DepositAsset {
+            assets: All.into(),
+            beneficiary: AccountId32 { network: None, id: derived_account(2, &ALICE).into() }
+                .into(),
+        },
+    ])
+}
+
+// Helper function to send a message from Alice on parachain 2 to parachain 1
+fn send_from_para_b(message: Xcm<()>) {
+    ParaB::execute_with(|| {
+        assert_ok!(parachain::PolkadotXcm::send(
+            parachain::RuntimeOrigin::signed(ALICE),
+            Box::new((Parent, Parachain(1)).into()),
+            Box::new(VersionedXcm::V3(message)),
+        ));
+    });
+}
+
+// Helper function to read the outcome of the last XCMP message on parachain 1
+fn last_xcmp_event() -> Option<parachain::mock_msg_queue::Event<parachain::Runtime>> {
+    parachain::System::events().into_iter().rev().find_map(|record| match record.event {
+        parachain::RuntimeEvent::MsgQueue(event) => Some(event),
+        _ => None,
+    })
+}
+
+#[test]
+fn sibling_account_creates_and_completes_todo() {
+    MockNet::reset();
+
+    send_from_para_b(paid_transact(create_todo_call(b"Cross-chain todo")));
+
+    ParaA::execute_with(|| {
+        assert!(matches!(last_xcmp_event(), Some(parachain::mock_msg_queue::Event::Success(_))));
+
+        // The todo belongs to the account derived for Alice on parachain 2
+        let owner = derived_account(2, &ALICE);
+        let todos = pallet_todo::Todos::<parachain::Runtime>::get(&owner);
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title.to_vec(), b"Cross-chain todo".to_vec());
+        assert!(!todos[0].is_completed());
+
+        // Alice's own account on parachain 1 is untouched
+        assert!(pallet_todo::Todos::<parachain::Runtime>::get(&ALICE).is_empty());
+    });
+
+    let toggle = parachain::RuntimeCall::Todo(pallet_todo::Call::toggle_todo_completion { id: 0 });
+    send_from_para_b(paid_transact(toggle));
+
+    ParaA::execute_with(|| {
+        let todos = pallet_todo::Todos::<parachain::Runtime>::get(derived_account(2, &ALICE));
+        assert!(todos[0].is_completed());
+    });
+}
+
+#[test]
+fn derived_account_pays_for_execution() {
+    MockNet::reset();
+
+    let owner = ParaA::execute_with(|| {
+        let owner = derived_account(2, &ALICE);
+        assert_eq!(parachain::Balances::free_balance(&owner), INITIAL_BALANCE);
+        owner
+    });
+
+    send_from_para_b(paid_transact(create_todo_call(b"Paid for")));
+
+    ParaA::execute_with(|| {
+        let balance = parachain::Balances::free_balance(&owner);
+        assert!(balance < INITIAL_BALANCE);
+        assert!(balance > INITIAL_BALANCE - FEE);
+    });
+}
+
+#[test]
+fn unpaid_message_is_rejected_by_barrier() {
+    MockNet::reset();
+
+    send_from_para_b(Xcm(vec![Transact {
+        origin_kind: OriginKind::SovereignAccount,
+        require_weight_at_most: Weight::from_parts(1_000_000_000, 1024 * 1024),
+        call: create_todo_call(b"Free ride").encode().into(),
+    }]));
+
+    ParaA::execute_with(|| {
+        assert!(matches!(
+            last_xcmp_event(),
+            Some(parachain::mock_msg_queue::Event::Fail(_, XcmError::Barrier))
+        ));
+        assert!(pallet_todo::Todos::<parachain::Runtime>::get(derived_account(2, &ALICE)).is_empty());
+    });
+}
+
+#[test]
+fn non_todo_calls_are_filtered() {
+    MockNet::reset();
+
+    let transfer = parachain::RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+        dest: ALICE,
+        value: INITIAL_BALANCE / 2,
+    });
+    send_from_para_b(paid_transact(transfer));
+
+    ParaA::execute_with(|| {
+        assert!(matches!(
+            last_xcmp_event(),
+            Some(parachain::mock_msg_queue::Event::Fail(_, XcmError::NoPermission))
+        ));
+        assert_eq!(parachain::Balances::free_balance(&ALICE), INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn untrusted_relay_chain_is_rejected() {
+    MockNet::reset();
+
+    // The relay chain is not among parachain 1's trusted locations
+    Relay::execute_with(|| {
+        assert_ok!(relay_chain::XcmPallet::send_xcm(
+            Here,
+            Parachain(1),
+            paid_transact(create_todo_call(b"From the relay")),
+        ));
+    });
+
+    ParaA::execute_with(|| {
+        assert!(matches!(
+            last_xcmp_event(),
+            Some(parachain::mock_msg_queue::Event::ExecutedDownward(
+                _,
+                Outcome::Error(XcmError::Barrier)
+            ))
+        ));
+    });
+}